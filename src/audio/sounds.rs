@@ -114,6 +114,40 @@ impl ToneGenerator {
                 sample_rate,
             )),
 
+            // Warm-up End sounds (quiet rising nudge)
+            (SoundType::WarmUpEnd, NotificationStyle::Simple) => Box::new(Self::sine_wave(
+                frequencies::D4,
+                Duration::from_millis(400),
+                sample_rate,
+            )),
+            (SoundType::WarmUpEnd, NotificationStyle::Musical) => Box::new(Self::create_sequence(
+                &[frequencies::D4, frequencies::F4, frequencies::A4],
+                Duration::from_millis(250),
+                sample_rate,
+            )),
+            (SoundType::WarmUpEnd, NotificationStyle::Gentle) => Box::new(Self::create_sequence(
+                &[frequencies::D4, frequencies::F4],
+                Duration::from_millis(400),
+                sample_rate,
+            )),
+
+            // Overtime Start sounds (a single soft note)
+            (SoundType::OvertimeStart, NotificationStyle::Simple) => Box::new(Self::sine_wave(
+                frequencies::G4,
+                Duration::from_millis(300),
+                sample_rate,
+            )),
+            (SoundType::OvertimeStart, NotificationStyle::Musical) => Box::new(Self::create_chord(
+                &[frequencies::E4, frequencies::G4],
+                Duration::from_millis(500),
+                sample_rate,
+            )),
+            (SoundType::OvertimeStart, NotificationStyle::Gentle) => Box::new(Self::sine_wave(
+                frequencies::E4,
+                Duration::from_millis(500),
+                sample_rate,
+            )),
+
             // Test sound
             (SoundType::Test, _) => Box::new(Self::sine_wave(
                 frequencies::A4,
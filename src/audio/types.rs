@@ -16,6 +16,10 @@ pub enum SoundType {
     LongBreakStart,
     /// Work session started (optional)
     SessionStart,
+    /// Warm-up finished (soft nudge towards a full pomodoro)
+    WarmUpEnd,
+    /// Pomodoro ran out and keeps counting (quiet cue that doesn't break focus)
+    OvertimeStart,
     /// Test/preview sound
     Test,
 }
@@ -0,0 +1,277 @@
+//! Command-line argument parsing
+
+use crate::core::config::{Config, LongBreakRule, SessionMode};
+use crate::core::deadline;
+use chrono::NaiveTime;
+use thiserror::Error;
+
+pub const USAGE: &str = "\
+Usage: pomo-tui [OPTIONS]
+
+Options:
+  -w, --work <MIN>               Pomodoro length in minutes
+  -s, --short-break <MIN>        Short break length in minutes
+  -l, --long-break <MIN>         Long break length in minutes
+      --long-break-every <N>     Take a long break every N pomodoros
+      --long-break-after <MIN>   Take a long break after MIN minutes of focus
+      --flowtime                 Open-ended work with proportional breaks
+      --plan <NAME>              Follow a named session plan from the config
+      --warm-up                  Start with a short warm-up before the first
+                                 pomodoro
+      --overtime                 Keep counting past the end of a pomodoro
+      --until <HH:MM>            Fit pomodoros and breaks up to a deadline
+      --time-warp <FACTOR>       Run the clock FACTOR times faster, up to 3600
+                                 (demo mode, history is not saved)
+      --save                     Save the given options to the config file
+  -h, --help                     Print this help
+  -V, --version                  Print version";
+
+/// Fastest `--time-warp` - an hour a second, well short of where the sped-up
+/// clock would overflow
+pub const MAX_TIME_WARP: f64 = 3600.0;
+
+/// Command-line errors
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("Unknown option: {0}")]
+    UnknownOption(String),
+
+    #[error("Missing value for {0}")]
+    MissingValue(String),
+
+    #[error("Invalid value for {0}: {1}")]
+    InvalidValue(String, String),
+}
+
+/// Options passed on the command line
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CliArgs {
+    pub work_minutes: Option<u64>,
+    pub short_break_minutes: Option<u64>,
+    pub long_break_minutes: Option<u64>,
+    pub long_break_rule: Option<LongBreakRule>,
+    pub flowtime: bool,
+    pub plan: Option<String>,
+    pub warm_up: bool,
+    pub overtime: bool,
+    pub until: Option<NaiveTime>,
+    pub time_warp: Option<f64>,
+    pub save: bool,
+    pub help: bool,
+    pub version: bool,
+}
+
+impl CliArgs {
+    /// Parse arguments, excluding the program name
+    pub fn parse<I, S>(args: I) -> Result<Self, CliError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter().map(Into::into);
+
+        while let Some(arg) = args.next() {
+            // Support both "--work 50" and "--work=50"
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg.clone(), None),
+            };
+
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| CliError::MissingValue(flag.clone()))
+            };
+
+            match flag.as_str() {
+                "-w" | "--work" => parsed.work_minutes = Some(parse_number(&flag, &value()?)?),
+                "-s" | "--short-break" => {
+                    parsed.short_break_minutes = Some(parse_number(&flag, &value()?)?)
+                }
+                "-l" | "--long-break" => {
+                    parsed.long_break_minutes = Some(parse_number(&flag, &value()?)?)
+                }
+                "--long-break-every" => {
+                    let sessions = parse_number(&flag, &value()?)?;
+                    let sessions = u32::try_from(sessions)
+                        .map_err(|_| CliError::InvalidValue(flag.clone(), sessions.to_string()))?;
+                    parsed.long_break_rule = Some(LongBreakRule::EverySessions(sessions));
+                }
+                "--long-break-after" => {
+                    let minutes = parse_number(&flag, &value()?)?;
+                    parsed.long_break_rule = Some(LongBreakRule::AfterFocusMinutes(minutes));
+                }
+                "--flowtime" => parsed.flowtime = true,
+                "--plan" => parsed.plan = Some(value()?),
+                "--warm-up" => parsed.warm_up = true,
+                "--overtime" => parsed.overtime = true,
+                "--until" => {
+                    let raw = value()?;
+                    let time = deadline::parse_time(&raw)
+                        .ok_or_else(|| CliError::InvalidValue(flag.clone(), raw))?;
+                    parsed.until = Some(time);
+                }
+                "--time-warp" => {
+                    let raw = value()?;
+                    let factor: f64 = raw
+                        .parse()
+                        .map_err(|_| CliError::InvalidValue(flag.clone(), raw.clone()))?;
+                    if !(factor > 0.0 && factor <= MAX_TIME_WARP) {
+                        return Err(CliError::InvalidValue(flag.clone(), raw));
+                    }
+                    parsed.time_warp = Some(factor);
+                }
+                "--save" => parsed.save = true,
+                "-h" | "--help" => parsed.help = true,
+                "-V" | "--version" => parsed.version = true,
+                _ => return Err(CliError::UnknownOption(arg)),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Override config values with the ones given on the command line
+    pub fn apply(&self, config: &mut Config) {
+        if let Some(minutes) = self.work_minutes {
+            config.work_minutes = minutes;
+        }
+        if let Some(minutes) = self.short_break_minutes {
+            config.short_break_minutes = minutes;
+        }
+        if let Some(minutes) = self.long_break_minutes {
+            config.long_break_minutes = minutes;
+        }
+        if let Some(rule) = self.long_break_rule {
+            config.long_break_rule = rule;
+        }
+        if self.flowtime {
+            config.mode = SessionMode::Flowtime;
+        }
+        if let Some(ref plan) = self.plan {
+            config.plan = Some(plan.clone());
+        }
+        if self.warm_up {
+            config.warm_up = true;
+        }
+        if self.overtime {
+            config.overtime = true;
+        }
+    }
+}
+
+fn parse_number(flag: &str, value: &str) -> Result<u64, CliError> {
+    value
+        .parse()
+        .map_err(|_| CliError::InvalidValue(flag.to_string(), value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_durations() {
+        let args = CliArgs::parse(["--work", "50", "-s", "10", "--long-break=30"]).unwrap();
+        assert_eq!(args.work_minutes, Some(50));
+        assert_eq!(args.short_break_minutes, Some(10));
+        assert_eq!(args.long_break_minutes, Some(30));
+        assert!(!args.save);
+    }
+
+    #[test]
+    fn test_parse_long_break_rules() {
+        let args = CliArgs::parse(["--long-break-every", "2"]).unwrap();
+        assert_eq!(args.long_break_rule, Some(LongBreakRule::EverySessions(2)));
+
+        let args = CliArgs::parse(["--long-break-after", "180"]).unwrap();
+        assert_eq!(
+            args.long_break_rule,
+            Some(LongBreakRule::AfterFocusMinutes(180))
+        );
+    }
+
+    #[test]
+    fn test_parse_time_warp() {
+        let args = CliArgs::parse(["--time-warp=60"]).unwrap();
+        assert_eq!(args.time_warp, Some(60.0));
+        assert!(matches!(
+            CliArgs::parse(["--time-warp", "0"]),
+            Err(CliError::InvalidValue(_, _))
+        ));
+        assert_eq!(
+            CliArgs::parse(["--time-warp", "3600"]).unwrap().time_warp,
+            Some(MAX_TIME_WARP)
+        );
+        assert!(matches!(
+            CliArgs::parse(["--time-warp", "1e300"]),
+            Err(CliError::InvalidValue(_, _))
+        ));
+        assert!(matches!(
+            CliArgs::parse(["--time-warp", "inf"]),
+            Err(CliError::InvalidValue(_, _))
+        ));
+        assert!(matches!(
+            CliArgs::parse(["--time-warp", "fast"]),
+            Err(CliError::InvalidValue(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_parse_until() {
+        let args = CliArgs::parse(["--until", "12:30"]).unwrap();
+        assert_eq!(args.until, NaiveTime::from_hms_opt(12, 30, 0));
+        assert!(matches!(
+            CliArgs::parse(["--until=noon"]),
+            Err(CliError::InvalidValue(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            CliArgs::parse(["--bogus"]),
+            Err(CliError::UnknownOption(_))
+        ));
+        assert!(matches!(
+            CliArgs::parse(["--work"]),
+            Err(CliError::MissingValue(_))
+        ));
+        assert!(matches!(
+            CliArgs::parse(["--work", "ten"]),
+            Err(CliError::InvalidValue(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_apply_overrides_config() {
+        let mut config = Config::default();
+        let args = CliArgs::parse([
+            "-w",
+            "90",
+            "-l",
+            "20",
+            "--long-break-every",
+            "2",
+            "--overtime",
+            "--flowtime",
+            "--plan=deep work",
+            "--warm-up",
+        ])
+        .unwrap();
+        args.apply(&mut config);
+
+        assert_eq!(config.work_minutes, 90);
+        assert_eq!(config.short_break_minutes, 5);
+        assert_eq!(config.long_break_minutes, 20);
+        assert_eq!(config.long_break_rule, LongBreakRule::EverySessions(2));
+        assert!(config.overtime);
+        assert_eq!(config.mode, SessionMode::Flowtime);
+        assert_eq!(config.plan.as_deref(), Some("deep work"));
+        assert!(config.warm_up);
+    }
+}
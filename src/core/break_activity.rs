@@ -1,4 +1,5 @@
-use std::time::Duration;
+use super::clock::Clock;
+use std::time::{Duration, Instant};
 
 /// Different activities available during breaks
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -34,6 +35,23 @@ impl BreakActivity {
         }
     }
 
+    /// Stable identifier used when persisting the activity
+    pub fn key(&self) -> &'static str {
+        match self {
+            BreakActivity::Breathing => "breathing",
+            BreakActivity::Stretch => "stretch",
+        }
+    }
+
+    /// Parse an identifier produced by [`BreakActivity::key`]
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "breathing" => Some(BreakActivity::Breathing),
+            "stretch" => Some(BreakActivity::Stretch),
+            _ => None,
+        }
+    }
+
     /// Whether this activity has a visual animation
     pub fn has_animation(&self) -> bool {
         match self {
@@ -49,6 +67,7 @@ pub struct BreakAnimation {
     phase_elapsed: Duration,
     total_elapsed: Duration,
     animation_frame: u32,
+    last_tick: Option<Instant>,
 }
 
 impl BreakAnimation {
@@ -59,6 +78,7 @@ impl BreakAnimation {
             phase_elapsed: Duration::ZERO,
             total_elapsed: Duration::ZERO,
             animation_frame: 0,
+            last_tick: None,
         }
     }
 
@@ -71,9 +91,10 @@ impl BreakAnimation {
         match self.activity {
             BreakActivity::Stretch => {
                 // Stretch animation updates every 1000ms
-                if self.phase_elapsed >= Duration::from_millis(1000) {
+                let frame_duration = Duration::from_millis(1000);
+                while self.phase_elapsed >= frame_duration {
                     self.animation_frame = (self.animation_frame + 1) % 4; // 4 stretch positions
-                    self.phase_elapsed = Duration::ZERO;
+                    self.phase_elapsed -= frame_duration;
                 }
             }
             BreakActivity::Breathing => {
@@ -82,6 +103,20 @@ impl BreakAnimation {
         }
     }
 
+    /// Advance by however much time passed on `clock` since the last tick
+    pub fn tick(&mut self, clock: &dyn Clock) {
+        let now = clock.now();
+        if let Some(last) = self.last_tick {
+            self.update(now.saturating_duration_since(last));
+        }
+        self.last_tick = Some(now);
+    }
+
+    /// Forget the last tick, so time spent paused isn't counted
+    pub fn stop_ticking(&mut self) {
+        self.last_tick = None;
+    }
+
     /// Get current animation frame
     pub fn get_frame(&self) -> u32 {
         self.animation_frame
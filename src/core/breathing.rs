@@ -1,4 +1,5 @@
-use std::time::Duration;
+use super::clock::Clock;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum BreathingPattern {
@@ -8,6 +9,38 @@ pub enum BreathingPattern {
     Simple,         // 4-0-4-0 breathing
 }
 
+impl BreathingPattern {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            BreathingPattern::ExtendedExhale => "Extended Exhale (3-6)",
+            BreathingPattern::Coherent => "Coherent Breathing (5-5)",
+            BreathingPattern::ShortBox => "Short Box (3-3-3-3)",
+            BreathingPattern::Simple => "Simple Breathing (4-4)",
+        }
+    }
+
+    /// Stable identifier used when persisting the pattern
+    pub fn key(&self) -> &'static str {
+        match self {
+            BreathingPattern::ExtendedExhale => "extended_exhale",
+            BreathingPattern::Coherent => "coherent",
+            BreathingPattern::ShortBox => "short_box",
+            BreathingPattern::Simple => "simple",
+        }
+    }
+
+    /// Parse an identifier produced by [`BreathingPattern::key`]
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "extended_exhale" => Some(BreathingPattern::ExtendedExhale),
+            "coherent" => Some(BreathingPattern::Coherent),
+            "short_box" => Some(BreathingPattern::ShortBox),
+            "simple" => Some(BreathingPattern::Simple),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BreathingExercise {
     pattern: BreathingPattern,
@@ -18,6 +51,7 @@ pub struct BreathingExercise {
     target_cycles: u32,
     post_exhale_transition: bool, // Track if we're in post-exhale transition
     pub ready_to_complete: bool, // Set when we've reached target cycles and should complete on next valid phase
+    last_tick: Option<Instant>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -45,6 +79,7 @@ impl BreathingExercise {
             target_cycles,
             post_exhale_transition: false,
             ready_to_complete: false,
+            last_tick: None,
         }
     }
 
@@ -81,13 +116,35 @@ impl BreathingExercise {
         self.phase_elapsed += delta;
         self.total_elapsed += delta;
 
-        let phase_duration = self.get_phase_duration();
-
-        if self.phase_elapsed >= phase_duration {
+        // A long delta (a stalled loop, time warp) can span several phases
+        loop {
+            let phase_duration = self.get_phase_duration();
+            if self.phase_elapsed < phase_duration {
+                break;
+            }
+            let carry = self.phase_elapsed - phase_duration;
             self.advance_phase();
+            self.phase_elapsed = carry;
+            if phase_duration.is_zero() {
+                break;
+            }
         }
     }
 
+    /// Advance by however much time passed on `clock` since the last tick
+    pub fn tick(&mut self, clock: &dyn Clock) {
+        let now = clock.now();
+        if let Some(last) = self.last_tick {
+            self.update(now.saturating_duration_since(last));
+        }
+        self.last_tick = Some(now);
+    }
+
+    /// Forget the last tick, so time spent paused isn't counted
+    pub fn stop_ticking(&mut self) {
+        self.last_tick = None;
+    }
+
     fn get_phase_duration(&self) -> Duration {
         match self.pattern {
             BreathingPattern::ExtendedExhale => match self.current_phase {
@@ -295,12 +352,7 @@ impl BreathingExercise {
     }
 
     pub fn get_pattern_name(&self) -> &str {
-        match self.pattern {
-            BreathingPattern::ExtendedExhale => "Extended Exhale (3-6)",
-            BreathingPattern::Coherent => "Coherent Breathing (5-5)",
-            BreathingPattern::ShortBox => "Short Box (3-3-3-3)",
-            BreathingPattern::Simple => "Simple Breathing (4-4)",
-        }
+        self.pattern.display_name()
     }
 
     pub fn get_cycle_count(&self) -> u32 {
@@ -344,6 +396,7 @@ impl BreathingExercise {
         self.cycle_count = 0;
         self.post_exhale_transition = false;
         self.ready_to_complete = false;
+        self.last_tick = None;
         // Keep target_cycles unchanged when resetting
     }
 
@@ -363,6 +416,7 @@ impl BreathingExercise {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::clock::ManualClock;
 
     #[test]
     fn test_breathing_exercise_ends_on_exhale_extended_exhale() {
@@ -644,4 +698,41 @@ mod tests {
         assert_eq!(exercise.get_remaining_cycles(), 5);
         assert_eq!(exercise.get_current_phase(), BreathPhase::Inhale);
     }
+
+    #[test]
+    fn test_long_delta_spans_several_phases() {
+        // 5s inhale + 1s transition + 5s exhale + 1s transition
+        let mut stepped = BreathingExercise::new(BreathingPattern::Coherent);
+        for _ in 0..65 {
+            stepped.update(Duration::from_millis(100));
+        }
+        let mut jumped = BreathingExercise::new(BreathingPattern::Coherent);
+        jumped.update(Duration::from_millis(6500));
+
+        assert_eq!(jumped.get_current_phase(), BreathPhase::Exhale);
+        assert_eq!(jumped.get_current_phase(), stepped.get_current_phase());
+        assert_eq!(jumped.get_remaining_in_phase(), Duration::from_millis(4500));
+
+        jumped.update(Duration::from_secs(12 * 2));
+        assert_eq!(jumped.get_cycle_count(), 2);
+    }
+
+    #[test]
+    fn test_tick_follows_clock() {
+        let clock = ManualClock::new();
+        let mut exercise = BreathingExercise::new(BreathingPattern::Simple);
+
+        // The first tick only sets the starting point
+        exercise.tick(&clock);
+        clock.advance(Duration::from_millis(4500));
+        exercise.tick(&clock);
+        assert_eq!(exercise.get_current_phase(), BreathPhase::Transition);
+        assert_eq!(exercise.get_total_elapsed(), Duration::from_millis(4500));
+
+        // Time while stopped is skipped
+        exercise.stop_ticking();
+        clock.advance(Duration::from_secs(60));
+        exercise.tick(&clock);
+        assert_eq!(exercise.get_total_elapsed(), Duration::from_millis(4500));
+    }
 }
@@ -0,0 +1,51 @@
+//! Energy and mood check-ins, each a level from 1 to 5, taken as a pomodoro
+//! starts and ends
+
+/// Highest level of energy or mood
+pub const CHECK_IN_MAX: u8 = 5;
+
+/// How someone felt at one point in a pomodoro
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckIn {
+    pub energy: u8,
+    pub mood: u8,
+}
+
+impl CheckIn {
+    /// Rebuild a stored check-in, if both levels are there and in range
+    pub fn from_levels(energy: Option<u8>, mood: Option<u8>) -> Option<Self> {
+        let (energy, mood) = (energy?, mood?);
+        (Self::is_level(energy) && Self::is_level(mood)).then_some(Self { energy, mood })
+    }
+
+    /// Level for a key press, `'1'` to `'5'`
+    pub fn level_for_key(key: char) -> Option<u8> {
+        let level = key.to_digit(10)? as u8;
+        Self::is_level(level).then_some(level)
+    }
+
+    fn is_level(level: u8) -> bool {
+        (1..=CHECK_IN_MAX).contains(&level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels_run_from_one_to_five() {
+        assert_eq!(CheckIn::level_for_key('1'), Some(1));
+        assert_eq!(CheckIn::level_for_key('5'), Some(5));
+        assert_eq!(CheckIn::level_for_key('0'), None);
+        assert_eq!(CheckIn::level_for_key('6'), None);
+        assert_eq!(CheckIn::level_for_key('x'), None);
+
+        assert_eq!(
+            CheckIn::from_levels(Some(2), Some(4)),
+            Some(CheckIn { energy: 2, mood: 4 })
+        );
+        assert_eq!(CheckIn::from_levels(Some(2), None), None);
+        assert_eq!(CheckIn::from_levels(Some(9), Some(4)), None);
+    }
+}
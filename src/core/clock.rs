@@ -0,0 +1,178 @@
+//! Time sources for timers and break activities
+
+use chrono::{DateTime, Local};
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Source of the current time
+pub trait Clock: Debug + Send + Sync {
+    /// Monotonic time, used for measuring durations
+    fn now(&self) -> Instant;
+
+    /// Wall-clock time matching [`Clock::now`], used for history timestamps
+    fn wall_now(&self) -> DateTime<Local>;
+
+    /// How many seconds pass on this clock per real second
+    fn speed(&self) -> f64 {
+        1.0
+    }
+}
+
+/// The real system clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl SystemClock {
+    /// Shared system clock, the default for timers
+    pub fn shared() -> Arc<dyn Clock> {
+        Arc::new(SystemClock)
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn wall_now(&self) -> DateTime<Local> {
+        Local::now()
+    }
+}
+
+/// Clock that only moves when told to, for tests
+#[derive(Debug)]
+pub struct ManualClock {
+    started: Instant,
+    wall_started: DateTime<Local>,
+    offset: Mutex<Duration>,
+    /// Wall-clock time the monotonic clock didn't see
+    suspended: Mutex<Duration>,
+    /// How far the wall clock was set back
+    set_back: Mutex<Duration>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            wall_started: Local::now(),
+            offset: Mutex::new(Duration::ZERO),
+            suspended: Mutex::new(Duration::ZERO),
+            set_back: Mutex::new(Duration::ZERO),
+        }
+    }
+
+    /// Move the clock forward by `delta`
+    pub fn advance(&self, delta: Duration) {
+        let mut offset = self.offset.lock().unwrap_or_else(|e| e.into_inner());
+        *offset += delta;
+    }
+
+    /// Move only the wall clock forward, as a suspend does on Linux
+    pub fn suspend(&self, delta: Duration) {
+        let mut suspended = self.suspended.lock().unwrap_or_else(|e| e.into_inner());
+        *suspended += delta;
+    }
+
+    /// Move only the wall clock back, as changing the system time does
+    pub fn set_wall_back(&self, delta: Duration) {
+        let mut set_back = self.set_back.lock().unwrap_or_else(|e| e.into_inner());
+        *set_back += delta;
+    }
+
+    fn offset(&self) -> Duration {
+        *self.offset.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn suspended(&self) -> Duration {
+        *self.suspended.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn set_back(&self) -> Duration {
+        *self.set_back.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.started + self.offset()
+    }
+
+    fn wall_now(&self) -> DateTime<Local> {
+        self.wall_started
+            + chrono::Duration::from_std(self.offset() + self.suspended()).unwrap_or_default()
+            - chrono::Duration::from_std(self.set_back()).unwrap_or_default()
+    }
+}
+
+/// Real time sped up by a constant factor ("time warp")
+#[derive(Debug, Clone)]
+pub struct VirtualClock {
+    started: Instant,
+    wall_started: DateTime<Local>,
+    speed: f64,
+}
+
+impl VirtualClock {
+    /// Clock running `speed` times faster than real time from now on
+    pub fn new(speed: f64) -> Self {
+        Self {
+            started: Instant::now(),
+            wall_started: Local::now(),
+            speed,
+        }
+    }
+
+    fn virtual_elapsed(&self) -> Duration {
+        self.started.elapsed().mul_f64(self.speed)
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> Instant {
+        self.started + self.virtual_elapsed()
+    }
+
+    fn wall_now(&self) -> DateTime<Local> {
+        self.wall_started + chrono::Duration::from_std(self.virtual_elapsed()).unwrap_or_default()
+    }
+
+    fn speed(&self) -> f64 {
+        self.speed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock_only_moves_when_advanced() {
+        let clock = ManualClock::new();
+        let start = clock.now();
+        let wall_start = clock.wall_now();
+        assert_eq!(clock.now(), start);
+
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(clock.now() - start, Duration::from_secs(90));
+        assert_eq!((clock.wall_now() - wall_start).num_seconds(), 90);
+        assert_eq!(clock.speed(), 1.0);
+    }
+
+    #[test]
+    fn test_virtual_clock_runs_faster() {
+        let clock = VirtualClock::new(1000.0);
+        let start = clock.now();
+        std::thread::sleep(Duration::from_millis(5));
+        // At least 5 virtual seconds for 5 real milliseconds
+        assert!(clock.now() - start >= Duration::from_secs(5));
+        assert_eq!(clock.speed(), 1000.0);
+    }
+}
@@ -0,0 +1,670 @@
+//! Session configuration and persistence
+
+use super::plan::SessionPlan;
+use super::session::PhaseKind;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Longest phase we accept from config or CLI (8 hours)
+const MAX_PHASE_MINUTES: u64 = 8 * 60;
+
+/// Warm-ups are meant to be short - long enough to open the file, no more
+const WARM_UP_MINUTES: std::ops::RangeInclusive<u64> = 2..=5;
+
+/// Long enough to notice and press a key, short enough to not need one
+const AUTO_START_GRACE_SECONDS: std::ops::RangeInclusive<u64> = 10..=30;
+
+/// Configuration-related errors
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Invalid configuration: {0}")]
+    Invalid(String),
+
+    #[error("Configuration error: {0}")]
+    Io(String),
+}
+
+/// Rule deciding when a break becomes a long break
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum LongBreakRule {
+    /// Long break after every N completed pomodoros
+    EverySessions(u32),
+    /// Long break once this many minutes of focus have accumulated
+    AfterFocusMinutes(u64),
+}
+
+impl LongBreakRule {
+    /// Whether the break that follows should be a long one
+    pub fn is_long_break(&self, session_count: u32, focus_since_long_break: Duration) -> bool {
+        match *self {
+            LongBreakRule::EverySessions(n) => n > 0 && session_count % n == 0,
+            LongBreakRule::AfterFocusMinutes(minutes) => {
+                focus_since_long_break >= Duration::from_secs(minutes * 60)
+            }
+        }
+    }
+}
+
+/// How long work phases last
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionMode {
+    /// Fixed-length pomodoros
+    #[default]
+    Pomodoro,
+    /// Open-ended work, followed by a break proportional to the time worked
+    Flowtime,
+}
+
+/// How long a break is after a flowtime work phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FlowtimeBreaks {
+    /// Minutes of work per minute of break
+    pub ratio: u32,
+    /// Shortest break in minutes
+    pub min_minutes: u64,
+    /// Longest break in minutes
+    pub max_minutes: u64,
+}
+
+impl Default for FlowtimeBreaks {
+    fn default() -> Self {
+        Self {
+            ratio: 5,
+            min_minutes: 5,
+            max_minutes: 30,
+        }
+    }
+}
+
+impl FlowtimeBreaks {
+    /// Break earned by working for `worked`
+    pub fn break_for(&self, worked: Duration) -> Duration {
+        let min = Duration::from_secs(self.min_minutes * 60);
+        let max = Duration::from_secs(self.max_minutes * 60);
+        (worked / self.ratio.max(1)).clamp(min, max.max(min))
+    }
+}
+
+/// What happens when a pause goes on for the configured maximum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseLimitAction {
+    /// Play a reminder and keep waiting
+    #[default]
+    Remind,
+    /// Throw the pomodoro away, as the classic rules say
+    Void,
+}
+
+/// Which phases start by themselves once the one before is over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoStart {
+    /// Start breaks (with the highlighted activity) without waiting for Space
+    pub breaks: bool,
+    /// Start the next pomodoro when a break is over
+    pub pomodoros: bool,
+    /// Countdown before starting, during which any key cancels
+    pub grace_seconds: u64,
+}
+
+impl Default for AutoStart {
+    fn default() -> Self {
+        Self {
+            breaks: false,
+            pomodoros: false,
+            grace_seconds: 15,
+        }
+    }
+}
+
+impl AutoStart {
+    /// Whether a phase of this kind starts by itself
+    pub fn applies_to(&self, kind: PhaseKind) -> bool {
+        match kind {
+            PhaseKind::Pomodoro => self.pomodoros,
+            PhaseKind::ShortBreak | PhaseKind::LongBreak => self.breaks,
+            PhaseKind::WarmUp => false,
+        }
+    }
+
+    pub fn grace(&self) -> Duration {
+        Duration::from_secs(self.grace_seconds)
+    }
+}
+
+/// When to step in on long stretches of work without a real break
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HyperfocusGuard {
+    pub enabled: bool,
+    /// Minutes of focus before a gentle note
+    pub note_minutes: u64,
+    /// Minutes of focus before the break screen
+    pub block_minutes: u64,
+    /// Breaks skipped in a row before the break screen, one fewer gets a note
+    pub max_skipped_breaks: u32,
+    /// Seconds before the break screen can be dismissed
+    pub dismiss_after_seconds: u64,
+}
+
+impl Default for HyperfocusGuard {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            note_minutes: 90,
+            block_minutes: 150,
+            max_skipped_breaks: 3,
+            dismiss_after_seconds: 60,
+        }
+    }
+}
+
+impl HyperfocusGuard {
+    pub fn note_after(&self) -> Duration {
+        Duration::from_secs(self.note_minutes * 60)
+    }
+
+    pub fn block_after(&self) -> Duration {
+        Duration::from_secs(self.block_minutes * 60)
+    }
+
+    pub fn dismiss_after(&self) -> Duration {
+        Duration::from_secs(self.dismiss_after_seconds)
+    }
+}
+
+/// Session durations and cycle settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Length of a pomodoro in minutes
+    pub work_minutes: u64,
+    /// Length of a short break in minutes
+    pub short_break_minutes: u64,
+    /// Length of a long break in minutes
+    pub long_break_minutes: u64,
+    /// When to take a long break instead of a short one
+    pub long_break_rule: LongBreakRule,
+    /// Fixed pomodoros or open-ended flowtime
+    pub mode: SessionMode,
+    /// Break lengths in flowtime mode
+    pub flowtime: FlowtimeBreaks,
+    /// Named phase sequences to choose from
+    pub plans: Vec<SessionPlan>,
+    /// Plan to follow instead of the standard cycle
+    pub plan: Option<String>,
+    /// Keep counting past the end of a pomodoro until you move on
+    pub overtime: bool,
+    /// Minutes between reminders while in overtime, 0 for none
+    pub overtime_reminder_minutes: u64,
+    /// Longest a pause may last in minutes, 0 for no limit
+    pub max_pause_minutes: u64,
+    /// What to do once a pause reaches `max_pause_minutes`
+    pub on_max_pause: PauseLimitAction,
+    /// Start the day with a short warm-up before the first pomodoro
+    pub warm_up: bool,
+    /// Length of the warm-up in minutes
+    pub warm_up_minutes: u64,
+    /// Ask what each pomodoro is for before it starts
+    pub ask_intention: bool,
+    /// Ask where things were left off when a pomodoro ends, and show it
+    /// when the next one starts
+    pub ask_breadcrumb: bool,
+    /// Ask for energy and mood, 1 to 5, as each pomodoro starts and ends
+    pub check_in: bool,
+    /// Phases that start by themselves after a short countdown
+    pub auto_start: AutoStart,
+    /// Steps in when work goes on too long without a real break
+    pub hyperfocus: HyperfocusGuard,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            work_minutes: 25,
+            short_break_minutes: 5,
+            long_break_minutes: 15,
+            long_break_rule: LongBreakRule::EverySessions(4),
+            mode: SessionMode::Pomodoro,
+            flowtime: FlowtimeBreaks::default(),
+            plans: SessionPlan::presets(),
+            plan: None,
+            overtime: false,
+            overtime_reminder_minutes: 5,
+            max_pause_minutes: 10,
+            on_max_pause: PauseLimitAction::Remind,
+            warm_up: false,
+            warm_up_minutes: 3,
+            ask_intention: false,
+            ask_breadcrumb: false,
+            check_in: false,
+            auto_start: AutoStart::default(),
+            hyperfocus: HyperfocusGuard::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Load configuration from disk, or return default if not found
+    pub fn load() -> Result<Self, ConfigError> {
+        let config_path = Self::config_path()?;
+
+        if !config_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&config_path)
+            .map_err(|e| ConfigError::Io(format!("Failed to read config: {}", e)))?;
+
+        let config: Self = serde_json::from_str(&content)
+            .map_err(|e| ConfigError::Invalid(format!("Failed to parse config: {}", e)))?;
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Save configuration to disk
+    pub fn save(&self) -> Result<(), ConfigError> {
+        self.validate()?;
+        let config_path = Self::config_path()?;
+
+        if let Some(parent) = config_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| ConfigError::Io(format!("Failed to create config dir: {}", e)))?;
+        }
+
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| ConfigError::Io(format!("Failed to serialize config: {}", e)))?;
+
+        fs::write(&config_path, content)
+            .map_err(|e| ConfigError::Io(format!("Failed to write config: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Get the path to the configuration file
+    pub fn config_path() -> Result<PathBuf, ConfigError> {
+        let mut path = dirs::config_dir()
+            .ok_or_else(|| ConfigError::Io("No config directory found".to_string()))?;
+
+        path.push("pomo-tui");
+        path.push("config.json");
+
+        Ok(path)
+    }
+
+    /// Check that all values are usable
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        check_minutes("work_minutes", self.work_minutes)?;
+        check_minutes("short_break_minutes", self.short_break_minutes)?;
+        check_minutes("long_break_minutes", self.long_break_minutes)?;
+
+        if self.overtime_reminder_minutes > MAX_PHASE_MINUTES {
+            return Err(ConfigError::Invalid(format!(
+                "overtime_reminder_minutes must be at most {} minutes",
+                MAX_PHASE_MINUTES
+            )));
+        }
+
+        if self.max_pause_minutes > MAX_PHASE_MINUTES {
+            return Err(ConfigError::Invalid(format!(
+                "max_pause_minutes must be at most {} minutes",
+                MAX_PHASE_MINUTES
+            )));
+        }
+
+        check_warm_up_minutes("warm_up_minutes", self.warm_up_minutes)?;
+
+        if !AUTO_START_GRACE_SECONDS.contains(&self.auto_start.grace_seconds) {
+            return Err(ConfigError::Invalid(format!(
+                "auto_start grace_seconds must be between {} and {} seconds (got {})",
+                AUTO_START_GRACE_SECONDS.start(),
+                AUTO_START_GRACE_SECONDS.end(),
+                self.auto_start.grace_seconds
+            )));
+        }
+
+        check_minutes("hyperfocus note_minutes", self.hyperfocus.note_minutes)?;
+        check_minutes("hyperfocus block_minutes", self.hyperfocus.block_minutes)?;
+        if self.hyperfocus.block_minutes < self.hyperfocus.note_minutes {
+            return Err(ConfigError::Invalid(
+                "hyperfocus block_minutes must not be shorter than note_minutes".to_string(),
+            ));
+        }
+        if self.hyperfocus.max_skipped_breaks == 0 {
+            return Err(ConfigError::Invalid(
+                "hyperfocus max_skipped_breaks must be at least 1".to_string(),
+            ));
+        }
+
+        if self.flowtime.ratio == 0 {
+            return Err(ConfigError::Invalid(
+                "flowtime break ratio must be at least 1".to_string(),
+            ));
+        }
+        check_minutes("flowtime min_minutes", self.flowtime.min_minutes)?;
+        check_minutes("flowtime max_minutes", self.flowtime.max_minutes)?;
+        if self.flowtime.max_minutes < self.flowtime.min_minutes {
+            return Err(ConfigError::Invalid(
+                "flowtime max_minutes must not be shorter than min_minutes".to_string(),
+            ));
+        }
+
+        for (index, plan) in self.plans.iter().enumerate() {
+            if plan.name.trim().is_empty() {
+                return Err(ConfigError::Invalid(
+                    "plan names must not be empty".to_string(),
+                ));
+            }
+            if plan.is_empty() {
+                return Err(ConfigError::Invalid(format!(
+                    "plan '{}' needs at least one phase",
+                    plan.name
+                )));
+            }
+            if self.plans[..index]
+                .iter()
+                .any(|other| other.name == plan.name)
+            {
+                return Err(ConfigError::Invalid(format!(
+                    "plan '{}' is defined twice",
+                    plan.name
+                )));
+            }
+            for phase in &plan.phases {
+                check_minutes(&format!("plan '{}' phase", plan.name), phase.minutes)?;
+            }
+            if let Some(minutes) = plan.warm_up_minutes {
+                check_warm_up_minutes(&format!("plan '{}' warm_up_minutes", plan.name), minutes)?;
+            }
+        }
+        if let Some(ref name) = self.plan {
+            if self.find_plan(name).is_none() {
+                return Err(ConfigError::Invalid(format!("unknown plan '{}'", name)));
+            }
+        }
+
+        if self.long_break_minutes < self.short_break_minutes {
+            return Err(ConfigError::Invalid(
+                "long_break_minutes must not be shorter than short_break_minutes".to_string(),
+            ));
+        }
+
+        match self.long_break_rule {
+            LongBreakRule::EverySessions(0) => Err(ConfigError::Invalid(
+                "long break interval must be at least 1 session".to_string(),
+            )),
+            LongBreakRule::AfterFocusMinutes(0) => Err(ConfigError::Invalid(
+                "long break focus threshold must be at least 1 minute".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn work_duration(&self) -> Duration {
+        Duration::from_secs(self.work_minutes * 60)
+    }
+
+    pub fn short_break_duration(&self) -> Duration {
+        Duration::from_secs(self.short_break_minutes * 60)
+    }
+
+    pub fn long_break_duration(&self) -> Duration {
+        Duration::from_secs(self.long_break_minutes * 60)
+    }
+
+    pub fn warm_up_duration(&self) -> Duration {
+        Duration::from_secs(self.warm_up_minutes * 60)
+    }
+
+    /// The plan called `name`, if there is one
+    pub fn find_plan(&self, name: &str) -> Option<&SessionPlan> {
+        self.plans.iter().find(|plan| plan.name == name)
+    }
+
+    /// Longest a pause may last, `None` if there is no limit
+    pub fn max_pause(&self) -> Option<Duration> {
+        (self.max_pause_minutes > 0).then(|| Duration::from_secs(self.max_pause_minutes * 60))
+    }
+
+    /// Time between overtime reminders, `None` if they are turned off
+    pub fn overtime_reminder_interval(&self) -> Option<Duration> {
+        (self.overtime_reminder_minutes > 0)
+            .then(|| Duration::from_secs(self.overtime_reminder_minutes * 60))
+    }
+}
+
+fn check_minutes(name: &str, minutes: u64) -> Result<(), ConfigError> {
+    if minutes == 0 || minutes > MAX_PHASE_MINUTES {
+        Err(ConfigError::Invalid(format!(
+            "{} must be between 1 and {} minutes (got {})",
+            name, MAX_PHASE_MINUTES, minutes
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+fn check_warm_up_minutes(name: &str, minutes: u64) -> Result<(), ConfigError> {
+    if WARM_UP_MINUTES.contains(&minutes) {
+        Ok(())
+    } else {
+        Err(ConfigError::Invalid(format!(
+            "{} must be between {} and {} minutes (got {})",
+            name,
+            WARM_UP_MINUTES.start(),
+            WARM_UP_MINUTES.end(),
+            minutes
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_is_valid() {
+        let config = Config::default();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.work_duration(), Duration::from_secs(25 * 60));
+        assert_eq!(config.long_break_rule, LongBreakRule::EverySessions(4));
+    }
+
+    #[test]
+    fn test_validate_rejects_zero_and_oversized_durations() {
+        let config = Config {
+            work_minutes: 0,
+            ..Config::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = Config {
+            short_break_minutes: MAX_PHASE_MINUTES + 1,
+            long_break_minutes: MAX_PHASE_MINUTES + 1,
+            ..Config::default()
+        };
+        assert!(config.validate().is_err());
+
+        // Warm-ups stay between 2 and 5 minutes
+        let config = Config {
+            warm_up: true,
+            warm_up_minutes: 10,
+            ..Config::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_pause_limit() {
+        assert_eq!(
+            Config::default().max_pause(),
+            Some(Duration::from_secs(10 * 60))
+        );
+
+        let config: Config =
+            serde_json::from_str(r#"{"max_pause_minutes": 0, "on_max_pause": "void"}"#).unwrap();
+        assert_eq!(config.max_pause(), None);
+        assert_eq!(config.on_max_pause, PauseLimitAction::Void);
+
+        let config = Config {
+            max_pause_minutes: MAX_PHASE_MINUTES + 1,
+            ..Config::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_long_break_shorter_than_short_break() {
+        let config = Config {
+            short_break_minutes: 20,
+            long_break_minutes: 10,
+            ..Config::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_zero_long_break_rule() {
+        let config = Config {
+            long_break_rule: LongBreakRule::EverySessions(0),
+            ..Config::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = Config {
+            long_break_rule: LongBreakRule::AfterFocusMinutes(0),
+            ..Config::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_long_break_rule_every_sessions() {
+        let rule = LongBreakRule::EverySessions(3);
+        assert!(!rule.is_long_break(1, Duration::ZERO));
+        assert!(!rule.is_long_break(2, Duration::ZERO));
+        assert!(rule.is_long_break(3, Duration::ZERO));
+        assert!(rule.is_long_break(6, Duration::ZERO));
+    }
+
+    #[test]
+    fn test_long_break_rule_after_focus_minutes() {
+        let rule = LongBreakRule::AfterFocusMinutes(100);
+        assert!(!rule.is_long_break(1, Duration::from_secs(99 * 60)));
+        assert!(rule.is_long_break(1, Duration::from_secs(100 * 60)));
+    }
+
+    #[test]
+    fn test_flowtime_breaks_are_proportional_and_clamped() {
+        let breaks = FlowtimeBreaks::default();
+        let minutes = |m: u64| Duration::from_secs(m * 60);
+        assert_eq!(breaks.break_for(minutes(50)), minutes(10));
+        assert_eq!(breaks.break_for(minutes(5)), minutes(5));
+        assert_eq!(breaks.break_for(minutes(300)), minutes(30));
+
+        let config = Config {
+            flowtime: FlowtimeBreaks {
+                min_minutes: 20,
+                max_minutes: 10,
+                ..FlowtimeBreaks::default()
+            },
+            ..Config::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_plans() {
+        let mut config = Config {
+            plan: Some("deep work".to_string()),
+            ..Config::default()
+        };
+        assert!(config.validate().is_ok());
+
+        config.plan = Some("nap".to_string());
+        assert!(config.validate().is_err());
+
+        config.plan = None;
+        config.plans.push(config.plans[0].clone());
+        assert!(config.validate().is_err());
+
+        config.plans.pop();
+        config.plans[0].warm_up_minutes = Some(10);
+        assert!(config.validate().is_err());
+
+        config.plans[0].warm_up_minutes = None;
+        config.plans[0].phases.clear();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_partial_config_uses_defaults() {
+        let config: Config = serde_json::from_str(r#"{"work_minutes": 50}"#).unwrap();
+        assert_eq!(config.work_minutes, 50);
+        assert_eq!(config.short_break_minutes, 5);
+        assert!(!config.overtime);
+        assert_eq!(config.mode, SessionMode::Pomodoro);
+        assert_eq!(
+            config.overtime_reminder_interval(),
+            Some(Duration::from_secs(5 * 60))
+        );
+
+        let config: Config = serde_json::from_str(
+            r#"{"long_break_rule": {"type": "after_focus_minutes", "value": 180}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.long_break_rule,
+            LongBreakRule::AfterFocusMinutes(180)
+        );
+
+        let config: Config =
+            serde_json::from_str(r#"{"mode": "flowtime", "flowtime": {"ratio": 3}}"#).unwrap();
+        assert_eq!(config.mode, SessionMode::Flowtime);
+        assert_eq!(config.flowtime.ratio, 3);
+        assert_eq!(config.flowtime.max_minutes, 30);
+    }
+
+    #[test]
+    fn test_auto_start_per_phase() {
+        let config: Config = serde_json::from_str(r#"{"auto_start": {"breaks": true}}"#).unwrap();
+        assert!(config.validate().is_ok());
+        assert!(config.auto_start.applies_to(PhaseKind::LongBreak));
+        assert!(!config.auto_start.applies_to(PhaseKind::Pomodoro));
+        assert_eq!(config.auto_start.grace(), Duration::from_secs(15));
+
+        let config = Config {
+            auto_start: AutoStart {
+                grace_seconds: 5,
+                ..AutoStart::default()
+            },
+            ..Config::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_hyperfocus_guard() {
+        let config: Config =
+            serde_json::from_str(r#"{"hyperfocus": {"block_minutes": 120}}"#).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.hyperfocus.note_minutes, 90);
+
+        let config = Config {
+            hyperfocus: HyperfocusGuard {
+                note_minutes: 180,
+                ..HyperfocusGuard::default()
+            },
+            ..Config::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
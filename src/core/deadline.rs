@@ -0,0 +1,154 @@
+//! Fitting pomodoros and breaks into the time left before a deadline
+
+use super::config::Config;
+use super::session::PhaseKind;
+use chrono::{DateTime, Local, NaiveTime};
+use std::time::Duration;
+
+/// Leftover time shorter than this is folded into the phase before it
+const MIN_PHASE_SECS: i64 = 60;
+
+/// A phase in a schedule that ends at a deadline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledPhase {
+    pub kind: PhaseKind,
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+    /// Cut short to land on the deadline
+    pub shortened: bool,
+}
+
+impl ScheduledPhase {
+    pub fn duration(&self) -> Duration {
+        (self.end - self.start).to_std().unwrap_or_default()
+    }
+}
+
+/// Alternate pomodoros and short breaks from `start`, beginning with `first`,
+/// cutting the last phase short so the schedule ends exactly at `deadline`
+///
+/// Returns nothing if less than a minute is left.
+pub fn schedule(
+    first: PhaseKind,
+    start: DateTime<Local>,
+    deadline: DateTime<Local>,
+    config: &Config,
+) -> Vec<ScheduledPhase> {
+    let mut phases = Vec::new();
+    let mut kind = first;
+    let mut start = start;
+    let min_phase = chrono::Duration::seconds(MIN_PHASE_SECS);
+
+    while deadline - start >= min_phase {
+        let full = if kind.is_break() {
+            config.short_break_duration()
+        } else {
+            config.work_duration()
+        };
+        let full = chrono::Duration::from_std(full).unwrap_or_default();
+        if full <= chrono::Duration::zero() {
+            break;
+        }
+        let mut end = (start + full).min(deadline);
+        if deadline - end < min_phase {
+            end = deadline;
+        }
+        phases.push(ScheduledPhase {
+            kind,
+            start,
+            end,
+            shortened: end < start + full,
+        });
+
+        start = end;
+        kind = if kind.is_break() {
+            PhaseKind::Pomodoro
+        } else {
+            PhaseKind::ShortBreak
+        };
+    }
+    phases
+}
+
+/// The next time `time` comes round after `now`, today or tomorrow
+pub fn next_occurrence(time: NaiveTime, now: DateTime<Local>) -> Option<DateTime<Local>> {
+    let today = now.date_naive().and_time(time);
+    let candidate = today.and_local_timezone(Local).earliest()?;
+    if candidate > now {
+        Some(candidate)
+    } else {
+        (today + chrono::Duration::days(1))
+            .and_local_timezone(Local)
+            .earliest()
+    }
+}
+
+/// Parse a wall-clock time such as "12:30"
+pub fn parse_time(input: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(input.trim(), "%H:%M").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2024, 3, 14, hour, minute, 0)
+            .single()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_schedule_lands_on_deadline() {
+        let phases = schedule(
+            PhaseKind::Pomodoro,
+            at(11, 0),
+            at(12, 20),
+            &Config::default(),
+        );
+        let kinds: Vec<_> = phases.iter().map(|phase| phase.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                PhaseKind::Pomodoro,
+                PhaseKind::ShortBreak,
+                PhaseKind::Pomodoro,
+                PhaseKind::ShortBreak,
+                PhaseKind::Pomodoro,
+            ]
+        );
+        // The third pomodoro would run to 12:25, so it is cut to 20 minutes
+        let last = phases.last().unwrap();
+        assert_eq!(last.end, at(12, 20));
+        assert_eq!(last.duration(), Duration::from_secs(20 * 60));
+        assert!(last.shortened);
+        assert!(!phases[0].shortened);
+    }
+
+    #[test]
+    fn test_schedule_starting_with_a_break() {
+        let phases = schedule(
+            PhaseKind::ShortBreak,
+            at(12, 27),
+            at(12, 30),
+            &Config::default(),
+        );
+        assert_eq!(phases.len(), 1);
+        assert_eq!(phases[0].duration(), Duration::from_secs(3 * 60));
+
+        let nearly = at(12, 30) - chrono::Duration::seconds(30);
+        assert!(schedule(PhaseKind::Pomodoro, nearly, at(12, 30), &Config::default()).is_empty());
+    }
+
+    #[test]
+    fn test_parse_and_next_occurrence() {
+        let time = parse_time(" 12:30 ").unwrap();
+        assert_eq!(next_occurrence(time, at(11, 0)), Some(at(12, 30)));
+        // A time that has passed means tomorrow
+        let tomorrow = next_occurrence(time, at(13, 0)).unwrap();
+        assert_eq!(tomorrow - at(12, 30), chrono::Duration::days(1));
+        assert!(parse_time("half past twelve").is_none());
+    }
+}
@@ -0,0 +1,158 @@
+//! Hyperfocus guard - noticing when work has gone on too long without a
+//! real break
+
+use super::config::HyperfocusGuard;
+use super::session::{PhaseKind, PhaseSummary, SessionOutcome};
+use std::time::Duration;
+
+/// How firmly the guard steps in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GuardLevel {
+    /// A nudge in the status bar
+    Note,
+    /// A screen that has to be acknowledged before carrying on
+    Block,
+}
+
+impl GuardLevel {
+    pub fn key(&self) -> &'static str {
+        match self {
+            GuardLevel::Note => "note",
+            GuardLevel::Block => "block",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "note" => Some(GuardLevel::Note),
+            "block" => Some(GuardLevel::Block),
+            _ => None,
+        }
+    }
+}
+
+/// Work since the last break that was actually taken
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FocusStreak {
+    /// Focus time in finished work phases
+    pub focus: Duration,
+    /// Breaks skipped in a row
+    pub skipped_breaks: u32,
+}
+
+impl FocusStreak {
+    /// Take a finished phase into account - a completed break ends the streak
+    pub fn record(&mut self, summary: &PhaseSummary) {
+        match (summary.kind, summary.outcome) {
+            (PhaseKind::ShortBreak | PhaseKind::LongBreak, SessionOutcome::Completed) => {
+                *self = Self::default()
+            }
+            (PhaseKind::ShortBreak | PhaseKind::LongBreak, SessionOutcome::Skipped) => {
+                self.skipped_breaks += 1
+            }
+            (PhaseKind::ShortBreak | PhaseKind::LongBreak, _) => {}
+            (PhaseKind::WarmUp | PhaseKind::Pomodoro, _) => self.focus += summary.actual,
+        }
+    }
+
+    /// Whether the streak calls for the guard, counting `current` focus in
+    /// the work phase still running
+    pub fn level(&self, guard: &HyperfocusGuard, current: Duration) -> Option<GuardLevel> {
+        if !guard.enabled {
+            return None;
+        }
+        let focus = self.focus + current;
+        let max_skipped = guard.max_skipped_breaks;
+        if focus >= guard.block_after() || self.skipped_breaks >= max_skipped {
+            Some(GuardLevel::Block)
+        } else if focus >= guard.note_after()
+            || (self.skipped_breaks > 0 && self.skipped_breaks + 1 >= max_skipped)
+        {
+            Some(GuardLevel::Note)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Local;
+
+    fn summary(kind: PhaseKind, outcome: SessionOutcome, minutes: u64) -> PhaseSummary {
+        PhaseSummary {
+            kind,
+            outcome,
+            started_at: Local::now(),
+            ended_at: Local::now(),
+            planned: Duration::from_secs(minutes * 60),
+            actual: Duration::from_secs(minutes * 60),
+            paused: Duration::ZERO,
+            pauses: 0,
+            overtime: Duration::ZERO,
+            shortened: false,
+            extended: false,
+            adjustments: Vec::new(),
+            started: true,
+        }
+    }
+
+    #[test]
+    fn test_streak_escalates_until_a_break_is_taken() {
+        let guard = HyperfocusGuard {
+            enabled: true,
+            ..HyperfocusGuard::default()
+        };
+        let mut streak = FocusStreak::default();
+        let minutes = |m: u64| Duration::from_secs(m * 60);
+
+        streak.record(&summary(PhaseKind::Pomodoro, SessionOutcome::Completed, 50));
+        assert_eq!(streak.level(&guard, minutes(10)), None);
+        assert_eq!(streak.level(&guard, minutes(40)), Some(GuardLevel::Note));
+        assert_eq!(streak.level(&guard, minutes(100)), Some(GuardLevel::Block));
+
+        // A reset break doesn't count either way, a skipped one does
+        streak.record(&summary(PhaseKind::ShortBreak, SessionOutcome::Reset, 1));
+        streak.record(&summary(PhaseKind::ShortBreak, SessionOutcome::Skipped, 0));
+        streak.record(&summary(PhaseKind::ShortBreak, SessionOutcome::Skipped, 0));
+        assert_eq!(streak.level(&guard, Duration::ZERO), Some(GuardLevel::Note));
+        streak.record(&summary(PhaseKind::ShortBreak, SessionOutcome::Skipped, 0));
+        assert_eq!(
+            streak.level(&guard, Duration::ZERO),
+            Some(GuardLevel::Block)
+        );
+
+        streak.record(&summary(
+            PhaseKind::LongBreak,
+            SessionOutcome::Completed,
+            15,
+        ));
+        assert_eq!(streak, FocusStreak::default());
+
+        let off = HyperfocusGuard {
+            enabled: false,
+            ..guard
+        };
+        assert_eq!(streak.level(&off, minutes(600)), None);
+        assert!(!HyperfocusGuard::default().enabled);
+    }
+
+    #[test]
+    fn test_single_skipped_break_limit_has_no_note() {
+        let guard = HyperfocusGuard {
+            enabled: true,
+            max_skipped_breaks: 1,
+            ..HyperfocusGuard::default()
+        };
+        let mut streak = FocusStreak::default();
+        streak.record(&summary(PhaseKind::Pomodoro, SessionOutcome::Completed, 25));
+        assert_eq!(streak.level(&guard, Duration::ZERO), None);
+
+        streak.record(&summary(PhaseKind::ShortBreak, SessionOutcome::Skipped, 0));
+        assert_eq!(
+            streak.level(&guard, Duration::ZERO),
+            Some(GuardLevel::Block)
+        );
+    }
+}
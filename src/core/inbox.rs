@@ -0,0 +1,60 @@
+//! Distraction inbox - thoughts parked mid-pomodoro, to be sorted out later
+
+use chrono::{DateTime, Duration, Local, NaiveDate};
+
+/// A thought captured without leaving the pomodoro
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InboxItem {
+    /// Database id, `None` until the item is stored
+    pub id: Option<i64>,
+    pub captured_at: DateTime<Local>,
+    pub text: String,
+    /// Day to look at it again, if it was put off
+    pub scheduled_for: Option<NaiveDate>,
+}
+
+impl InboxItem {
+    pub fn new(text: impl Into<String>, captured_at: DateTime<Local>) -> Self {
+        Self {
+            id: None,
+            captured_at,
+            text: text.into(),
+            scheduled_for: None,
+        }
+    }
+
+    /// Waiting to be sorted out as of `today`, rather than put off until later
+    pub fn is_due(&self, today: NaiveDate) -> bool {
+        self.scheduled_for.is_none_or(|day| day <= today)
+    }
+
+    /// Day the item moves to when it's put off once more - tomorrow, or the
+    /// day after the one it's already scheduled for
+    pub fn next_schedule(&self, today: NaiveDate) -> NaiveDate {
+        self.scheduled_for.unwrap_or(today).max(today) + Duration::days(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_putting_an_item_off_moves_it_a_day_at_a_time() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 14).unwrap();
+        let day = |d| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+        let mut item = InboxItem::new("Book the dentist", Local::now());
+        assert!(item.is_due(today));
+
+        item.scheduled_for = Some(item.next_schedule(today));
+        assert_eq!(item.scheduled_for, Some(day(15)));
+        assert!(!item.is_due(today));
+        item.scheduled_for = Some(item.next_schedule(today));
+        assert_eq!(item.scheduled_for, Some(day(16)));
+
+        // An item scheduled for a day that's gone is due, and moves from today
+        item.scheduled_for = Some(day(10));
+        assert!(item.is_due(today));
+        assert_eq!(item.next_schedule(today), day(15));
+    }
+}
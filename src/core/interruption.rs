@@ -0,0 +1,106 @@
+//! Interruptions logged during a pomodoro, with the classic marks - `'` for
+//! internal ones (a thought, an urge to check something) and `-` for
+//! external ones (a colleague, a call)
+
+use chrono::{DateTime, Local};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterruptionKind {
+    Internal,
+    External,
+}
+
+impl InterruptionKind {
+    pub fn key(&self) -> &'static str {
+        match self {
+            InterruptionKind::Internal => "internal",
+            InterruptionKind::External => "external",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "internal" => Some(InterruptionKind::Internal),
+            "external" => Some(InterruptionKind::External),
+            _ => None,
+        }
+    }
+
+    /// Mark used on paper for this kind
+    pub fn mark(&self) -> char {
+        match self {
+            InterruptionKind::Internal => '\'',
+            InterruptionKind::External => '-',
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            InterruptionKind::Internal => "Internal",
+            InterruptionKind::External => "External",
+        }
+    }
+}
+
+/// One logged interruption
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interruption {
+    pub logged_at: DateTime<Local>,
+    pub kind: InterruptionKind,
+    pub note: Option<String>,
+    /// The timer was paused to deal with it
+    pub paused: bool,
+}
+
+/// Interruptions of each kind, e.g. in the current pomodoro
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InterruptionTally {
+    pub internal: u32,
+    pub external: u32,
+}
+
+impl InterruptionTally {
+    pub fn add(&mut self, kind: InterruptionKind) {
+        match kind {
+            InterruptionKind::Internal => self.internal += 1,
+            InterruptionKind::External => self.external += 1,
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.internal + self.external
+    }
+}
+
+impl fmt::Display for InterruptionTally {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{} {}{}",
+            self.internal,
+            InterruptionKind::Internal.mark(),
+            self.external,
+            InterruptionKind::External.mark()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tally_counts_each_kind() {
+        let mut tally = InterruptionTally::default();
+        tally.add(InterruptionKind::Internal);
+        tally.add(InterruptionKind::Internal);
+        tally.add(InterruptionKind::External);
+        assert_eq!(tally.total(), 3);
+        assert_eq!(tally.to_string(), "2' 1-");
+        assert_eq!(
+            InterruptionKind::from_key(InterruptionKind::External.key()),
+            Some(InterruptionKind::External)
+        );
+    }
+}
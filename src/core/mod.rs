@@ -1,7 +1,35 @@
 pub mod break_activity;
 pub mod breathing;
+pub mod check_in;
+pub mod clock;
+pub mod config;
+pub mod deadline;
+pub mod hyperfocus;
+pub mod inbox;
+pub mod interruption;
+pub mod plan;
+pub mod session;
+pub mod suspend;
+pub mod task;
 pub mod timer;
 
 pub use break_activity::{BreakActivity, BreakAnimation};
 pub use breathing::{BreathingExercise, BreathingPattern};
+pub use check_in::{CheckIn, CHECK_IN_MAX};
+pub use clock::{Clock, ManualClock, SystemClock, VirtualClock};
+pub use config::{
+    AutoStart, Config, ConfigError, FlowtimeBreaks, HyperfocusGuard, LongBreakRule,
+    PauseLimitAction, SessionMode,
+};
+pub use deadline::ScheduledPhase;
+pub use hyperfocus::{FocusStreak, GuardLevel};
+pub use inbox::InboxItem;
+pub use interruption::{Interruption, InterruptionKind, InterruptionTally};
+pub use plan::{PlanPhase, SessionPlan};
+pub use session::{
+    IntentionResult, PhaseKind, PhaseSummary, Session, SessionCommand, SessionEvent,
+    SessionOutcome, SessionState, TimeAdjustment, VoidReason,
+};
+pub use suspend::{ClockGap, GapDetector, GapUse};
+pub use task::{pick_weighted, Priority, Task, TaskStatus};
 pub use timer::Timer;
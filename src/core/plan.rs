@@ -0,0 +1,168 @@
+//! Named session plans - fixed sequences of work and break phases
+
+use super::session::PhaseKind;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// One step of a plan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlanPhase {
+    pub kind: PhaseKind,
+    pub minutes: u64,
+    /// Shown next to the phase, e.g. "review"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+impl PlanPhase {
+    pub fn new(kind: PhaseKind, minutes: u64) -> Self {
+        Self {
+            kind,
+            minutes,
+            label: None,
+        }
+    }
+
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(self.minutes * 60)
+    }
+}
+
+/// Named sequence of phases, optionally repeated
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionPlan {
+    pub name: String,
+    pub phases: Vec<PlanPhase>,
+    /// How many times to go through `phases`
+    #[serde(default = "default_repeat")]
+    pub repeat: u32,
+    /// Warm up before the first phase, instead of following the `warm_up`
+    /// setting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warm_up: Option<bool>,
+    /// Length of that warm-up, instead of `warm_up_minutes`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warm_up_minutes: Option<u64>,
+}
+
+fn default_repeat() -> u32 {
+    1
+}
+
+impl SessionPlan {
+    pub fn new(name: &str, phases: Vec<PlanPhase>, repeat: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            phases,
+            repeat,
+            warm_up: None,
+            warm_up_minutes: None,
+        }
+    }
+
+    /// Start the plan with a warm-up of `minutes`
+    pub fn with_warm_up(mut self, minutes: u64) -> Self {
+        self.warm_up = Some(true);
+        self.warm_up_minutes = Some(minutes);
+        self
+    }
+
+    /// Number of phases in the plan, counting repeats
+    pub fn len(&self) -> usize {
+        self.phases.len() * self.repeat.max(1) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The phase at `step`, counting from 0 across repeats
+    pub fn phase(&self, step: usize) -> Option<&PlanPhase> {
+        if step >= self.len() {
+            return None;
+        }
+        self.phases.get(step % self.phases.len())
+    }
+
+    /// Compact description such as "90/20 ×2" or "25/5/30 review"
+    pub fn summary(&self) -> String {
+        let phases: Vec<String> = self
+            .phases
+            .iter()
+            .map(|phase| match phase.label {
+                Some(ref label) => format!("{} {}", phase.minutes, label),
+                None => phase.minutes.to_string(),
+            })
+            .collect();
+        let mut summary = phases.join("/");
+        if self.repeat > 1 {
+            summary.push_str(&format!(" ×{}", self.repeat));
+        }
+        summary
+    }
+
+    /// Plans offered out of the box
+    pub fn presets() -> Vec<SessionPlan> {
+        vec![
+            SessionPlan::new(
+                "deep work",
+                vec![
+                    PlanPhase::new(PhaseKind::Pomodoro, 90),
+                    PlanPhase::new(PhaseKind::ShortBreak, 20),
+                ],
+                2,
+            ),
+            SessionPlan::new(
+                "study",
+                vec![
+                    PlanPhase::new(PhaseKind::Pomodoro, 25),
+                    PlanPhase::new(PhaseKind::ShortBreak, 5),
+                    PlanPhase::new(PhaseKind::Pomodoro, 25),
+                    PlanPhase::new(PhaseKind::ShortBreak, 5),
+                    PlanPhase::new(PhaseKind::Pomodoro, 25),
+                    PlanPhase::new(PhaseKind::Pomodoro, 30).with_label("review"),
+                ],
+                1,
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_plan_steps() {
+        let plan = &SessionPlan::presets()[0];
+        assert_eq!(plan.len(), 4);
+        assert_eq!(plan.phase(2).unwrap().kind, PhaseKind::Pomodoro);
+        assert_eq!(plan.phase(3).unwrap().duration().as_secs(), 20 * 60);
+        assert!(plan.phase(4).is_none());
+        assert_eq!(plan.summary(), "90/20 ×2");
+        assert_eq!(
+            SessionPlan::presets()[1].summary(),
+            "25/5/25/5/25/30 review"
+        );
+    }
+
+    #[test]
+    fn test_plan_from_json() {
+        let plan: SessionPlan = serde_json::from_str(
+            r#"{"name": "sprint", "phases": [
+                {"kind": "pomodoro", "minutes": 50},
+                {"kind": "long_break", "minutes": 10, "label": "walk"}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(plan.repeat, 1);
+        assert_eq!(plan.len(), 2);
+        assert_eq!(plan.phase(1).unwrap().label.as_deref(), Some("walk"));
+        assert_eq!(plan.warm_up, None);
+    }
+}
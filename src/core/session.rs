@@ -0,0 +1,1789 @@
+//! Headless session engine
+//!
+//! A [`Session`] owns the timer and steps through pomodoros and breaks. Front
+//! ends drive it with [`SessionCommand`]s, call [`Session::tick`] regularly and
+//! react to the [`SessionEvent`]s it queues up - nothing here knows about the
+//! terminal, audio or Focus mode.
+
+use super::clock::{Clock, SystemClock};
+use super::config::{Config, PauseLimitAction, SessionMode};
+use super::deadline::{self, ScheduledPhase};
+use super::plan::SessionPlan;
+use super::timer::{Timer, TimerState};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Kind of phase in the pomodoro cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PhaseKind {
+    /// Short, low-stakes session before the first pomodoro
+    WarmUp,
+    Pomodoro,
+    ShortBreak,
+    LongBreak,
+}
+
+impl PhaseKind {
+    pub fn key(&self) -> &'static str {
+        match self {
+            PhaseKind::WarmUp => "warm_up",
+            PhaseKind::Pomodoro => "pomodoro",
+            PhaseKind::ShortBreak => "short_break",
+            PhaseKind::LongBreak => "long_break",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "warm_up" => Some(PhaseKind::WarmUp),
+            "pomodoro" => Some(PhaseKind::Pomodoro),
+            "short_break" => Some(PhaseKind::ShortBreak),
+            "long_break" => Some(PhaseKind::LongBreak),
+            _ => None,
+        }
+    }
+
+    pub fn is_break(&self) -> bool {
+        matches!(self, PhaseKind::ShortBreak | PhaseKind::LongBreak)
+    }
+}
+
+/// How a phase ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionOutcome {
+    /// Timer ran to zero
+    Completed,
+    /// User moved on before the timer finished
+    Skipped,
+    /// User reset the timer and threw the phase away
+    Reset,
+    /// Thrown away under the pomodoro rules, e.g. after too long a pause,
+    /// without counting towards the break cycle
+    Voided,
+}
+
+impl SessionOutcome {
+    pub fn key(&self) -> &'static str {
+        match self {
+            SessionOutcome::Completed => "completed",
+            SessionOutcome::Skipped => "skipped",
+            SessionOutcome::Reset => "reset",
+            SessionOutcome::Voided => "voided",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "completed" => Some(SessionOutcome::Completed),
+            "skipped" => Some(SessionOutcome::Skipped),
+            "reset" => Some(SessionOutcome::Reset),
+            "voided" => Some(SessionOutcome::Voided),
+            _ => None,
+        }
+    }
+
+    /// The phase was thrown away and set up again rather than moved on from
+    pub fn is_discarded(&self) -> bool {
+        matches!(self, SessionOutcome::Reset | SessionOutcome::Voided)
+    }
+}
+
+/// Why a pomodoro was voided
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VoidReason {
+    Interrupted,
+    Distracted,
+    TaskChanged,
+    Emergency,
+}
+
+impl VoidReason {
+    /// Every reason, in the order they are offered
+    pub const ALL: [VoidReason; 4] = [
+        VoidReason::Interrupted,
+        VoidReason::Distracted,
+        VoidReason::TaskChanged,
+        VoidReason::Emergency,
+    ];
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            VoidReason::Interrupted => "Interrupted",
+            VoidReason::Distracted => "Distracted",
+            VoidReason::TaskChanged => "Task changed",
+            VoidReason::Emergency => "Emergency",
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            VoidReason::Interrupted => "interrupted",
+            VoidReason::Distracted => "distracted",
+            VoidReason::TaskChanged => "task_changed",
+            VoidReason::Emergency => "emergency",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "interrupted" => Some(VoidReason::Interrupted),
+            "distracted" => Some(VoidReason::Distracted),
+            "task_changed" => Some(VoidReason::TaskChanged),
+            "emergency" => Some(VoidReason::Emergency),
+            _ => None,
+        }
+    }
+}
+
+/// Whether a pomodoro did what it was meant to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntentionResult {
+    Yes,
+    Partly,
+    No,
+}
+
+impl IntentionResult {
+    pub fn key(&self) -> &'static str {
+        match self {
+            IntentionResult::Yes => "yes",
+            IntentionResult::Partly => "partly",
+            IntentionResult::No => "no",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "yes" => Some(IntentionResult::Yes),
+            "partly" => Some(IntentionResult::Partly),
+            "no" => Some(IntentionResult::No),
+            _ => None,
+        }
+    }
+}
+
+/// Something the user (or another tool) asks the session to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionCommand {
+    /// Start the timer of an idle phase
+    Start,
+    Pause,
+    Resume,
+    /// Pause on the app's own account, e.g. to ask about a clock gap. Unlike
+    /// [`SessionCommand::Pause`] it isn't counted as one of the phase's pauses
+    /// and never runs into the pause limit
+    Hold,
+    /// Move on from a completed phase, or stop a pomodoro that is counting up
+    Next,
+    /// End the current phase early and move on to the next one
+    Skip,
+    /// Cut a long break down to a short one
+    Shorten,
+    /// Give a shortened break its full length back
+    Extend,
+    /// Add minutes to a running or paused phase, or take them off when negative
+    Adjust(i64),
+    /// Count time the clock missed, e.g. during a suspend, as spent in the
+    /// running or paused phase
+    Credit(Duration),
+    /// Throw the current phase away and set it up again
+    Reset,
+    /// Throw away a started pomodoro under the pomodoro rules and set it up again
+    Void,
+    /// Put the timer back to zero without ending the phase
+    Restart,
+}
+
+/// Time added to or taken off a phase while it ran
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeAdjustment {
+    pub at: DateTime<Local>,
+    /// Seconds added, negative when time was taken off
+    pub delta_secs: i64,
+}
+
+impl TimeAdjustment {
+    /// Time was added, putting the end off
+    pub fn is_snooze(&self) -> bool {
+        self.delta_secs > 0
+    }
+}
+
+/// What happened in a phase, for the session history
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseSummary {
+    pub kind: PhaseKind,
+    pub outcome: SessionOutcome,
+    pub started_at: DateTime<Local>,
+    pub ended_at: DateTime<Local>,
+    /// Duration the timer was set to
+    pub planned: Duration,
+    /// Time actually spent running, excluding pauses
+    pub actual: Duration,
+    /// Total time spent paused
+    pub paused: Duration,
+    /// Number of times the timer was paused
+    pub pauses: u32,
+    /// Time spent past the planned duration in overtime
+    pub overtime: Duration,
+    pub shortened: bool,
+    pub extended: bool,
+    /// Time added or taken off along the way, oldest first
+    pub adjustments: Vec<TimeAdjustment>,
+    /// The timer was running or paused when the phase ended
+    pub started: bool,
+}
+
+/// Something that happened in the session, queued until drained
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionEvent {
+    /// The timer of an idle phase started running
+    PhaseStarted(PhaseKind),
+    Paused(PhaseKind),
+    Resumed(PhaseKind),
+    /// The timer ran out
+    PhaseCompleted(PhaseKind),
+    /// A pomodoro ran out and keeps counting in overtime
+    OvertimeStarted,
+    /// Still in overtime, with this much extra time so far
+    OvertimeReminder(Duration),
+    /// The timer has been paused for the configured maximum
+    PauseLimitReached(Duration),
+    /// The phase is over and won't be reported again
+    PhaseEnded(PhaseSummary),
+    /// A new phase is set up and waiting to be started
+    PhaseReady {
+        kind: PhaseKind,
+        /// How the previous phase ended
+        after: SessionOutcome,
+    },
+    BreakShortened,
+    BreakExtended,
+    /// Time was added to or taken off the running phase
+    TimeAdjusted(TimeAdjustment),
+    /// The last phase of the plan is over, back to the usual cycle
+    PlanCompleted(String),
+    /// No time is left before the deadline, back to the usual cycle
+    DeadlineReached,
+}
+
+/// Progress through the cycle, enough to rebuild a session elsewhere
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionState {
+    pub kind: PhaseKind,
+    pub planned: Duration,
+    pub session_count: u32,
+    pub focus_since_long_break: Duration,
+    pub shortened: bool,
+    pub extended: bool,
+    pub started_at: Option<DateTime<Local>>,
+    /// The phase has already been reported as ended
+    pub ended: bool,
+    /// Name of the plan being followed, if any
+    pub plan: Option<String>,
+    /// Current phase of the plan, counting from 0
+    pub plan_step: usize,
+    /// Time the phases are being fitted to, if any
+    pub deadline: Option<DateTime<Local>>,
+    /// Times the current phase has been paused
+    pub pauses: u32,
+    /// Time added to or taken off the current phase so far
+    pub adjustments: Vec<TimeAdjustment>,
+}
+
+/// Pomodoro/break state machine
+#[derive(Debug)]
+pub struct Session {
+    config: Config,
+    clock: Arc<dyn Clock>,
+    timer: Timer,
+    kind: PhaseKind,
+    session_count: u32,
+    focus_since_long_break: Duration,
+    shortened: bool,
+    extended: bool,
+    /// Length the current phase was set up with, for giving a shortened
+    /// break its time back
+    full_length: Duration,
+    started_at: Option<DateTime<Local>>,
+    ended: bool,
+    plan: Option<SessionPlan>,
+    plan_step: usize,
+    deadline: Option<DateTime<Local>>,
+    /// Focus time of the last pomodoro, for sizing flowtime breaks
+    last_focus: Duration,
+    /// Overtime reminders sent in this phase, counting the start of overtime
+    overtime_notices: u64,
+    adjustments: Vec<TimeAdjustment>,
+    /// Times the current phase has been paused
+    pauses: u32,
+    /// The current pause has already reached the limit
+    pause_limit_reached: bool,
+    /// The timer is paused on the app's account, so the limit doesn't apply
+    held: bool,
+    events: Vec<SessionEvent>,
+}
+
+impl Session {
+    pub fn new(config: Config) -> Self {
+        Self::with_clock(config, SystemClock::shared())
+    }
+
+    /// Create a session whose timers and timestamps follow `clock`
+    pub fn with_clock(config: Config, clock: Arc<dyn Clock>) -> Self {
+        let plan = config
+            .plan
+            .as_deref()
+            .and_then(|name| usable_plan(&config, name));
+        let mut session = Self {
+            timer: Timer::with_clock(0, clock.clone()),
+            config,
+            clock,
+            kind: PhaseKind::Pomodoro,
+            session_count: 0,
+            focus_since_long_break: Duration::ZERO,
+            shortened: false,
+            extended: false,
+            full_length: Duration::ZERO,
+            started_at: None,
+            ended: false,
+            plan,
+            plan_step: 0,
+            deadline: None,
+            last_focus: Duration::ZERO,
+            overtime_notices: 0,
+            adjustments: Vec::new(),
+            pauses: 0,
+            pause_limit_reached: false,
+            held: false,
+            events: Vec::new(),
+        };
+        let (kind, duration) = session.first_phase();
+        session.kind = kind;
+        session.timer = session.new_timer(duration);
+        session.full_length = duration;
+        session
+    }
+
+    /// Pick up a session from a saved state, with the timer at `elapsed`
+    pub fn restore(
+        &mut self,
+        state: SessionState,
+        elapsed: Duration,
+        paused: Duration,
+        timer_state: TimerState,
+    ) {
+        self.kind = state.kind;
+        // A plan that has since been removed from the config falls back to the standard cycle
+        self.plan = state
+            .plan
+            .as_deref()
+            .and_then(|name| usable_plan(&self.config, name));
+        self.plan_step = match self.plan {
+            Some(ref plan) => state.plan_step.min(plan.len() - 1),
+            None => 0,
+        };
+        self.deadline = state.deadline;
+        // A phase that ran out while the app was closed completes as soon as
+        // it's resumed, unless it keeps counting past its end
+        let elapsed = if self.is_open_ended() || self.allows_overtime() {
+            elapsed
+        } else {
+            elapsed.min(state.planned)
+        };
+        let mut timer = Timer::restore(
+            state.planned,
+            elapsed,
+            paused,
+            timer_state,
+            self.clock.clone(),
+        );
+        self.configure(&mut timer);
+        self.timer = timer;
+        self.session_count = state.session_count;
+        self.focus_since_long_break = state.focus_since_long_break;
+        self.shortened = state.shortened;
+        self.extended = state.extended;
+        self.full_length = if state.shortened {
+            self.set_up_length()
+        } else {
+            state.planned
+        };
+        self.started_at = state.started_at;
+        self.ended = state.ended;
+        self.overtime_notices = 0;
+        self.adjustments = state.adjustments;
+        self.pauses = state.pauses;
+        self.pause_limit_reached = false;
+        self.held = false;
+    }
+
+    pub fn handle(&mut self, command: SessionCommand) {
+        let state = self.timer.state();
+        match command {
+            SessionCommand::Start if state == TimerState::Idle => {
+                // Fit the phase to the time left now, not when it was set up
+                if self.deadline.is_some() {
+                    let duration = match self.deadline_duration(self.kind) {
+                        Some(duration) => duration,
+                        None => self.full_duration(self.kind),
+                    };
+                    self.timer = self.new_timer(duration);
+                    self.full_length = duration;
+                }
+                self.timer.start();
+                if self.started_at.is_none() {
+                    self.started_at = Some(self.clock.wall_now());
+                }
+                self.events.push(SessionEvent::PhaseStarted(self.kind));
+            }
+            SessionCommand::Pause if state == TimerState::Running => {
+                self.timer.pause();
+                self.pauses += 1;
+                self.events.push(SessionEvent::Paused(self.kind));
+            }
+            SessionCommand::Hold if state == TimerState::Running => {
+                self.timer.pause();
+                self.held = true;
+                self.events.push(SessionEvent::Paused(self.kind));
+            }
+            SessionCommand::Resume if state == TimerState::Paused => {
+                self.timer.resume();
+                self.pause_limit_reached = false;
+                self.held = false;
+                self.events.push(SessionEvent::Resumed(self.kind));
+            }
+            // Stopping a pomodoro that counts up (flowtime or overtime) completes it
+            SessionCommand::Next | SessionCommand::Skip if self.timer.is_counting_up() => {
+                self.count_pomodoro(self.timer.elapsed());
+                self.end_phase(SessionOutcome::Completed);
+                self.timer.stop();
+                self.start_next_phase(SessionOutcome::Completed);
+            }
+            SessionCommand::Next if state == TimerState::Completed => {
+                self.start_next_phase(SessionOutcome::Completed)
+            }
+            // A phase that already ran out just moves on
+            SessionCommand::Skip if state == TimerState::Completed => {
+                self.start_next_phase(SessionOutcome::Completed)
+            }
+            SessionCommand::Skip => {
+                if self.kind == PhaseKind::Pomodoro {
+                    // A skipped pomodoro still counts towards the long break
+                    self.count_pomodoro(self.timer.elapsed());
+                }
+                self.end_phase(SessionOutcome::Skipped);
+                self.start_next_phase(SessionOutcome::Skipped);
+            }
+            SessionCommand::Shorten if self.kind.is_break() => {
+                let short_break = self.config.short_break_duration();
+                // Only shorten if the current break is longer than a short break
+                if self.timer.duration() > short_break {
+                    self.timer = self.new_timer(short_break);
+                    self.shortened = true;
+                    self.events.push(SessionEvent::BreakShortened);
+                }
+            }
+            // A shortened break gets back the length it was set up with, as far
+            // as the deadline allows
+            SessionCommand::Extend if self.kind.is_break() && self.shortened => {
+                let length = match self.time_to_deadline() {
+                    Some(left) => self.full_length.min(left),
+                    None => self.full_length,
+                };
+                if length > self.timer.duration() {
+                    self.timer = self.new_timer(length);
+                    self.shortened = false;
+                    self.extended = true;
+                    self.events.push(SessionEvent::BreakExtended);
+                }
+            }
+            SessionCommand::Adjust(minutes)
+                if matches!(state, TimerState::Running | TimerState::Paused) =>
+            {
+                let mut seconds = minutes.saturating_mul(60);
+                // Time added can't push the phase past the deadline
+                if let Some(left) = self.time_to_deadline() {
+                    let room = left.saturating_sub(self.timer.remaining()).as_secs();
+                    seconds = seconds.min(room as i64);
+                }
+                let delta_secs = self.timer.adjust(seconds);
+                if delta_secs != 0 {
+                    let adjustment = TimeAdjustment {
+                        at: self.clock.wall_now(),
+                        delta_secs,
+                    };
+                    self.adjustments.push(adjustment);
+                    self.events.push(SessionEvent::TimeAdjusted(adjustment));
+                }
+            }
+            SessionCommand::Credit(time)
+                if matches!(state, TimerState::Running | TimerState::Paused) =>
+            {
+                self.timer.credit(time)
+            }
+            SessionCommand::Reset => self.discard(SessionOutcome::Reset),
+            SessionCommand::Void
+                if self.kind == PhaseKind::Pomodoro && state != TimerState::Idle =>
+            {
+                self.discard(SessionOutcome::Voided)
+            }
+            SessionCommand::Restart => {
+                self.timer.reset();
+                self.overtime_notices = 0;
+                self.pauses = 0;
+                self.pause_limit_reached = false;
+                self.held = false;
+            }
+            _ => {}
+        }
+    }
+
+    /// Follow the plan called `name` from its first phase, or the standard
+    /// cycle for `None`
+    ///
+    /// Only switches while the timer is idle, so nothing in progress is lost.
+    /// Returns false if the timer isn't idle or there is no such plan.
+    pub fn select_plan(&mut self, name: Option<&str>) -> bool {
+        if self.timer.state() != TimerState::Idle {
+            return false;
+        }
+        let plan = match name {
+            Some(name) => match usable_plan(&self.config, name) {
+                Some(plan) => Some(plan),
+                None => return false,
+            },
+            None => None,
+        };
+        self.plan = plan;
+        self.plan_step = 0;
+        self.deadline = None;
+        let (kind, duration) = self.first_phase();
+        self.start_phase(kind, duration, SessionOutcome::Reset);
+        true
+    }
+
+    /// Fit pomodoros and breaks into the time left before `deadline`, or go
+    /// back to the usual cycle for `None`
+    ///
+    /// Like [`Session::select_plan`], only switches while the timer is idle.
+    /// Returns false if it isn't, or if less than a minute is left.
+    pub fn set_deadline(&mut self, deadline: Option<DateTime<Local>>) -> bool {
+        if self.timer.state() != TimerState::Idle {
+            return false;
+        }
+        let Some(deadline) = deadline else {
+            self.deadline = None;
+            let (kind, duration) = self.first_phase();
+            self.start_phase(kind, duration, SessionOutcome::Reset);
+            return true;
+        };
+        // A deadline leaves no room for warming up
+        let kind = match self.kind {
+            PhaseKind::WarmUp => PhaseKind::Pomodoro,
+            kind => kind,
+        };
+        let now = self.clock.wall_now();
+        let Some(first) = deadline::schedule(kind, now, deadline, &self.config)
+            .first()
+            .map(ScheduledPhase::duration)
+        else {
+            return false;
+        };
+
+        self.plan = None;
+        self.plan_step = 0;
+        self.deadline = Some(deadline);
+        self.start_phase(kind, first, SessionOutcome::Reset);
+        true
+    }
+
+    /// The current phase and the ones that still fit before the deadline,
+    /// empty without a deadline
+    pub fn schedule(&self) -> Vec<ScheduledPhase> {
+        let Some(deadline) = self.deadline else {
+            return Vec::new();
+        };
+        let now = self.clock.wall_now();
+        let remaining = match self.timer.state() {
+            TimerState::Idle => self.timer.duration(),
+            _ => self.timer.remaining(),
+        };
+        let end = now + chrono::Duration::from_std(remaining).unwrap_or_default();
+
+        let mut phases = vec![ScheduledPhase {
+            kind: self.kind,
+            start: self.started_at.unwrap_or(now),
+            end,
+            shortened: self.timer.duration() < self.full_duration(self.kind),
+        }];
+        phases.extend(deadline::schedule(
+            next_kind(self.kind),
+            end,
+            deadline,
+            &self.config,
+        ));
+        phases
+    }
+
+    /// Check the timer, completing the phase if it ran out
+    ///
+    /// Returns true when the phase completed, or was voided after too long
+    /// a pause, on this tick. A pomodoro in overtime keeps going and only
+    /// queues reminders.
+    pub fn tick(&mut self) -> bool {
+        if self.timer.state() == TimerState::Paused {
+            return self.check_pause_limit();
+        }
+        if self.timer.is_overtime() {
+            self.remind_overtime();
+            return false;
+        }
+        if !self.timer.is_expired() {
+            return false;
+        }
+        self.timer.stop();
+        self.events.push(SessionEvent::PhaseCompleted(self.kind));
+        if self.kind == PhaseKind::Pomodoro {
+            self.count_pomodoro(self.timer.duration());
+        }
+        self.end_phase(SessionOutcome::Completed);
+        if let Some(deadline) = self.deadline {
+            let now = self.clock.wall_now();
+            if deadline::schedule(next_kind(self.kind), now, deadline, &self.config).is_empty() {
+                self.reach_deadline();
+            }
+        }
+        true
+    }
+
+    /// Take the events queued since the last call, oldest first
+    pub fn drain_events(&mut self) -> Vec<SessionEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    pub fn state(&self) -> SessionState {
+        SessionState {
+            kind: self.kind,
+            planned: self.timer.duration(),
+            session_count: self.session_count,
+            focus_since_long_break: self.focus_since_long_break,
+            shortened: self.shortened,
+            extended: self.extended,
+            started_at: self.started_at,
+            ended: self.ended,
+            plan: self.plan.as_ref().map(|plan| plan.name.clone()),
+            plan_step: self.plan_step,
+            deadline: self.deadline,
+            pauses: self.pauses,
+            adjustments: self.adjustments.clone(),
+        }
+    }
+
+    pub fn timer(&self) -> &Timer {
+        &self.timer
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    /// Kind of the phase that will follow the current one, once it has ended
+    pub fn upcoming_kind(&self) -> PhaseKind {
+        if self.deadline.is_some() {
+            return next_kind(self.kind);
+        }
+        if self.kind == PhaseKind::WarmUp {
+            return self.cycle_start().0;
+        }
+        if let Some(phase) = self
+            .plan
+            .as_ref()
+            .and_then(|plan| plan.phase(self.plan_step + 1))
+        {
+            return phase.kind;
+        }
+        match self.kind {
+            PhaseKind::Pomodoro
+                if self
+                    .config
+                    .long_break_rule
+                    .is_long_break(self.session_count, self.focus_since_long_break) =>
+            {
+                PhaseKind::LongBreak
+            }
+            PhaseKind::Pomodoro => PhaseKind::ShortBreak,
+            _ => PhaseKind::Pomodoro,
+        }
+    }
+
+    pub fn kind(&self) -> PhaseKind {
+        self.kind
+    }
+
+    /// Pomodoros finished or skipped so far
+    pub fn session_count(&self) -> u32 {
+        self.session_count
+    }
+
+    /// Focus time since the last long break
+    pub fn focus_since_long_break(&self) -> Duration {
+        self.focus_since_long_break
+    }
+
+    pub fn was_shortened(&self) -> bool {
+        self.shortened
+    }
+
+    pub fn was_extended(&self) -> bool {
+        self.extended
+    }
+
+    /// The plan being followed, `None` for the standard cycle
+    pub fn plan(&self) -> Option<&SessionPlan> {
+        self.plan.as_ref()
+    }
+
+    /// Current phase of the plan, counting from 0
+    pub fn plan_step(&self) -> usize {
+        self.plan_step
+    }
+
+    /// Time the phases are being fitted to, if any
+    pub fn deadline(&self) -> Option<DateTime<Local>> {
+        self.deadline
+    }
+
+    /// The pomodoro ran out and is still counting
+    pub fn in_overtime(&self) -> bool {
+        self.timer.is_overtime()
+    }
+
+    /// When the current phase first started running
+    pub fn started_at(&self) -> Option<DateTime<Local>> {
+        self.started_at
+    }
+
+    /// Report the current phase as ended (at most once per phase)
+    fn end_phase(&mut self, outcome: SessionOutcome) {
+        if self.ended {
+            return;
+        }
+        self.ended = true;
+
+        let ended_at = self.clock.wall_now();
+        self.events.push(SessionEvent::PhaseEnded(PhaseSummary {
+            kind: self.kind,
+            outcome,
+            started_at: self.started_at.unwrap_or(ended_at),
+            ended_at,
+            planned: self.timer.duration(),
+            actual: if self.timer.is_open_ended() {
+                self.timer.elapsed()
+            } else {
+                self.timer.elapsed().min(self.timer.duration())
+            },
+            paused: self.timer.total_pause_duration(),
+            pauses: self.pauses,
+            overtime: self.timer.overtime(),
+            shortened: self.shortened,
+            extended: self.extended,
+            adjustments: self.adjustments.clone(),
+            started: self.timer.state() != TimerState::Idle,
+        }));
+    }
+
+    /// End the phase with `outcome` unless it never started, and set it up again
+    fn discard(&mut self, outcome: SessionOutcome) {
+        if self.timer.state() != TimerState::Idle {
+            self.end_phase(outcome);
+        }
+        self.timer.reset();
+        self.started_at = None;
+        self.ended = false;
+        self.overtime_notices = 0;
+        self.adjustments.clear();
+        self.pauses = 0;
+        self.pause_limit_reached = false;
+        self.held = false;
+        self.events.push(SessionEvent::PhaseReady {
+            kind: self.kind,
+            after: outcome,
+        });
+    }
+
+    /// Once a pause reaches the configured maximum, remind - or void the
+    /// pomodoro if the config follows the strict rules. Returns true if it
+    /// was voided. Holds don't count, the app is waiting on an answer.
+    fn check_pause_limit(&mut self) -> bool {
+        let Some(limit) = self.config.max_pause() else {
+            return false;
+        };
+        if self.held {
+            return false;
+        }
+        let pause = self.timer.current_pause();
+        if self.pause_limit_reached || pause < limit {
+            return false;
+        }
+        self.pause_limit_reached = true;
+        self.events.push(SessionEvent::PauseLimitReached(pause));
+        if self.config.on_max_pause == PauseLimitAction::Void && self.kind == PhaseKind::Pomodoro {
+            self.discard(SessionOutcome::Voided);
+            return true;
+        }
+        false
+    }
+
+    fn count_pomodoro(&mut self, focus: Duration) {
+        self.session_count += 1;
+        self.focus_since_long_break += focus;
+        self.last_focus = focus;
+    }
+
+    /// Announce overtime once, then every reminder interval while it runs
+    fn remind_overtime(&mut self) {
+        if self.timer.state() != TimerState::Running {
+            return;
+        }
+        let overtime = self.timer.overtime();
+        let due = match self.config.overtime_reminder_interval() {
+            Some(interval) => 1 + overtime.as_secs() / interval.as_secs(),
+            None => 1,
+        };
+        if self.overtime_notices == 0 {
+            self.events.push(SessionEvent::OvertimeStarted);
+        } else if due > self.overtime_notices {
+            self.events.push(SessionEvent::OvertimeReminder(overtime));
+        }
+        self.overtime_notices = due;
+    }
+
+    fn start_next_phase(&mut self, after: SessionOutcome) {
+        if self.deadline.is_some() {
+            let kind = next_kind(self.kind);
+            if let Some(duration) = self.deadline_duration(kind) {
+                self.start_phase(kind, duration, after);
+                return;
+            }
+        }
+
+        if self.kind == PhaseKind::WarmUp {
+            let (kind, duration) = self.cycle_start();
+            self.start_phase(kind, duration, after);
+            return;
+        }
+
+        if let Some(ref plan) = self.plan {
+            let step = self.plan_step + 1;
+            if let Some(phase) = plan.phase(step).cloned() {
+                self.plan_step = step;
+                if phase.kind == PhaseKind::LongBreak {
+                    self.focus_since_long_break = Duration::ZERO;
+                }
+                self.start_phase(phase.kind, phase.duration(), after);
+                return;
+            }
+            // The last phase is over, so the usual cycle takes it from here
+            self.events
+                .push(SessionEvent::PlanCompleted(plan.name.clone()));
+            self.plan = None;
+            self.plan_step = 0;
+        }
+
+        if self.kind == PhaseKind::Pomodoro {
+            self.start_break(after);
+        } else {
+            self.start_phase(PhaseKind::Pomodoro, self.work_duration(), after);
+        }
+    }
+
+    fn start_break(&mut self, after: SessionOutcome) {
+        let is_long_break = self
+            .config
+            .long_break_rule
+            .is_long_break(self.session_count, self.focus_since_long_break);
+        // Flowtime breaks scale with the work, and a long one is never shorter
+        // than the configured long break
+        let flowtime = (self.config.mode == SessionMode::Flowtime)
+            .then(|| self.config.flowtime.break_for(self.last_focus));
+        if is_long_break {
+            self.focus_since_long_break = Duration::ZERO;
+            let earned = flowtime.unwrap_or_default();
+            let duration = self.config.long_break_duration().max(earned);
+            self.start_phase(PhaseKind::LongBreak, duration, after);
+        } else {
+            let duration = flowtime.unwrap_or_else(|| self.config.short_break_duration());
+            self.start_phase(PhaseKind::ShortBreak, duration, after);
+        }
+    }
+
+    /// Set up an idle phase - nothing starts until [`SessionCommand::Start`]
+    fn start_phase(&mut self, kind: PhaseKind, duration: Duration, after: SessionOutcome) {
+        self.kind = kind;
+        self.timer = self.new_timer(duration);
+        self.shortened = false;
+        self.extended = false;
+        self.full_length = duration;
+        self.started_at = None;
+        self.ended = false;
+        self.overtime_notices = 0;
+        self.adjustments.clear();
+        self.pauses = 0;
+        self.pause_limit_reached = false;
+        self.held = false;
+        self.events.push(SessionEvent::PhaseReady { kind, after });
+    }
+
+    /// Time from now until the deadline, if there is one
+    fn time_to_deadline(&self) -> Option<Duration> {
+        let deadline = self.deadline?;
+        Some(
+            (deadline - self.clock.wall_now())
+                .to_std()
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Length of a `kind` phase starting now that fits before the deadline,
+    /// clearing the deadline once nothing fits any more
+    fn deadline_duration(&mut self, kind: PhaseKind) -> Option<Duration> {
+        let deadline = self.deadline?;
+        let now = self.clock.wall_now();
+        let duration = deadline::schedule(kind, now, deadline, &self.config)
+            .first()
+            .map(ScheduledPhase::duration);
+        if duration.is_none() {
+            self.reach_deadline();
+        }
+        duration
+    }
+
+    fn reach_deadline(&mut self) {
+        self.deadline = None;
+        self.events.push(SessionEvent::DeadlineReached);
+    }
+
+    /// Length of a `kind` phase in the usual cycle
+    fn full_duration(&self, kind: PhaseKind) -> Duration {
+        match kind {
+            PhaseKind::WarmUp => self
+                .warm_up()
+                .unwrap_or_else(|| self.config.warm_up_duration()),
+            PhaseKind::Pomodoro => self.work_duration(),
+            PhaseKind::ShortBreak => self.config.short_break_duration(),
+            PhaseKind::LongBreak => self.config.long_break_duration(),
+        }
+    }
+
+    /// Length the current phase gets when it's set up, following the plan
+    /// if there is one
+    fn set_up_length(&self) -> Duration {
+        let plan_phase = self
+            .plan
+            .as_ref()
+            .filter(|_| self.kind != PhaseKind::WarmUp)
+            .and_then(|plan| plan.phase(self.plan_step));
+        match plan_phase {
+            Some(phase) => phase.duration(),
+            None => self.full_duration(self.kind),
+        }
+    }
+
+    /// Length of the warm-up before the first pomodoro, if there is one.
+    /// The plan's own setting wins over the config
+    fn warm_up(&self) -> Option<Duration> {
+        let plan = self.plan.as_ref();
+        let enabled = plan
+            .and_then(|plan| plan.warm_up)
+            .unwrap_or(self.config.warm_up);
+        if !enabled {
+            return None;
+        }
+        let minutes = plan
+            .and_then(|plan| plan.warm_up_minutes)
+            .unwrap_or(self.config.warm_up_minutes);
+        Some(Duration::from_secs(minutes * 60))
+    }
+
+    /// Kind and length of the first phase, warming up before the first
+    /// pomodoro if that is turned on
+    fn first_phase(&self) -> (PhaseKind, Duration) {
+        match self.warm_up() {
+            Some(duration) if self.session_count == 0 => (PhaseKind::WarmUp, duration),
+            _ => self.cycle_start(),
+        }
+    }
+
+    /// Kind and length of the first phase of the plan or cycle
+    fn cycle_start(&self) -> (PhaseKind, Duration) {
+        match self.plan.as_ref().and_then(|plan| plan.phase(0)) {
+            Some(phase) => (phase.kind, phase.duration()),
+            None => (PhaseKind::Pomodoro, self.work_duration()),
+        }
+    }
+
+    /// Planned length of a pomodoro, zero when it is open-ended
+    fn work_duration(&self) -> Duration {
+        match self.config.mode {
+            SessionMode::Pomodoro => self.config.work_duration(),
+            SessionMode::Flowtime => Duration::ZERO,
+        }
+    }
+
+    fn new_timer(&self, duration: Duration) -> Timer {
+        let mut timer = Timer::with_clock(duration.as_secs(), self.clock.clone());
+        self.configure(&mut timer);
+        timer
+    }
+
+    /// Flowtime pomodoros count up, fixed ones may run into overtime
+    fn configure(&self, timer: &mut Timer) {
+        timer.set_open_ended(self.is_open_ended());
+        timer.set_overtime(self.allows_overtime());
+    }
+
+    /// The current phase is flowtime work, counting up with no set end
+    fn is_open_ended(&self) -> bool {
+        // Plans and deadlines set their own lengths
+        let fixed = self.plan.is_some() || self.deadline.is_some();
+        self.kind == PhaseKind::Pomodoro && self.config.mode == SessionMode::Flowtime && !fixed
+    }
+
+    /// The current phase keeps counting in overtime once it runs out
+    fn allows_overtime(&self) -> bool {
+        // A deadline must be hit
+        self.kind == PhaseKind::Pomodoro && self.config.overtime && self.deadline.is_none()
+    }
+}
+
+/// Work follows a break or warm-up and a short break follows work, when
+/// fitting to a deadline
+fn next_kind(kind: PhaseKind) -> PhaseKind {
+    match kind {
+        PhaseKind::Pomodoro => PhaseKind::ShortBreak,
+        _ => PhaseKind::Pomodoro,
+    }
+}
+
+/// The plan called `name`, unless it has no phases to step through
+fn usable_plan(config: &Config, name: &str) -> Option<SessionPlan> {
+    config
+        .find_plan(name)
+        .filter(|plan| !plan.is_empty())
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clock::ManualClock;
+    use crate::core::LongBreakRule;
+
+    fn manual_session(config: Config) -> (Session, Arc<ManualClock>) {
+        let clock = Arc::new(ManualClock::new());
+        (Session::with_clock(config, clock.clone()), clock)
+    }
+
+    /// Run the current phase to the end and move on to the next one
+    fn complete_phase(session: &mut Session, clock: &ManualClock) {
+        session.handle(SessionCommand::Start);
+        clock.advance(session.timer().duration());
+        assert!(session.tick());
+        session.handle(SessionCommand::Next);
+    }
+
+    #[test]
+    fn test_pomodoro_events() {
+        let (mut session, clock) = manual_session(Config::default());
+
+        session.handle(SessionCommand::Start);
+        clock.advance(Duration::from_secs(60));
+        session.handle(SessionCommand::Pause);
+        // Commands that don't apply are ignored
+        session.handle(SessionCommand::Pause);
+        session.handle(SessionCommand::Next);
+        clock.advance(Duration::from_secs(30));
+        session.handle(SessionCommand::Resume);
+        assert_eq!(
+            session.drain_events(),
+            vec![
+                SessionEvent::PhaseStarted(PhaseKind::Pomodoro),
+                SessionEvent::Paused(PhaseKind::Pomodoro),
+                SessionEvent::Resumed(PhaseKind::Pomodoro),
+            ]
+        );
+        assert!(session.drain_events().is_empty());
+
+        clock.advance(Duration::from_secs(24 * 60));
+        assert!(session.tick());
+        assert!(!session.tick());
+        let events = session.drain_events();
+        assert_eq!(events[0], SessionEvent::PhaseCompleted(PhaseKind::Pomodoro));
+        let SessionEvent::PhaseEnded(summary) = &events[1] else {
+            panic!("expected PhaseEnded, got {:?}", events[1]);
+        };
+        assert_eq!(summary.outcome, SessionOutcome::Completed);
+        assert_eq!(summary.actual, Duration::from_secs(25 * 60));
+        assert_eq!(summary.paused, Duration::from_secs(30));
+        assert_eq!(
+            (summary.ended_at - summary.started_at).num_seconds(),
+            25 * 60 + 30
+        );
+        assert_eq!(session.session_count(), 1);
+
+        session.handle(SessionCommand::Next);
+        assert_eq!(session.kind(), PhaseKind::ShortBreak);
+        assert_eq!(session.timer().state(), TimerState::Idle);
+        assert_eq!(
+            session.drain_events(),
+            vec![SessionEvent::PhaseReady {
+                kind: PhaseKind::ShortBreak,
+                after: SessionOutcome::Completed,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_skip_and_reset_end_the_phase_once() {
+        let (mut session, _clock) = manual_session(Config::default());
+
+        // Resetting an idle phase has nothing to report
+        session.handle(SessionCommand::Reset);
+        assert!(!session
+            .drain_events()
+            .iter()
+            .any(|event| matches!(event, SessionEvent::PhaseEnded(_))));
+
+        session.handle(SessionCommand::Start);
+        session.handle(SessionCommand::Skip);
+        let events = session.drain_events();
+        let ended: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                SessionEvent::PhaseEnded(summary) => Some(summary.outcome),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(ended, vec![SessionOutcome::Skipped]);
+        assert_eq!(session.session_count(), 1);
+        assert_eq!(session.kind(), PhaseKind::ShortBreak);
+
+        // Skipping a break doesn't count as a pomodoro
+        session.handle(SessionCommand::Skip);
+        assert_eq!(session.session_count(), 1);
+        assert_eq!(session.kind(), PhaseKind::Pomodoro);
+    }
+
+    #[test]
+    fn test_shorten_and_extend_long_break() {
+        let config = Config {
+            long_break_rule: LongBreakRule::EverySessions(1),
+            ..Config::default()
+        };
+        let (mut session, clock) = manual_session(config);
+
+        // Nothing to shorten or extend during a pomodoro
+        session.handle(SessionCommand::Shorten);
+        session.handle(SessionCommand::Extend);
+        assert_eq!(session.timer().duration().as_secs(), 25 * 60);
+
+        complete_phase(&mut session, &clock);
+        assert_eq!(session.kind(), PhaseKind::LongBreak);
+        session.drain_events();
+
+        session.handle(SessionCommand::Shorten);
+        assert_eq!(session.timer().duration().as_secs(), 5 * 60);
+        assert!(session.was_shortened());
+        session.handle(SessionCommand::Extend);
+        assert_eq!(session.timer().duration().as_secs(), 15 * 60);
+        assert!(!session.was_shortened());
+        assert!(session.was_extended());
+        assert_eq!(
+            session.drain_events(),
+            vec![SessionEvent::BreakShortened, SessionEvent::BreakExtended]
+        );
+    }
+
+    #[test]
+    fn test_long_break_after_cumulative_focus() {
+        let config = Config {
+            long_break_rule: LongBreakRule::AfterFocusMinutes(60),
+            ..Config::default()
+        };
+        let (mut session, clock) = manual_session(config);
+
+        // Two completed 25 minute pomodoros are not enough for a long break
+        for _ in 0..2 {
+            complete_phase(&mut session, &clock);
+            assert_eq!(session.kind(), PhaseKind::ShortBreak);
+            session.handle(SessionCommand::Skip);
+        }
+
+        // The third pushes cumulative focus past 60 minutes
+        complete_phase(&mut session, &clock);
+        assert_eq!(session.kind(), PhaseKind::LongBreak);
+        assert_eq!(session.timer().duration().as_secs(), 15 * 60);
+
+        // Cumulative focus starts over after the long break
+        complete_phase(&mut session, &clock);
+        complete_phase(&mut session, &clock);
+        assert_eq!(session.kind(), PhaseKind::ShortBreak);
+    }
+
+    #[test]
+    fn test_overtime_until_next() {
+        let config = Config {
+            overtime: true,
+            overtime_reminder_minutes: 5,
+            ..Config::default()
+        };
+        let (mut session, clock) = manual_session(config);
+        session.handle(SessionCommand::Start);
+        clock.advance(Duration::from_secs(25 * 60));
+        session.drain_events();
+
+        assert!(!session.tick());
+        assert!(session.in_overtime());
+        assert_eq!(session.timer().state(), TimerState::Running);
+        assert_eq!(session.drain_events(), vec![SessionEvent::OvertimeStarted]);
+
+        clock.advance(Duration::from_secs(4 * 60));
+        session.tick();
+        assert!(session.drain_events().is_empty());
+        clock.advance(Duration::from_secs(2 * 60));
+        session.tick();
+        session.tick();
+        assert_eq!(
+            session.drain_events(),
+            vec![SessionEvent::OvertimeReminder(Duration::from_secs(6 * 60))]
+        );
+
+        session.handle(SessionCommand::Next);
+        let events = session.drain_events();
+        let SessionEvent::PhaseEnded(summary) = &events[0] else {
+            panic!("expected PhaseEnded, got {:?}", events[0]);
+        };
+        assert_eq!(summary.outcome, SessionOutcome::Completed);
+        assert_eq!(summary.actual, Duration::from_secs(25 * 60));
+        assert_eq!(summary.overtime, Duration::from_secs(6 * 60));
+        assert_eq!(session.session_count(), 1);
+        assert_eq!(
+            session.focus_since_long_break(),
+            Duration::from_secs(31 * 60)
+        );
+        assert_eq!(session.kind(), PhaseKind::ShortBreak);
+
+        // Breaks never run over
+        session.handle(SessionCommand::Start);
+        clock.advance(Duration::from_secs(5 * 60));
+        assert!(session.tick());
+        assert!(!session.in_overtime());
+    }
+
+    #[test]
+    fn test_pause_limit_reminds_or_voids() {
+        let (mut session, clock) = manual_session(Config::default());
+        session.handle(SessionCommand::Start);
+        clock.advance(Duration::from_secs(5 * 60));
+        session.handle(SessionCommand::Pause);
+        session.drain_events();
+
+        clock.advance(Duration::from_secs(10 * 60));
+        assert!(!session.tick());
+        assert!(!session.tick());
+        assert_eq!(
+            session.drain_events(),
+            vec![SessionEvent::PauseLimitReached(Duration::from_secs(
+                10 * 60
+            ))]
+        );
+        assert_eq!(session.timer().state(), TimerState::Paused);
+
+        // Each new pause gets its own reminder
+        session.handle(SessionCommand::Resume);
+        session.handle(SessionCommand::Pause);
+        assert_eq!(session.state().pauses, 2);
+
+        let config = Config {
+            max_pause_minutes: 2,
+            on_max_pause: PauseLimitAction::Void,
+            ..Config::default()
+        };
+        let (mut session, clock) = manual_session(config);
+        session.handle(SessionCommand::Start);
+        session.handle(SessionCommand::Pause);
+        clock.advance(Duration::from_secs(60));
+        session.handle(SessionCommand::Resume);
+        session.handle(SessionCommand::Pause);
+        session.drain_events();
+
+        clock.advance(Duration::from_secs(2 * 60));
+        assert!(session.tick());
+        let events = session.drain_events();
+        assert_eq!(
+            events[0],
+            SessionEvent::PauseLimitReached(Duration::from_secs(2 * 60))
+        );
+        let SessionEvent::PhaseEnded(summary) = &events[1] else {
+            panic!("expected PhaseEnded, got {:?}", events[1]);
+        };
+        assert_eq!(summary.outcome, SessionOutcome::Voided);
+        assert_eq!(summary.pauses, 2);
+        assert_eq!(
+            events[2],
+            SessionEvent::PhaseReady {
+                kind: PhaseKind::Pomodoro,
+                after: SessionOutcome::Voided,
+            }
+        );
+        assert_eq!(session.timer().state(), TimerState::Idle);
+        assert_eq!(session.session_count(), 0);
+        assert_eq!(session.state().pauses, 0);
+    }
+
+    #[test]
+    fn test_flowtime_break_follows_work() {
+        let config = Config {
+            mode: SessionMode::Flowtime,
+            ..Config::default()
+        };
+        let (mut session, clock) = manual_session(config);
+        assert!(session.timer().is_open_ended());
+
+        session.handle(SessionCommand::Start);
+        clock.advance(Duration::from_secs(70 * 60));
+        assert!(!session.tick());
+        session.drain_events();
+
+        session.handle(SessionCommand::Next);
+        let events = session.drain_events();
+        let SessionEvent::PhaseEnded(summary) = &events[0] else {
+            panic!("expected PhaseEnded, got {:?}", events[0]);
+        };
+        assert_eq!(summary.outcome, SessionOutcome::Completed);
+        assert_eq!(summary.planned, Duration::ZERO);
+        assert_eq!(summary.actual, Duration::from_secs(70 * 60));
+        assert_eq!(summary.overtime, Duration::ZERO);
+        assert_eq!(session.session_count(), 1);
+
+        // 70 minutes at 1:5 earns a 14 minute break
+        assert_eq!(session.kind(), PhaseKind::ShortBreak);
+        assert_eq!(session.timer().duration(), Duration::from_secs(14 * 60));
+        assert!(!session.timer().is_open_ended());
+
+        complete_phase(&mut session, &clock);
+        assert_eq!(session.kind(), PhaseKind::Pomodoro);
+        assert!(session.timer().is_open_ended());
+    }
+
+    #[test]
+    fn test_flowtime_long_break_follows_the_rule() {
+        let config = Config {
+            mode: SessionMode::Flowtime,
+            long_break_rule: LongBreakRule::EverySessions(2),
+            ..Config::default()
+        };
+        let (mut session, clock) = manual_session(config);
+        let work = |session: &mut Session, minutes: u64| {
+            session.handle(SessionCommand::Start);
+            clock.advance(Duration::from_secs(minutes * 60));
+            session.handle(SessionCommand::Next);
+        };
+
+        work(&mut session, 20);
+        assert_eq!(session.kind(), PhaseKind::ShortBreak);
+        assert_eq!(session.timer().duration(), Duration::from_secs(5 * 60));
+        session.handle(SessionCommand::Skip);
+
+        // The long break is the longer of the configured one and the earned one
+        work(&mut session, 20);
+        assert_eq!(session.kind(), PhaseKind::LongBreak);
+        assert_eq!(session.timer().duration(), Duration::from_secs(15 * 60));
+        session.handle(SessionCommand::Skip);
+
+        work(&mut session, 20);
+        assert_eq!(session.kind(), PhaseKind::ShortBreak);
+        session.handle(SessionCommand::Skip);
+        work(&mut session, 100);
+        assert_eq!(session.kind(), PhaseKind::LongBreak);
+        assert_eq!(session.timer().duration(), Duration::from_secs(20 * 60));
+    }
+
+    #[test]
+    fn test_adjust_running_phase() {
+        let (mut session, clock) = manual_session(Config::default());
+        // Nothing to adjust until the timer runs
+        session.handle(SessionCommand::Adjust(5));
+        assert_eq!(session.timer().duration().as_secs(), 25 * 60);
+
+        session.handle(SessionCommand::Start);
+        clock.advance(Duration::from_secs(20 * 60));
+        session.handle(SessionCommand::Adjust(5));
+        session.handle(SessionCommand::Pause);
+        session.handle(SessionCommand::Adjust(-1));
+        assert_eq!(session.timer().duration().as_secs(), 29 * 60);
+
+        // Can't take off more than is left
+        session.handle(SessionCommand::Resume);
+        session.handle(SessionCommand::Adjust(-60));
+        assert_eq!(session.timer().duration().as_secs(), 20 * 60);
+        assert!(session.tick());
+
+        let summary = session
+            .drain_events()
+            .into_iter()
+            .find_map(|event| match event {
+                SessionEvent::PhaseEnded(summary) => Some(summary),
+                _ => None,
+            })
+            .unwrap();
+        let deltas: Vec<i64> = summary
+            .adjustments
+            .iter()
+            .map(|adjustment| adjustment.delta_secs)
+            .collect();
+        assert_eq!(deltas, vec![300, -60, -540]);
+        assert_eq!(summary.planned.as_secs(), 20 * 60);
+
+        // The next phase starts with a clean slate
+        session.handle(SessionCommand::Next);
+        session.handle(SessionCommand::Start);
+        session.handle(SessionCommand::Skip);
+        assert!(session.drain_events().iter().any(|event| matches!(
+            event,
+            SessionEvent::PhaseEnded(summary) if summary.adjustments.is_empty()
+        )));
+    }
+
+    #[test]
+    fn test_warm_up_before_the_first_pomodoro() {
+        let config = Config {
+            warm_up: true,
+            ..Config::default()
+        };
+        let (mut session, clock) = manual_session(config);
+        assert_eq!(session.kind(), PhaseKind::WarmUp);
+        assert_eq!(session.timer().duration().as_secs(), 3 * 60);
+
+        complete_phase(&mut session, &clock);
+        let events = session.drain_events();
+        assert!(events.contains(&SessionEvent::PhaseCompleted(PhaseKind::WarmUp)));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            SessionEvent::PhaseEnded(summary) if summary.kind == PhaseKind::WarmUp
+        )));
+        // The warm-up isn't a pomodoro and doesn't count towards the long break
+        assert_eq!(session.kind(), PhaseKind::Pomodoro);
+        assert_eq!(session.timer().duration().as_secs(), 25 * 60);
+        assert_eq!(session.session_count(), 0);
+        assert_eq!(session.focus_since_long_break(), Duration::ZERO);
+
+        // Only the first pomodoro gets one
+        complete_phase(&mut session, &clock);
+        complete_phase(&mut session, &clock);
+        assert_eq!(session.kind(), PhaseKind::Pomodoro);
+        assert!(session.select_plan(None));
+        assert_eq!(session.kind(), PhaseKind::Pomodoro);
+    }
+
+    #[test]
+    fn test_plans_set_their_own_warm_up() {
+        use crate::core::plan::PlanPhase;
+
+        let mut config = Config::default();
+        config.plans.push(
+            SessionPlan::new(
+                "slow start",
+                vec![
+                    PlanPhase::new(PhaseKind::Pomodoro, 50),
+                    PlanPhase::new(PhaseKind::LongBreak, 10),
+                ],
+                1,
+            )
+            .with_warm_up(5),
+        );
+        let (mut session, clock) = manual_session(config);
+        assert_eq!(session.kind(), PhaseKind::Pomodoro);
+
+        assert!(session.select_plan(Some("slow start")));
+        assert_eq!(session.kind(), PhaseKind::WarmUp);
+        assert_eq!(session.timer().duration().as_secs(), 5 * 60);
+        assert_eq!(session.upcoming_kind(), PhaseKind::Pomodoro);
+
+        // The plan's first phase follows the warm-up
+        complete_phase(&mut session, &clock);
+        assert_eq!(session.plan_step(), 0);
+        assert_eq!(session.kind(), PhaseKind::Pomodoro);
+        assert_eq!(session.timer().duration().as_secs(), 50 * 60);
+
+        // A plan can also turn the warm-up off
+        let mut config = Config {
+            warm_up: true,
+            ..Config::default()
+        };
+        config.plans[0].warm_up = Some(false);
+        let (mut session, _clock) = manual_session(config);
+        assert_eq!(session.kind(), PhaseKind::WarmUp);
+        assert!(session.select_plan(Some("deep work")));
+        assert_eq!(session.kind(), PhaseKind::Pomodoro);
+        assert_eq!(session.timer().duration().as_secs(), 90 * 60);
+    }
+
+    #[test]
+    fn test_plan_steps_through_its_phases() {
+        let (mut session, clock) = manual_session(Config::default());
+        assert!(!session.select_plan(Some("nap")));
+        assert!(session.select_plan(Some("deep work")));
+        assert_eq!(session.plan().unwrap().len(), 4);
+        assert_eq!(session.timer().duration().as_secs(), 90 * 60);
+
+        complete_phase(&mut session, &clock);
+        assert_eq!(session.plan_step(), 1);
+        assert_eq!(session.kind(), PhaseKind::ShortBreak);
+        assert_eq!(session.timer().duration().as_secs(), 20 * 60);
+
+        // Can't switch plans with a phase in progress
+        session.handle(SessionCommand::Start);
+        assert!(!session.select_plan(None));
+        session.handle(SessionCommand::Skip);
+        complete_phase(&mut session, &clock);
+        assert_eq!(session.plan_step(), 3);
+        session.drain_events();
+
+        let state = session.state();
+        assert_eq!(state.plan.as_deref(), Some("deep work"));
+
+        // After the last phase the plan ends and the usual cycle carries on
+        session.handle(SessionCommand::Skip);
+        assert!(session
+            .drain_events()
+            .contains(&SessionEvent::PlanCompleted("deep work".to_string())));
+        assert!(session.plan().is_none());
+        assert_eq!(session.plan_step(), 0);
+        assert_eq!(session.kind(), PhaseKind::Pomodoro);
+        assert_eq!(session.timer().duration().as_secs(), 25 * 60);
+        assert_eq!(session.upcoming_kind(), PhaseKind::ShortBreak);
+
+        assert!(session.select_plan(Some("deep work")));
+        assert!(session.select_plan(None));
+        assert!(session.plan().is_none());
+        assert_eq!(session.timer().duration().as_secs(), 25 * 60);
+    }
+
+    #[test]
+    fn test_extend_gives_a_plan_break_its_own_length_back() {
+        let (mut session, clock) = manual_session(Config::default());
+        assert!(session.select_plan(Some("deep work")));
+        complete_phase(&mut session, &clock);
+        assert_eq!(session.timer().duration().as_secs(), 20 * 60);
+
+        session.handle(SessionCommand::Shorten);
+        assert_eq!(session.timer().duration().as_secs(), 5 * 60);
+        session.handle(SessionCommand::Extend);
+        assert_eq!(session.timer().duration().as_secs(), 20 * 60);
+        assert!(session.was_extended());
+
+        // Also after picking the session up again
+        session.handle(SessionCommand::Shorten);
+        let state = session.state();
+        let (mut restored, _clock) = manual_session(Config::default());
+        restored.restore(state, Duration::ZERO, Duration::ZERO, TimerState::Idle);
+        restored.handle(SessionCommand::Extend);
+        assert_eq!(restored.timer().duration().as_secs(), 20 * 60);
+    }
+
+    #[test]
+    fn test_upcoming_kind() {
+        let config = Config {
+            long_break_rule: LongBreakRule::EverySessions(2),
+            ..Config::default()
+        };
+        let (mut session, clock) = manual_session(config);
+        session.handle(SessionCommand::Start);
+        clock.advance(session.timer().duration());
+        session.tick();
+        assert_eq!(session.upcoming_kind(), PhaseKind::ShortBreak);
+        session.handle(SessionCommand::Next);
+        assert_eq!(session.upcoming_kind(), PhaseKind::Pomodoro);
+        complete_phase(&mut session, &clock);
+        session.handle(SessionCommand::Start);
+        clock.advance(session.timer().duration());
+        session.tick();
+        assert_eq!(session.upcoming_kind(), PhaseKind::LongBreak);
+
+        // A plan says what comes next, even work after work
+        let (mut session, clock) = manual_session(Config::default());
+        assert!(session.select_plan(Some("study")));
+        for _ in 0..4 {
+            complete_phase(&mut session, &clock);
+        }
+        assert_eq!(session.upcoming_kind(), PhaseKind::Pomodoro);
+    }
+
+    #[test]
+    fn test_plan_from_config_and_restore() {
+        let config = Config {
+            plan: Some("study".to_string()),
+            ..Config::default()
+        };
+        let (session, _clock) = manual_session(config.clone());
+        assert_eq!(session.plan_step(), 0);
+        assert_eq!(session.timer().duration().as_secs(), 25 * 60);
+
+        let (mut restored, _clock) = manual_session(config);
+        let state = SessionState {
+            kind: PhaseKind::Pomodoro,
+            planned: Duration::from_secs(30 * 60),
+            plan_step: 5,
+            ..session.state()
+        };
+        restored.restore(state, Duration::ZERO, Duration::ZERO, TimerState::Idle);
+        assert_eq!(restored.plan_step(), 5);
+        let phase = restored.plan().unwrap().phase(5).unwrap();
+        assert_eq!(phase.label.as_deref(), Some("review"));
+    }
+
+    #[test]
+    fn test_deadline_fits_phases_and_ends_the_last_one_on_time() {
+        let config = Config {
+            overtime: true,
+            ..Config::default()
+        };
+        let (mut session, clock) = manual_session(config);
+        let minutes = |m: i64| chrono::Duration::minutes(m);
+        let deadline = clock.wall_now() + minutes(40);
+
+        // Nothing fits in under a minute
+        assert!(!session.set_deadline(Some(clock.wall_now() + chrono::Duration::seconds(30))));
+        assert!(session.set_deadline(Some(deadline)));
+        let schedule = session.schedule();
+        assert_eq!(schedule.len(), 3);
+        assert_eq!(schedule[2].end, deadline);
+        assert!(schedule[2].shortened);
+
+        // Waiting before starting leaves less time for the last pomodoro
+        clock.advance(Duration::from_secs(5 * 60));
+        complete_phase(&mut session, &clock);
+        assert!(!session.in_overtime());
+        complete_phase(&mut session, &clock);
+        assert_eq!(session.kind(), PhaseKind::Pomodoro);
+        assert_eq!(session.timer().duration(), Duration::from_secs(5 * 60));
+        session.drain_events();
+
+        session.handle(SessionCommand::Start);
+        clock.advance(Duration::from_secs(5 * 60));
+        assert!(session.tick());
+        assert!(session
+            .drain_events()
+            .contains(&SessionEvent::DeadlineReached));
+        assert!(session.deadline().is_none());
+        assert!(session.schedule().is_empty());
+
+        // Then the usual cycle carries on
+        session.handle(SessionCommand::Next);
+        assert_eq!(session.timer().duration(), Duration::from_secs(5 * 60));
+        assert_eq!(clock.wall_now(), deadline);
+    }
+
+    #[test]
+    fn test_adjustments_stop_at_the_deadline() {
+        let (mut session, clock) = manual_session(Config::default());
+        let deadline = clock.wall_now() + chrono::Duration::minutes(40);
+        assert!(session.set_deadline(Some(deadline)));
+        session.handle(SessionCommand::Start);
+        clock.advance(Duration::from_secs(10 * 60));
+
+        session.handle(SessionCommand::Adjust(10));
+        assert_eq!(session.timer().duration(), Duration::from_secs(35 * 60));
+        // Only the five minutes left before the deadline can be added
+        session.handle(SessionCommand::Adjust(10));
+        assert_eq!(session.timer().duration(), Duration::from_secs(40 * 60));
+        session.drain_events();
+        session.handle(SessionCommand::Adjust(1));
+        assert!(session.drain_events().is_empty());
+        assert_eq!(session.schedule().last().unwrap().end, deadline);
+
+        // Taking time off is always fine
+        session.handle(SessionCommand::Adjust(-5));
+        assert_eq!(session.timer().duration(), Duration::from_secs(35 * 60));
+    }
+
+    #[test]
+    fn test_restore_state() {
+        let (mut session, clock) = manual_session(Config::default());
+        complete_phase(&mut session, &clock);
+        session.handle(SessionCommand::Start);
+        clock.advance(Duration::from_secs(60));
+
+        let state = session.state();
+        let (mut restored, _clock) = manual_session(Config::default());
+        restored.restore(
+            state.clone(),
+            session.timer().elapsed(),
+            Duration::ZERO,
+            TimerState::Running,
+        );
+        assert_eq!(restored.state(), state);
+        assert_eq!(restored.timer().elapsed(), Duration::from_secs(60));
+        assert!(restored.drain_events().is_empty());
+    }
+
+    #[test]
+    fn test_restore_past_the_end() {
+        let (mut session, _clock) = manual_session(Config::default());
+        let state = session.state();
+        let closed = Duration::from_secs(40 * 60);
+
+        // A phase that ran out while closed stops at its end
+        session.restore(state.clone(), closed, Duration::ZERO, TimerState::Running);
+        assert_eq!(session.timer().elapsed(), state.planned);
+        assert!(session.tick());
+
+        // A pomodoro in overtime keeps the time it ran over
+        let config = Config {
+            overtime: true,
+            ..Config::default()
+        };
+        let (mut session, _clock) = manual_session(config);
+        session.restore(state, closed, Duration::ZERO, TimerState::Running);
+        assert!(!session.tick());
+        assert!(session.in_overtime());
+        assert_eq!(session.timer().overtime(), Duration::from_secs(15 * 60));
+    }
+
+    #[test]
+    fn test_restore_keeps_adjustments() {
+        let (mut session, _clock) = manual_session(Config::default());
+        session.handle(SessionCommand::Start);
+        session.handle(SessionCommand::Adjust(5));
+        let state = session.state();
+        assert_eq!(state.adjustments.len(), 1);
+
+        let (mut restored, _clock) = manual_session(Config::default());
+        restored.restore(state, Duration::ZERO, Duration::ZERO, TimerState::Running);
+        restored.handle(SessionCommand::Adjust(-1));
+        restored.handle(SessionCommand::Skip);
+        let summary = restored
+            .drain_events()
+            .into_iter()
+            .find_map(|event| match event {
+                SessionEvent::PhaseEnded(summary) => Some(summary),
+                _ => None,
+            })
+            .unwrap();
+        let deltas: Vec<i64> = summary
+            .adjustments
+            .iter()
+            .map(|adjustment| adjustment.delta_secs)
+            .collect();
+        assert_eq!(deltas, vec![300, -60]);
+    }
+}
@@ -0,0 +1,148 @@
+//! Noticing time the monotonic clock missed
+//!
+//! Timers measure with [`Clock::now`], which on Linux stands still while the
+//! machine is suspended. Comparing it with the wall clock on every tick shows
+//! when that happened, so the time can be accounted for instead of silently
+//! vanishing from a running pomodoro. The wall clock falling behind shows
+//! that it was set back, which timers don't notice but the history does.
+
+use super::clock::Clock;
+use chrono::{DateTime, Local};
+use std::time::{Duration, Instant};
+
+/// Differences shorter than this are a busy machine, not a suspend
+const MIN_GAP: Duration = Duration::from_secs(60);
+
+/// Stretch of wall-clock time the monotonic clock didn't see, or that the
+/// wall clock went back over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockGap {
+    pub started_at: DateTime<Local>,
+    /// Before `started_at` when the clock was set back
+    pub ended_at: DateTime<Local>,
+}
+
+impl ClockGap {
+    pub fn duration(&self) -> Duration {
+        (self.ended_at - self.started_at)
+            .abs()
+            .to_std()
+            .unwrap_or_default()
+    }
+
+    /// The wall clock jumped back rather than ahead, so no time was missed
+    pub fn is_backwards(&self) -> bool {
+        self.ended_at < self.started_at
+    }
+}
+
+/// What the time in a gap is counted as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GapUse {
+    Work,
+    Break,
+    /// Time away that is neither, e.g. the laptop slept in a bag
+    Neither,
+}
+
+impl GapUse {
+    pub fn key(&self) -> &'static str {
+        match self {
+            GapUse::Work => "work",
+            GapUse::Break => "break",
+            GapUse::Neither => "neither",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "work" => Some(GapUse::Work),
+            "break" => Some(GapUse::Break),
+            "neither" => Some(GapUse::Neither),
+            _ => None,
+        }
+    }
+}
+
+/// Compares the monotonic and wall clocks from one check to the next
+#[derive(Debug, Clone, Default)]
+pub struct GapDetector {
+    last: Option<(Instant, DateTime<Local>)>,
+}
+
+impl GapDetector {
+    /// The gap since the last check, if the wall clock ran at least a
+    /// minute ahead of the monotonic one or fell a minute behind it
+    pub fn check(&mut self, clock: &dyn Clock) -> Option<ClockGap> {
+        let now = (clock.now(), clock.wall_now());
+        let (last, last_wall) = self.last.replace(now)?;
+
+        let monotonic =
+            chrono::Duration::from_std(now.0.saturating_duration_since(last)).unwrap_or_default();
+        let drift = (now.1 - last_wall) - monotonic;
+        if drift.abs().to_std().unwrap_or_default() < MIN_GAP {
+            return None;
+        }
+        if drift > chrono::Duration::zero() {
+            Some(ClockGap {
+                started_at: last_wall,
+                ended_at: last_wall + drift,
+            })
+        } else {
+            // From where the clock should be to where it was set back to
+            Some(ClockGap {
+                started_at: last_wall + monotonic,
+                ended_at: now.1,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ManualClock;
+
+    #[test]
+    fn test_detects_time_the_monotonic_clock_missed() {
+        let clock = ManualClock::new();
+        let mut detector = GapDetector::default();
+        assert!(detector.check(&clock).is_none());
+
+        // Both clocks moving together is just time passing
+        clock.advance(Duration::from_secs(10 * 60));
+        assert!(detector.check(&clock).is_none());
+
+        let before = clock.wall_now();
+        clock.suspend(Duration::from_secs(2 * 3600));
+        clock.advance(Duration::from_secs(1));
+        let gap = detector.check(&clock).unwrap();
+        assert_eq!(gap.started_at, before);
+        assert_eq!(gap.duration(), Duration::from_secs(2 * 3600));
+        assert!(detector.check(&clock).is_none());
+
+        clock.suspend(Duration::from_secs(30));
+        assert!(detector.check(&clock).is_none());
+        assert_eq!(GapUse::from_key(GapUse::Break.key()), Some(GapUse::Break));
+    }
+
+    #[test]
+    fn test_detects_the_wall_clock_going_back() {
+        let clock = ManualClock::new();
+        let mut detector = GapDetector::default();
+        detector.check(&clock);
+
+        clock.advance(Duration::from_secs(5));
+        let expected = clock.wall_now();
+        clock.set_wall_back(Duration::from_secs(3600));
+        let gap = detector.check(&clock).unwrap();
+        assert!(gap.is_backwards());
+        assert_eq!(gap.started_at, expected);
+        assert_eq!(gap.ended_at, clock.wall_now());
+        assert_eq!(gap.duration(), Duration::from_secs(3600));
+        assert!(detector.check(&clock).is_none());
+
+        clock.set_wall_back(Duration::from_secs(30));
+        assert!(detector.check(&clock).is_none());
+    }
+}
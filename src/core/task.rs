@@ -0,0 +1,233 @@
+use chrono::{DateTime, Local};
+
+/// Age after which a task stops gaining weight in [`pick_weighted`]
+const MAX_PICK_AGE_DAYS: i64 = 28;
+
+/// How important a task is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+}
+
+impl Priority {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Priority::Low => "Low",
+            Priority::Medium => "Medium",
+            Priority::High => "High",
+        }
+    }
+
+    /// Stable identifier used when persisting the priority
+    pub fn key(&self) -> &'static str {
+        match self {
+            Priority::Low => "low",
+            Priority::Medium => "medium",
+            Priority::High => "high",
+        }
+    }
+
+    /// Parse an identifier produced by [`Priority::key`]
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "low" => Some(Priority::Low),
+            "medium" => Some(Priority::Medium),
+            "high" => Some(Priority::High),
+            _ => None,
+        }
+    }
+
+    /// Relative chance of being picked by [`pick_weighted`]
+    pub fn weight(&self) -> f64 {
+        match self {
+            Priority::Low => 1.0,
+            Priority::Medium => 2.0,
+            Priority::High => 4.0,
+        }
+    }
+
+    /// Next priority, wrapping from High back to Low
+    pub fn cycle(&self) -> Self {
+        match self {
+            Priority::Low => Priority::Medium,
+            Priority::Medium => Priority::High,
+            Priority::High => Priority::Low,
+        }
+    }
+}
+
+/// Whether a task still needs work
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskStatus {
+    Open,
+    Done,
+}
+
+impl TaskStatus {
+    pub fn key(&self) -> &'static str {
+        match self {
+            TaskStatus::Open => "open",
+            TaskStatus::Done => "done",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "open" => Some(TaskStatus::Open),
+            "done" => Some(TaskStatus::Done),
+            _ => None,
+        }
+    }
+}
+
+/// A unit of work that pomodoros are spent on
+#[derive(Debug, Clone, PartialEq)]
+pub struct Task {
+    /// Database id, `None` until the task is stored
+    pub id: Option<i64>,
+    pub title: String,
+    pub notes: String,
+    pub priority: Priority,
+    pub estimated_pomodoros: u32,
+    pub completed_pomodoros: u32,
+    pub status: TaskStatus,
+    pub tags: Vec<String>,
+    /// Manual sort order, lower comes first
+    pub position: i64,
+    pub created_at: DateTime<Local>,
+}
+
+impl Task {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            id: None,
+            title: title.into(),
+            notes: String::new(),
+            priority: Priority::Medium,
+            estimated_pomodoros: 1,
+            completed_pomodoros: 0,
+            status: TaskStatus::Open,
+            tags: Vec::new(),
+            position: 0,
+            created_at: Local::now(),
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.status == TaskStatus::Done
+    }
+
+    /// Pomodoros left according to the estimate
+    pub fn remaining_pomodoros(&self) -> u32 {
+        self.estimated_pomodoros
+            .saturating_sub(self.completed_pomodoros)
+    }
+
+    /// Weight used by [`pick_weighted`]: the priority weight, plus a share per week of age
+    pub fn pick_weight(&self, now: DateTime<Local>) -> f64 {
+        let age_days = (now - self.created_at)
+            .num_days()
+            .clamp(0, MAX_PICK_AGE_DAYS);
+        self.priority.weight() * (1.0 + age_days as f64 / 7.0)
+    }
+
+    /// Parse a comma-separated tag list, dropping blanks and duplicates
+    pub fn parse_tags(input: &str) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in input.split(',') {
+            let tag = tag.trim().trim_start_matches('#').to_lowercase();
+            if !tag.is_empty() && !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        tags
+    }
+}
+
+/// Pick an open task at random, favouring high priority and older tasks
+///
+/// `roll` is a uniformly distributed number in `[0, 1)`.
+pub fn pick_weighted(tasks: &[Task], now: DateTime<Local>, roll: f64) -> Option<&Task> {
+    let open: Vec<&Task> = tasks.iter().filter(|task| !task.is_done()).collect();
+    let total: f64 = open.iter().map(|task| task.pick_weight(now)).sum();
+    let mut target = roll.clamp(0.0, 1.0) * total;
+
+    for task in &open {
+        let weight = task.pick_weight(now);
+        if target < weight {
+            return Some(task);
+        }
+        target -= weight;
+    }
+    open.last().copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_new_task_defaults() {
+        let task = Task::new("Write report");
+        assert_eq!(task.title, "Write report");
+        assert_eq!(task.priority, Priority::Medium);
+        assert_eq!(task.status, TaskStatus::Open);
+        assert_eq!(task.remaining_pomodoros(), 1);
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        assert_eq!(
+            Task::parse_tags(" Work, #deep , ,work,admin"),
+            vec!["work", "deep", "admin"]
+        );
+        assert!(Task::parse_tags("").is_empty());
+    }
+
+    #[test]
+    fn test_priority_cycle_and_keys() {
+        assert_eq!(Priority::Low.cycle(), Priority::Medium);
+        assert_eq!(Priority::High.cycle(), Priority::Low);
+        for priority in [Priority::Low, Priority::Medium, Priority::High] {
+            assert_eq!(Priority::from_key(priority.key()), Some(priority));
+        }
+    }
+
+    #[test]
+    fn test_pick_weight_grows_with_priority_and_age() {
+        let now = Local::now();
+        let mut task = Task::new("Old");
+        task.created_at = now;
+        assert_eq!(task.pick_weight(now), 2.0);
+
+        task.priority = Priority::High;
+        task.created_at = now - Duration::days(7);
+        assert_eq!(task.pick_weight(now), 8.0);
+
+        // Age stops counting after four weeks
+        task.created_at = now - Duration::days(365);
+        assert_eq!(task.pick_weight(now), 20.0);
+    }
+
+    #[test]
+    fn test_pick_weighted() {
+        let now = Local::now();
+        let mut low = Task::new("Low");
+        low.priority = Priority::Low;
+        let mut high = Task::new("High");
+        high.priority = Priority::High;
+        let mut done = Task::new("Done");
+        done.status = TaskStatus::Done;
+        let tasks = vec![done, low, high];
+
+        // Low takes the first fifth of the range, High the rest
+        assert_eq!(pick_weighted(&tasks, now, 0.0).unwrap().title, "Low");
+        assert_eq!(pick_weighted(&tasks, now, 0.19).unwrap().title, "Low");
+        assert_eq!(pick_weighted(&tasks, now, 0.21).unwrap().title, "High");
+        assert_eq!(pick_weighted(&tasks, now, 1.0).unwrap().title, "High");
+        assert!(pick_weighted(&tasks[..1], now, 0.5).is_none());
+    }
+}
@@ -1,5 +1,10 @@
+use super::clock::{Clock, SystemClock};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+/// Shortest duration an adjustment can leave a timer with
+const MIN_ADJUSTED_SECS: u64 = 60;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TimerState {
     Idle,
@@ -8,6 +13,27 @@ pub enum TimerState {
     Completed,
 }
 
+impl TimerState {
+    pub fn key(&self) -> &'static str {
+        match self {
+            TimerState::Idle => "idle",
+            TimerState::Running => "running",
+            TimerState::Paused => "paused",
+            TimerState::Completed => "completed",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "idle" => Some(TimerState::Idle),
+            "running" => Some(TimerState::Running),
+            "paused" => Some(TimerState::Paused),
+            "completed" => Some(TimerState::Completed),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Timer {
     start: Option<Instant>,
@@ -15,29 +41,78 @@ pub struct Timer {
     paused_at: Option<Instant>,
     total_pause_duration: Duration,
     state: TimerState,
+    overtime: bool,
+    open_ended: bool,
+    clock: Arc<dyn Clock>,
 }
 
 impl Timer {
     pub fn new(seconds: u64) -> Self {
+        Self::with_clock(seconds, SystemClock::shared())
+    }
+
+    /// Create a timer that reads the time from `clock`
+    pub fn with_clock(seconds: u64, clock: Arc<dyn Clock>) -> Self {
         Timer {
             start: None,
             duration: Duration::from_secs(seconds),
             paused_at: None,
             total_pause_duration: Duration::ZERO,
             state: TimerState::Idle,
+            overtime: false,
+            open_ended: false,
+            clock,
+        }
+    }
+
+    /// Rebuild a timer that had already run for `elapsed` with `paused` spent paused
+    pub fn restore(
+        duration: Duration,
+        elapsed: Duration,
+        paused: Duration,
+        state: TimerState,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        let mut timer = Timer {
+            start: None,
+            duration,
+            paused_at: None,
+            total_pause_duration: Duration::ZERO,
+            state,
+            overtime: false,
+            open_ended: false,
+            clock,
+        };
+        if state == TimerState::Idle {
+            return timer;
         }
+
+        let now = timer.clock.now();
+        // The monotonic clock may not reach back far enough after a reboot;
+        // keep the elapsed time and drop the pause history in that case
+        match now.checked_sub(elapsed + paused) {
+            Some(start) => {
+                timer.start = Some(start);
+                timer.total_pause_duration = paused;
+            }
+            None => timer.start = Some(now.checked_sub(elapsed).unwrap_or(now)),
+        }
+        if state == TimerState::Paused {
+            timer.paused_at = Some(now);
+        }
+        timer
     }
 
     pub fn start(&mut self) {
         if self.state == TimerState::Idle {
-            self.start = Some(Instant::now());
+            self.start = Some(self.clock.now());
             self.state = TimerState::Running;
         }
     }
 
     pub fn pause(&mut self) {
         if self.state == TimerState::Running {
-            self.paused_at = Some(Instant::now());
+            self.paused_at = Some(self.clock.now());
             self.state = TimerState::Paused;
         }
     }
@@ -45,7 +120,7 @@ impl Timer {
     pub fn resume(&mut self) {
         if self.state == TimerState::Paused {
             if let Some(paused_at) = self.paused_at {
-                self.total_pause_duration += paused_at.elapsed();
+                self.total_pause_duration += self.since(paused_at);
                 self.paused_at = None;
                 self.state = TimerState::Running;
             }
@@ -66,15 +141,17 @@ impl Timer {
     pub fn elapsed(&self) -> Duration {
         match self.start {
             Some(start) => {
-                let base_elapsed = start.elapsed();
+                let base_elapsed = self.since(start);
                 let current_pause = if self.state == TimerState::Paused {
                     self.paused_at
-                        .map(|p| p.elapsed())
+                        .map(|p| self.since(p))
                         .unwrap_or(Duration::ZERO)
                 } else {
                     Duration::ZERO
                 };
-                base_elapsed - self.total_pause_duration - current_pause
+                base_elapsed
+                    .saturating_sub(self.total_pause_duration)
+                    .saturating_sub(current_pause)
             }
             None => Duration::ZERO,
         }
@@ -90,10 +167,13 @@ impl Timer {
     }
 
     pub fn is_expired(&self) -> bool {
-        self.state == TimerState::Running && self.elapsed() >= self.duration
+        !self.open_ended && self.state == TimerState::Running && self.elapsed() >= self.duration
     }
 
     pub fn progress(&self) -> f64 {
+        if self.open_ended {
+            return 0.0;
+        }
         if self.duration.as_secs() == 0 {
             return 1.0;
         }
@@ -103,7 +183,7 @@ impl Timer {
     }
 
     pub fn state(&self) -> TimerState {
-        if self.is_expired() {
+        if self.is_expired() && !self.overtime {
             TimerState::Completed
         } else {
             self.state
@@ -113,4 +193,272 @@ impl Timer {
     pub fn duration(&self) -> Duration {
         self.duration
     }
+
+    /// Lengthen the timer by `seconds`, or shorten it when negative
+    ///
+    /// The duration never drops below a minute or below the time already
+    /// elapsed, so taking off more than is left ends the timer on the next
+    /// check instead of somewhere in the past. Open-ended timers have no
+    /// duration to change. Returns the change actually made, in seconds.
+    pub fn adjust(&mut self, seconds: i64) -> i64 {
+        if self.open_ended {
+            return 0;
+        }
+        let current = self.duration.as_secs();
+        let floor = self.elapsed().as_secs().max(MIN_ADJUSTED_SECS).min(current);
+        let target = current.saturating_add_signed(seconds).max(floor);
+        self.duration = Duration::from_secs(target);
+        target as i64 - current as i64
+    }
+
+    /// Count `time` the clock didn't see as spent running
+    pub fn credit(&mut self, time: Duration) {
+        if let Some(start) = self.start.and_then(|start| start.checked_sub(time)) {
+            self.start = Some(start);
+        }
+    }
+
+    /// Keep running past the duration instead of completing
+    pub fn set_overtime(&mut self, enabled: bool) {
+        self.overtime = enabled;
+    }
+
+    pub fn allows_overtime(&self) -> bool {
+        self.overtime
+    }
+
+    /// Count up with no end, ignoring the duration
+    pub fn set_open_ended(&mut self, enabled: bool) {
+        self.open_ended = enabled;
+    }
+
+    pub fn is_open_ended(&self) -> bool {
+        self.open_ended
+    }
+
+    /// Running or paused and counting up, either open-ended or in overtime
+    pub fn is_counting_up(&self) -> bool {
+        matches!(self.state, TimerState::Running | TimerState::Paused)
+            && (self.open_ended || self.is_overtime())
+    }
+
+    /// Running or paused past the duration with overtime allowed
+    pub fn is_overtime(&self) -> bool {
+        self.overtime
+            && !self.open_ended
+            && matches!(self.state, TimerState::Running | TimerState::Paused)
+            && self.elapsed() >= self.duration
+    }
+
+    /// Time counted past the duration, zero unless overtime is allowed
+    pub fn overtime(&self) -> Duration {
+        if self.overtime && !self.open_ended {
+            self.elapsed().saturating_sub(self.duration)
+        } else {
+            Duration::ZERO
+        }
+    }
+
+    /// Total time spent paused, including a pause still in progress
+    pub fn total_pause_duration(&self) -> Duration {
+        let current_pause = if self.state == TimerState::Paused {
+            self.paused_at
+                .map(|p| self.since(p))
+                .unwrap_or(Duration::ZERO)
+        } else {
+            Duration::ZERO
+        };
+        self.total_pause_duration + current_pause
+    }
+
+    /// How long the timer has been paused this time, zero unless paused
+    pub fn current_pause(&self) -> Duration {
+        match (self.state, self.paused_at) {
+            (TimerState::Paused, Some(paused_at)) => self.since(paused_at),
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Time on this timer's clock since `instant`
+    fn since(&self, instant: Instant) -> Duration {
+        self.clock.now().saturating_duration_since(instant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clock::ManualClock;
+
+    #[test]
+    fn test_restore_running_and_paused() {
+        let duration = Duration::from_secs(25 * 60);
+        let elapsed = Duration::from_secs(10 * 60);
+        let paused = Duration::from_secs(60);
+
+        let running = Timer::restore(
+            duration,
+            elapsed,
+            paused,
+            TimerState::Running,
+            SystemClock::shared(),
+        );
+        assert_eq!(running.state(), TimerState::Running);
+        assert_eq!(running.elapsed().as_secs(), 10 * 60);
+        assert_eq!(running.total_pause_duration().as_secs(), 60);
+
+        let mut paused_timer = Timer::restore(
+            duration,
+            elapsed,
+            paused,
+            TimerState::Paused,
+            SystemClock::shared(),
+        );
+        assert_eq!(paused_timer.state(), TimerState::Paused);
+        assert_eq!(paused_timer.remaining().as_secs(), 15 * 60);
+        paused_timer.resume();
+        assert_eq!(paused_timer.state(), TimerState::Running);
+        assert_eq!(paused_timer.elapsed().as_secs(), 10 * 60);
+    }
+
+    #[test]
+    fn test_restore_idle_and_completed() {
+        let duration = Duration::from_secs(60);
+        let idle = Timer::restore(
+            duration,
+            Duration::from_secs(30),
+            Duration::ZERO,
+            TimerState::Idle,
+            SystemClock::shared(),
+        );
+        assert_eq!(idle.elapsed(), Duration::ZERO);
+
+        let completed = Timer::restore(
+            duration,
+            duration * 2,
+            Duration::ZERO,
+            TimerState::Completed,
+            SystemClock::shared(),
+        );
+        assert_eq!(completed.state(), TimerState::Completed);
+        assert_eq!(completed.remaining(), Duration::ZERO);
+        assert!(!completed.is_expired());
+
+        for state in [
+            TimerState::Idle,
+            TimerState::Running,
+            TimerState::Paused,
+            TimerState::Completed,
+        ] {
+            assert_eq!(TimerState::from_key(state.key()), Some(state));
+        }
+    }
+
+    #[test]
+    fn test_full_pomodoro_on_manual_clock() {
+        let clock = Arc::new(ManualClock::new());
+        let mut timer = Timer::with_clock(25 * 60, clock.clone());
+        timer.start();
+
+        clock.advance(Duration::from_secs(10 * 60));
+        timer.pause();
+        // Time spent paused doesn't count
+        clock.advance(Duration::from_secs(5 * 60));
+        assert_eq!(timer.elapsed(), Duration::from_secs(10 * 60));
+        assert_eq!(timer.total_pause_duration(), Duration::from_secs(5 * 60));
+        timer.resume();
+
+        clock.advance(Duration::from_secs(15 * 60) - Duration::from_millis(1));
+        assert_eq!(timer.state(), TimerState::Running);
+        assert_eq!(timer.remaining(), Duration::from_millis(1));
+
+        clock.advance(Duration::from_millis(1));
+        assert!(timer.is_expired());
+        assert_eq!(timer.state(), TimerState::Completed);
+        assert_eq!(timer.progress(), 1.0);
+    }
+
+    #[test]
+    fn test_overtime_keeps_counting() {
+        let clock = Arc::new(ManualClock::new());
+        let mut timer = Timer::with_clock(60, clock.clone());
+        timer.set_overtime(true);
+        timer.start();
+
+        clock.advance(Duration::from_secs(60));
+        assert!(timer.is_overtime());
+        assert_eq!(timer.state(), TimerState::Running);
+        assert_eq!(timer.overtime(), Duration::ZERO);
+
+        clock.advance(Duration::from_secs(192));
+        assert_eq!(timer.remaining(), Duration::ZERO);
+        assert_eq!(timer.overtime(), Duration::from_secs(192));
+
+        // Pausing in overtime stops the extra time too
+        timer.pause();
+        clock.advance(Duration::from_secs(30));
+        assert!(timer.is_overtime());
+        assert_eq!(timer.overtime(), Duration::from_secs(192));
+
+        timer.stop();
+        assert!(!timer.is_overtime());
+        assert_eq!(timer.state(), TimerState::Completed);
+    }
+
+    #[test]
+    fn test_adjust_keeps_duration_past_elapsed() {
+        let clock = Arc::new(ManualClock::new());
+        let mut timer = Timer::with_clock(10 * 60, clock.clone());
+        timer.start();
+        clock.advance(Duration::from_secs(8 * 60));
+
+        assert_eq!(timer.adjust(5 * 60), 5 * 60);
+        assert_eq!(timer.remaining(), Duration::from_secs(7 * 60));
+
+        // Taking off more than is left stops at the time already elapsed
+        assert_eq!(timer.adjust(-10 * 60), -7 * 60);
+        assert_eq!(timer.duration(), Duration::from_secs(8 * 60));
+        assert!(timer.is_expired());
+
+        let mut idle = Timer::with_clock(2 * 60, clock.clone());
+        assert_eq!(idle.adjust(-5 * 60), -60);
+        assert_eq!(idle.duration(), Duration::from_secs(60));
+
+        let mut open = Timer::with_clock(0, clock);
+        open.set_open_ended(true);
+        assert_eq!(open.adjust(60), 0);
+    }
+
+    #[test]
+    fn test_credit_counts_missed_time() {
+        let clock = Arc::new(ManualClock::new());
+        let mut timer = Timer::with_clock(25 * 60, clock.clone());
+        timer.credit(Duration::from_secs(60));
+        assert_eq!(timer.elapsed(), Duration::ZERO);
+
+        timer.start();
+        clock.advance(Duration::from_secs(5 * 60));
+        timer.pause();
+        timer.credit(Duration::from_secs(30 * 60));
+        assert_eq!(timer.elapsed(), Duration::from_secs(35 * 60));
+        assert_eq!(timer.total_pause_duration(), Duration::ZERO);
+        timer.resume();
+        assert!(timer.is_expired());
+    }
+
+    #[test]
+    fn test_open_ended_never_expires() {
+        let clock = Arc::new(ManualClock::new());
+        let mut timer = Timer::with_clock(0, clock.clone());
+        timer.set_open_ended(true);
+        assert!(!timer.is_counting_up());
+        timer.start();
+
+        clock.advance(Duration::from_secs(3 * 60 * 60));
+        assert!(!timer.is_expired());
+        assert!(timer.is_counting_up());
+        assert_eq!(timer.state(), TimerState::Running);
+        assert_eq!(timer.elapsed(), Duration::from_secs(3 * 60 * 60));
+        assert_eq!(timer.progress(), 0.0);
+    }
 }
@@ -3,6 +3,9 @@
 
 #[cfg(feature = "audio")]
 pub mod audio;
+pub mod cli;
 pub mod core;
 pub mod integrations;
+pub mod storage;
+#[cfg(feature = "tui")]
 pub mod tui;
@@ -1,10 +1,47 @@
+use pomo_tui::cli::{CliArgs, USAGE};
+use pomo_tui::core::{Config, VirtualClock};
 use pomo_tui::tui::App;
 use std::error::Error;
+use std::sync::Arc;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
+    let args = match CliArgs::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if args.help {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    if args.version {
+        println!("pomo-tui {}", env!("CARGO_PKG_VERSION"));
+        return Ok(());
+    }
+
+    // Config file values, overridden by command-line flags
+    let mut config = Config::load()?;
+    args.apply(&mut config);
+    config.validate()?;
+    if args.save {
+        config.save()?;
+    }
+
     // Initialize and run the TUI application
-    let mut app = App::new()?;
+    let mut app = match args.time_warp {
+        Some(factor) => App::with_clock(config, Arc::new(VirtualClock::new(factor)))?,
+        None => App::with_config(config)?,
+    };
+    if let Some(time) = args.until {
+        if !app.work_until(time) {
+            eprintln!("Not enough time left before {}", time.format("%H:%M"));
+            std::process::exit(2);
+        }
+    }
     app.run().await?;
     Ok(())
 }
@@ -0,0 +1,109 @@
+//! Breadcrumbs - where a pomodoro left off, to pick the thread up after
+//! the break
+
+use super::{session, Storage, StorageError};
+use chrono::{DateTime, Duration as ChronoDuration, Local, NaiveDate, NaiveTime};
+use rusqlite::{params, OptionalExtension};
+
+/// A note left at the end of a pomodoro
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breadcrumb {
+    pub written_at: DateTime<Local>,
+    /// Task the pomodoro was for, if any
+    pub task_id: Option<i64>,
+    pub note: String,
+}
+
+impl Storage {
+    pub fn record_breadcrumb(&self, breadcrumb: &Breadcrumb) -> Result<i64, StorageError> {
+        self.conn.execute(
+            "INSERT INTO breadcrumbs (written_at, task_id, note) VALUES (?1, ?2, ?3)",
+            params![
+                breadcrumb.written_at.timestamp(),
+                breadcrumb.task_id,
+                breadcrumb.note,
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Latest breadcrumb left on `day` for the task, or for work without a
+    /// task when `task_id` is `None`
+    pub fn last_breadcrumb(
+        &self,
+        task_id: Option<i64>,
+        day: NaiveDate,
+    ) -> Result<Option<Breadcrumb>, StorageError> {
+        let start_of = |day: NaiveDate| {
+            day.and_time(NaiveTime::MIN)
+                .and_local_timezone(Local)
+                .earliest()
+                .map(|time| time.timestamp())
+        };
+        let (Some(from), Some(to)) = (start_of(day), start_of(day + ChronoDuration::days(1)))
+        else {
+            return Ok(None);
+        };
+
+        let row = self
+            .conn
+            .query_row(
+                "SELECT written_at, note FROM breadcrumbs
+                 WHERE task_id IS ?1 AND written_at >= ?2 AND written_at < ?3
+                 ORDER BY written_at DESC, id DESC LIMIT 1",
+                params![task_id, from, to],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()?;
+        Ok(row.map(|(written_at, note)| Breadcrumb {
+            written_at: session::from_timestamp(written_at),
+            task_id,
+            note,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Task;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_last_breadcrumb_per_task_and_day() {
+        let storage = Storage::open_in_memory().unwrap();
+        let at = |day, hour| Local.with_ymd_and_hms(2024, 3, day, hour, 0, 0).unwrap();
+        let task_id = storage.add_task(&Task::new("Write the report")).unwrap();
+        let crumb = |written_at, task_id, note: &str| Breadcrumb {
+            written_at,
+            task_id,
+            note: note.to_string(),
+        };
+        let latest = crumb(at(14, 11), Some(task_id), "fix the intro next");
+        for breadcrumb in [
+            crumb(at(13, 16), Some(task_id), "yesterday's note"),
+            crumb(at(14, 10), Some(task_id), "halfway through section 2"),
+            latest.clone(),
+            crumb(at(14, 12), None, "inbox zero"),
+        ] {
+            storage.record_breadcrumb(&breadcrumb).unwrap();
+        }
+
+        let day = |day| NaiveDate::from_ymd_opt(2024, 3, day).unwrap();
+        assert_eq!(
+            storage.last_breadcrumb(Some(task_id), day(14)).unwrap(),
+            Some(latest)
+        );
+        assert_eq!(
+            storage
+                .last_breadcrumb(None, day(14))
+                .unwrap()
+                .map(|breadcrumb| breadcrumb.note),
+            Some("inbox zero".to_string())
+        );
+        assert_eq!(
+            storage.last_breadcrumb(Some(task_id), day(15)).unwrap(),
+            None
+        );
+    }
+}
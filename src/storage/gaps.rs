@@ -0,0 +1,92 @@
+//! Clock gaps - time a running timer missed, e.g. while the machine slept
+
+use super::{session, PhaseKind, Storage, StorageError};
+use crate::core::{ClockGap, GapUse};
+use chrono::{DateTime, Local};
+use rusqlite::params;
+
+/// A clock gap and what the user counted it as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GapRecord {
+    pub gap: ClockGap,
+    /// Phase that was running when the gap happened
+    pub phase: PhaseKind,
+    pub counted_as: GapUse,
+}
+
+impl Storage {
+    pub fn record_gap(&self, record: &GapRecord) -> Result<i64, StorageError> {
+        self.conn.execute(
+            "INSERT INTO clock_gaps (started_at, ended_at, phase, counted_as)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                record.gap.started_at.timestamp(),
+                record.gap.ended_at.timestamp(),
+                record.phase.key(),
+                record.counted_as.key(),
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// All gaps that started in `[from, to)`, oldest first
+    pub fn gaps_between(
+        &self,
+        from: DateTime<Local>,
+        to: DateTime<Local>,
+    ) -> Result<Vec<GapRecord>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT started_at, ended_at, phase, counted_as FROM clock_gaps
+             WHERE started_at >= ?1 AND started_at < ?2
+             ORDER BY started_at, id",
+        )?;
+        let rows = stmt.query_map(params![from.timestamp(), to.timestamp()], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+
+        rows.map(|row| {
+            let (started_at, ended_at, phase, counted_as) = row?;
+            Ok(GapRecord {
+                gap: ClockGap {
+                    started_at: session::from_timestamp(started_at),
+                    ended_at: session::from_timestamp(ended_at),
+                },
+                phase: PhaseKind::from_key(&phase)
+                    .ok_or_else(|| StorageError::InvalidData(format!("gap phase '{}'", phase)))?,
+                counted_as: GapUse::from_key(&counted_as).ok_or_else(|| {
+                    StorageError::InvalidData(format!("gap counted as '{}'", counted_as))
+                })?,
+            })
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_record_and_read_back_gap() {
+        let storage = Storage::open_in_memory().unwrap();
+        let at = |hour| Local.with_ymd_and_hms(2024, 3, 14, hour, 0, 0).unwrap();
+        let record = GapRecord {
+            gap: ClockGap {
+                started_at: at(10),
+                ended_at: at(12),
+            },
+            phase: PhaseKind::Pomodoro,
+            counted_as: GapUse::Neither,
+        };
+        storage.record_gap(&record).unwrap();
+
+        assert_eq!(storage.gaps_between(at(9), at(11)).unwrap(), vec![record]);
+        assert!(storage.gaps_between(at(11), at(13)).unwrap().is_empty());
+    }
+}
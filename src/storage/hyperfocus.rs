@@ -0,0 +1,101 @@
+//! Log of the times the hyperfocus guard stepped in
+
+use super::{session, Storage, StorageError};
+use crate::core::GuardLevel;
+use chrono::{DateTime, Local};
+use rusqlite::params;
+use std::time::Duration;
+
+/// The guard stepping in, and why
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuardRecord {
+    pub fired_at: DateTime<Local>,
+    pub level: GuardLevel,
+    /// Focus time since the last real break
+    pub focus: Duration,
+    pub skipped_breaks: u32,
+}
+
+impl Storage {
+    pub fn record_guard(&self, record: &GuardRecord) -> Result<i64, StorageError> {
+        self.conn.execute(
+            "INSERT INTO hyperfocus_log (fired_at, level, focus_secs, skipped_breaks)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                record.fired_at.timestamp(),
+                record.level.key(),
+                record.focus.as_secs() as i64,
+                record.skipped_breaks,
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Every time the guard stepped in during `[from, to)`, oldest first
+    pub fn guard_log_between(
+        &self,
+        from: DateTime<Local>,
+        to: DateTime<Local>,
+    ) -> Result<Vec<GuardRecord>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT fired_at, level, focus_secs, skipped_breaks FROM hyperfocus_log
+             WHERE fired_at >= ?1 AND fired_at < ?2
+             ORDER BY fired_at, id",
+        )?;
+        let rows = stmt.query_map(params![from.timestamp(), to.timestamp()], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, u32>(3)?,
+            ))
+        })?;
+
+        rows.map(|row| {
+            let (fired_at, level, focus_secs, skipped_breaks) = row?;
+            Ok(GuardRecord {
+                fired_at: session::from_timestamp(fired_at),
+                level: GuardLevel::from_key(&level)
+                    .ok_or_else(|| StorageError::InvalidData(format!("guard level '{}'", level)))?,
+                focus: Duration::from_secs(focus_secs.max(0) as u64),
+                skipped_breaks,
+            })
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_record_and_read_back_guard_log() {
+        let storage = Storage::open_in_memory().unwrap();
+        let at = |hour| Local.with_ymd_and_hms(2024, 3, 14, hour, 0, 0).unwrap();
+        let note = GuardRecord {
+            fired_at: at(10),
+            level: GuardLevel::Note,
+            focus: Duration::from_secs(95 * 60),
+            skipped_breaks: 0,
+        };
+        let block = GuardRecord {
+            fired_at: at(12),
+            level: GuardLevel::Block,
+            focus: Duration::from_secs(60 * 60),
+            skipped_breaks: 3,
+        };
+        storage.record_guard(&block).unwrap();
+        storage.record_guard(&note).unwrap();
+
+        assert_eq!(
+            storage.guard_log_between(at(9), at(13)).unwrap(),
+            vec![note, block]
+        );
+        assert!(storage
+            .guard_log_between(at(13), at(14))
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,107 @@
+//! Distraction inbox items waiting to be sorted out
+
+use super::{session, Storage, StorageError};
+use crate::core::InboxItem;
+use chrono::NaiveDate;
+use rusqlite::params;
+
+/// Format of `scheduled_for` in the database
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+impl Storage {
+    pub fn add_inbox_item(&self, item: &InboxItem) -> Result<i64, StorageError> {
+        self.conn.execute(
+            "INSERT INTO inbox (captured_at, text, scheduled_for) VALUES (?1, ?2, ?3)",
+            params![
+                item.captured_at.timestamp(),
+                item.text,
+                item.scheduled_for
+                    .map(|day| day.format(DATE_FORMAT).to_string()),
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Everything in the inbox - unscheduled items first, then by the day
+    /// they were put off until, oldest first within each
+    pub fn inbox_items(&self) -> Result<Vec<InboxItem>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, captured_at, text, scheduled_for FROM inbox
+             ORDER BY scheduled_for IS NOT NULL, scheduled_for, captured_at, id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ))
+        })?;
+
+        rows.map(|row| {
+            let (id, captured_at, text, scheduled_for) = row?;
+            let scheduled_for = scheduled_for
+                .map(|day| {
+                    NaiveDate::parse_from_str(&day, DATE_FORMAT).map_err(|_| {
+                        StorageError::InvalidData(format!("inbox scheduled for '{}'", day))
+                    })
+                })
+                .transpose()?;
+            Ok(InboxItem {
+                id: Some(id),
+                captured_at: session::from_timestamp(captured_at),
+                text,
+                scheduled_for,
+            })
+        })
+        .collect()
+    }
+
+    /// Put an item off until `day`
+    pub fn schedule_inbox_item(&self, id: i64, day: NaiveDate) -> Result<(), StorageError> {
+        self.conn.execute(
+            "UPDATE inbox SET scheduled_for = ?1 WHERE id = ?2",
+            params![day.format(DATE_FORMAT).to_string(), id],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_inbox_item(&self, id: i64) -> Result<(), StorageError> {
+        self.conn
+            .execute("DELETE FROM inbox WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Local, TimeZone};
+
+    #[test]
+    fn test_add_schedule_and_delete_inbox_items() {
+        let storage = Storage::open_in_memory().unwrap();
+        let at = |hour| Local.with_ymd_and_hms(2024, 3, 14, hour, 0, 0).unwrap();
+        let dentist = storage
+            .add_inbox_item(&InboxItem::new("Book the dentist", at(10)))
+            .unwrap();
+        let plants = storage
+            .add_inbox_item(&InboxItem::new("Water the plants", at(11)))
+            .unwrap();
+        storage
+            .add_inbox_item(&InboxItem::new("Look up that song", at(12)))
+            .unwrap();
+
+        let tomorrow = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        storage.schedule_inbox_item(dentist, tomorrow).unwrap();
+        storage.delete_inbox_item(plants).unwrap();
+
+        let items = storage.inbox_items().unwrap();
+        let texts: Vec<&str> = items.iter().map(|item| item.text.as_str()).collect();
+        assert_eq!(texts, vec!["Look up that song", "Book the dentist"]);
+        assert_eq!(items[0].scheduled_for, None);
+        assert_eq!(items[0].captured_at, at(12));
+        assert_eq!(items[1].id, Some(dentist));
+        assert_eq!(items[1].scheduled_for, Some(tomorrow));
+    }
+}
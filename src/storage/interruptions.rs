@@ -0,0 +1,112 @@
+//! Interruptions logged during pomodoros
+
+use super::{session, Storage, StorageError};
+use crate::core::{Interruption, InterruptionKind};
+use chrono::{DateTime, Local};
+use rusqlite::params;
+
+/// An interruption and the task that was being worked on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterruptionRecord {
+    pub interruption: Interruption,
+    pub task_id: Option<i64>,
+}
+
+impl Storage {
+    pub fn record_interruption(&self, record: &InterruptionRecord) -> Result<i64, StorageError> {
+        let interruption = &record.interruption;
+        self.conn.execute(
+            "INSERT INTO interruptions (logged_at, kind, note, paused, task_id)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                interruption.logged_at.timestamp(),
+                interruption.kind.key(),
+                interruption.note,
+                interruption.paused,
+                record.task_id,
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// All interruptions logged in `[from, to)`, oldest first
+    pub fn interruptions_between(
+        &self,
+        from: DateTime<Local>,
+        to: DateTime<Local>,
+    ) -> Result<Vec<InterruptionRecord>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT logged_at, kind, note, paused, task_id FROM interruptions
+             WHERE logged_at >= ?1 AND logged_at < ?2
+             ORDER BY logged_at, id",
+        )?;
+        let rows = stmt.query_map(params![from.timestamp(), to.timestamp()], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, bool>(3)?,
+                row.get::<_, Option<i64>>(4)?,
+            ))
+        })?;
+
+        rows.map(|row| {
+            let (logged_at, kind, note, paused, task_id) = row?;
+            Ok(InterruptionRecord {
+                interruption: Interruption {
+                    logged_at: session::from_timestamp(logged_at),
+                    kind: InterruptionKind::from_key(&kind).ok_or_else(|| {
+                        StorageError::InvalidData(format!("interruption kind '{}'", kind))
+                    })?,
+                    note,
+                    paused,
+                },
+                task_id,
+            })
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Task;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_record_and_read_back_interruptions() {
+        let storage = Storage::open_in_memory().unwrap();
+        let at = |hour| Local.with_ymd_and_hms(2024, 3, 14, hour, 0, 0).unwrap();
+        let task_id = storage.add_task(&Task::new("Write the report")).unwrap();
+        let internal = InterruptionRecord {
+            interruption: Interruption {
+                logged_at: at(10),
+                kind: InterruptionKind::Internal,
+                note: Some("check the build".to_string()),
+                paused: false,
+            },
+            task_id: Some(task_id),
+        };
+        let external = InterruptionRecord {
+            interruption: Interruption {
+                logged_at: at(12),
+                kind: InterruptionKind::External,
+                note: None,
+                paused: true,
+            },
+            task_id: None,
+        };
+        storage.record_interruption(&external).unwrap();
+        storage.record_interruption(&internal).unwrap();
+
+        assert_eq!(
+            storage.interruptions_between(at(9), at(13)).unwrap(),
+            vec![internal, external]
+        );
+        assert!(storage
+            .interruptions_between(at(13), at(14))
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+//! Schema migrations
+//!
+//! Each entry is applied once, in order, and the schema version is tracked
+//! with SQLite's `user_version` pragma. Never edit a released migration -
+//! append a new one instead.
+
+use super::StorageError;
+use rusqlite::Connection;
+
+const MIGRATIONS: &[&str] = &[
+    // 1: session history
+    "CREATE TABLE sessions (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        kind TEXT NOT NULL,
+        outcome TEXT NOT NULL,
+        started_at INTEGER NOT NULL,
+        ended_at INTEGER NOT NULL,
+        planned_secs INTEGER NOT NULL,
+        actual_secs INTEGER NOT NULL,
+        paused_secs INTEGER NOT NULL,
+        shortened INTEGER NOT NULL DEFAULT 0,
+        extended INTEGER NOT NULL DEFAULT 0,
+        break_activity TEXT,
+        breathing_pattern TEXT
+    );
+    CREATE INDEX idx_sessions_started_at ON sessions(started_at);",
+    // 2: tasks, and the task each pomodoro was spent on
+    "CREATE TABLE tasks (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        title TEXT NOT NULL,
+        notes TEXT NOT NULL DEFAULT '',
+        priority TEXT NOT NULL,
+        estimated_pomodoros INTEGER NOT NULL DEFAULT 1,
+        completed_pomodoros INTEGER NOT NULL DEFAULT 0,
+        status TEXT NOT NULL,
+        tags TEXT NOT NULL DEFAULT '[]',
+        position INTEGER NOT NULL,
+        created_at INTEGER NOT NULL
+    );
+    ALTER TABLE sessions ADD COLUMN task_id INTEGER REFERENCES tasks(id) ON DELETE SET NULL;",
+    // 3: in-progress session, so it can be resumed after a restart
+    "CREATE TABLE session_snapshot (
+        id INTEGER PRIMARY KEY CHECK (id = 1),
+        saved_at INTEGER NOT NULL,
+        kind TEXT NOT NULL,
+        timer_state TEXT NOT NULL,
+        planned_ms INTEGER NOT NULL,
+        elapsed_ms INTEGER NOT NULL,
+        paused_ms INTEGER NOT NULL,
+        session_count INTEGER NOT NULL,
+        focus_since_long_break_secs INTEGER NOT NULL,
+        shortened INTEGER NOT NULL DEFAULT 0,
+        extended INTEGER NOT NULL DEFAULT 0,
+        break_activity TEXT,
+        breathing_pattern TEXT,
+        breathing_enabled INTEGER NOT NULL DEFAULT 1,
+        breathing_complete INTEGER NOT NULL DEFAULT 0,
+        phase_started_at INTEGER,
+        phase_recorded INTEGER NOT NULL DEFAULT 0,
+        task_id INTEGER
+    );",
+    // 4: extra time a pomodoro ran past its planned duration
+    "ALTER TABLE sessions ADD COLUMN overtime_secs INTEGER NOT NULL DEFAULT 0;",
+    // 5: session plan being followed, and how far along it is
+    "ALTER TABLE session_snapshot ADD COLUMN plan TEXT;
+    ALTER TABLE session_snapshot ADD COLUMN plan_step INTEGER NOT NULL DEFAULT 0;",
+    // 6: deadline the phases are being fitted to
+    "ALTER TABLE session_snapshot ADD COLUMN deadline INTEGER;",
+    // 7: minutes added to or taken off a session while it ran
+    "CREATE TABLE adjustments (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        session_id INTEGER NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+        adjusted_at INTEGER NOT NULL,
+        delta_secs INTEGER NOT NULL
+    );
+    CREATE INDEX idx_adjustments_session_id ON adjustments(session_id);",
+    // 8: time the timer missed while the machine slept, and what it counted as
+    "CREATE TABLE clock_gaps (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        started_at INTEGER NOT NULL,
+        ended_at INTEGER NOT NULL,
+        phase TEXT NOT NULL,
+        counted_as TEXT NOT NULL
+    );
+    CREATE INDEX idx_clock_gaps_started_at ON clock_gaps(started_at);",
+    // 9: every time the hyperfocus guard stepped in
+    "CREATE TABLE hyperfocus_log (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        fired_at INTEGER NOT NULL,
+        level TEXT NOT NULL,
+        focus_secs INTEGER NOT NULL,
+        skipped_breaks INTEGER NOT NULL
+    );
+    CREATE INDEX idx_hyperfocus_log_fired_at ON hyperfocus_log(fired_at);",
+    // 10: how many times each session, and the one in progress, was paused
+    "ALTER TABLE sessions ADD COLUMN pause_count INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE session_snapshot ADD COLUMN pause_count INTEGER NOT NULL DEFAULT 0;",
+    // 11: interruptions logged during pomodoros
+    "CREATE TABLE interruptions (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        logged_at INTEGER NOT NULL,
+        kind TEXT NOT NULL,
+        note TEXT,
+        paused INTEGER NOT NULL,
+        task_id INTEGER REFERENCES tasks(id) ON DELETE SET NULL
+    );
+    CREATE INDEX idx_interruptions_logged_at ON interruptions(logged_at);",
+    // 12: why a voided pomodoro was thrown away
+    "ALTER TABLE sessions ADD COLUMN void_reason TEXT;",
+    // 13: what each pomodoro was meant to achieve, and whether it did
+    "ALTER TABLE sessions ADD COLUMN intention TEXT;
+    ALTER TABLE sessions ADD COLUMN intention_result TEXT;
+    ALTER TABLE session_snapshot ADD COLUMN intention TEXT;",
+    // 14: where each pomodoro left off, to pick up after the break
+    "CREATE TABLE breadcrumbs (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        written_at INTEGER NOT NULL,
+        task_id INTEGER REFERENCES tasks(id) ON DELETE SET NULL,
+        note TEXT NOT NULL
+    );
+    CREATE INDEX idx_breadcrumbs_written_at ON breadcrumbs(written_at);",
+    // 15: energy and mood as each pomodoro started and ended
+    "ALTER TABLE sessions ADD COLUMN energy_before INTEGER;
+    ALTER TABLE sessions ADD COLUMN mood_before INTEGER;
+    ALTER TABLE sessions ADD COLUMN energy_after INTEGER;
+    ALTER TABLE sessions ADD COLUMN mood_after INTEGER;
+    ALTER TABLE session_snapshot ADD COLUMN energy INTEGER;
+    ALTER TABLE session_snapshot ADD COLUMN mood INTEGER;",
+    // 16: thoughts parked during pomodoros, waiting to be sorted out
+    "CREATE TABLE inbox (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        captured_at INTEGER NOT NULL,
+        text TEXT NOT NULL,
+        scheduled_for TEXT
+    );",
+    // 17: minutes added to or taken off the session in progress
+    "CREATE TABLE snapshot_adjustments (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        adjusted_at INTEGER NOT NULL,
+        delta_secs INTEGER NOT NULL
+    );",
+];
+
+/// Latest schema version known to this build
+pub fn latest_version() -> u32 {
+    MIGRATIONS.len() as u32
+}
+
+/// Current schema version of the database
+pub fn current_version(conn: &Connection) -> Result<u32, StorageError> {
+    Ok(conn.query_row("PRAGMA user_version", [], |row| row.get(0))?)
+}
+
+/// Apply all pending migrations
+pub fn run(conn: &mut Connection) -> Result<(), StorageError> {
+    let current = current_version(conn)?;
+    let latest = latest_version();
+
+    if current > latest {
+        return Err(StorageError::SchemaTooNew(current, latest));
+    }
+
+    for (index, sql) in MIGRATIONS.iter().enumerate().skip(current as usize) {
+        let tx = conn.transaction()?;
+        tx.execute_batch(sql)?;
+        tx.pragma_update(None, "user_version", index as u32 + 1)?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,565 @@
+//! Persistent session history backed by SQLite
+//!
+//! The database lives at `$DATABASE_URL` when set (e.g. `sqlite:./data/pomo.db`),
+//! otherwise in the platform data directory under `pomo-tui/pomo.db`.
+
+mod breadcrumbs;
+mod gaps;
+mod hyperfocus;
+mod inbox;
+mod interruptions;
+mod migrations;
+mod session;
+mod snapshot;
+mod stats;
+mod tasks;
+
+pub use breadcrumbs::Breadcrumb;
+pub use gaps::GapRecord;
+pub use hyperfocus::GuardRecord;
+pub use interruptions::InterruptionRecord;
+pub use session::{
+    IntentionResult, PhaseKind, SessionOutcome, SessionRecord, TimeAdjustment, VoidReason,
+};
+pub use snapshot::{ResumeMode, SessionSnapshot};
+pub use stats::{Completion, DayStats, Stats, PARTS_OF_DAY};
+
+use crate::core::{BreakActivity, BreathingPattern, CheckIn};
+use chrono::{DateTime, Duration as ChronoDuration, Local, NaiveTime};
+use rusqlite::{params, Connection, Row};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Storage-related errors
+#[derive(Debug, Error)]
+pub enum StorageError {
+    #[error("Database error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("No data directory found")]
+    NoDataDir,
+
+    #[error("Database schema version {0} is newer than supported version {1}")]
+    SchemaTooNew(u32, u32),
+
+    #[error("Invalid stored data: {0}")]
+    InvalidData(String),
+}
+
+/// Handle to the session history database
+pub struct Storage {
+    conn: Connection,
+}
+
+impl Storage {
+    /// Open (or create) the database at the given path and run migrations
+    pub fn open(path: &Path) -> Result<Self, StorageError> {
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    /// Open a throwaway in-memory database
+    pub fn open_in_memory() -> Result<Self, StorageError> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    /// Open the database at the configured default location
+    pub fn open_default() -> Result<Self, StorageError> {
+        Self::open(&Self::default_path()?)
+    }
+
+    /// Resolve the database path from `DATABASE_URL` or the data directory
+    pub fn default_path() -> Result<PathBuf, StorageError> {
+        if let Ok(url) = std::env::var("DATABASE_URL") {
+            let path = url
+                .strip_prefix("sqlite://")
+                .or_else(|| url.strip_prefix("sqlite:"))
+                .unwrap_or(&url);
+            if !path.is_empty() {
+                return Ok(PathBuf::from(path));
+            }
+        }
+
+        let mut path = dirs::data_dir().ok_or(StorageError::NoDataDir)?;
+        path.push("pomo-tui");
+        path.push("pomo.db");
+        Ok(path)
+    }
+
+    fn from_connection(mut conn: Connection) -> Result<Self, StorageError> {
+        conn.pragma_update(None, "foreign_keys", "ON")?;
+        migrations::run(&mut conn)?;
+        Ok(Self { conn })
+    }
+
+    /// Schema version currently applied to the database
+    pub fn schema_version(&self) -> Result<u32, StorageError> {
+        migrations::current_version(&self.conn)
+    }
+
+    /// Store a finished session and its time adjustments, and return its id
+    pub fn record_session(&self, record: &SessionRecord) -> Result<i64, StorageError> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT INTO sessions (
+                kind, outcome, started_at, ended_at, planned_secs, actual_secs,
+                paused_secs, shortened, extended, break_activity, breathing_pattern, task_id,
+                overtime_secs, pause_count, void_reason, intention, intention_result,
+                energy_before, mood_before, energy_after, mood_after
+            ) VALUES (
+                ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17,
+                ?18, ?19, ?20, ?21
+            )",
+            params![
+                record.kind.key(),
+                record.outcome.key(),
+                record.started_at.timestamp(),
+                record.ended_at.timestamp(),
+                record.planned.as_secs() as i64,
+                record.actual.as_secs() as i64,
+                record.paused.as_secs() as i64,
+                record.shortened,
+                record.extended,
+                record.break_activity.map(|a| a.key()),
+                record.breathing_pattern.map(|p| p.key()),
+                record.task_id,
+                record.overtime.as_secs() as i64,
+                record.pause_count,
+                record.void_reason.map(|r| r.key()),
+                record.intention,
+                record.intention_result.map(|r| r.key()),
+                record.check_in_before.map(|c| c.energy),
+                record.check_in_before.map(|c| c.mood),
+                record.check_in_after.map(|c| c.energy),
+                record.check_in_after.map(|c| c.mood),
+            ],
+        )?;
+        let id = tx.last_insert_rowid();
+        for adjustment in &record.adjustments {
+            tx.execute(
+                "INSERT INTO adjustments (session_id, adjusted_at, delta_secs) VALUES (?1, ?2, ?3)",
+                params![id, adjustment.at.timestamp(), adjustment.delta_secs],
+            )?;
+        }
+        tx.commit()?;
+        Ok(id)
+    }
+
+    /// Store whether a session did what it was meant to
+    pub fn set_intention_result(
+        &self,
+        id: i64,
+        result: IntentionResult,
+    ) -> Result<(), StorageError> {
+        self.conn.execute(
+            "UPDATE sessions SET intention_result = ?1 WHERE id = ?2",
+            params![result.key(), id],
+        )?;
+        Ok(())
+    }
+
+    /// Store how someone felt once a session was over
+    pub fn set_check_in_after(&self, id: i64, check_in: CheckIn) -> Result<(), StorageError> {
+        self.conn.execute(
+            "UPDATE sessions SET energy_after = ?1, mood_after = ?2 WHERE id = ?3",
+            params![check_in.energy, check_in.mood, id],
+        )?;
+        Ok(())
+    }
+
+    /// Forget a stored session, along with its time adjustments
+    pub fn delete_session(&self, id: i64) -> Result<(), StorageError> {
+        self.conn
+            .execute("DELETE FROM sessions WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    /// All sessions that started in `[from, to)`, oldest first
+    pub fn sessions_between(
+        &self,
+        from: DateTime<Local>,
+        to: DateTime<Local>,
+    ) -> Result<Vec<SessionRecord>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, kind, outcome, started_at, ended_at, planned_secs, actual_secs,
+                    paused_secs, shortened, extended, break_activity, breathing_pattern, task_id,
+                    overtime_secs, pause_count, void_reason, intention, intention_result,
+                    energy_before, mood_before, energy_after, mood_after
+             FROM sessions
+             WHERE started_at >= ?1 AND started_at < ?2
+             ORDER BY started_at, id",
+        )?;
+
+        let rows = stmt.query_map(
+            params![from.timestamp(), to.timestamp()],
+            RawSession::from_row,
+        )?;
+        let mut records = rows
+            .map(|row| row?.into_record())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut stmt = self.conn.prepare(
+            "SELECT a.session_id, a.adjusted_at, a.delta_secs
+             FROM adjustments a JOIN sessions s ON s.id = a.session_id
+             WHERE s.started_at >= ?1 AND s.started_at < ?2
+             ORDER BY a.id",
+        )?;
+        let rows = stmt.query_map(params![from.timestamp(), to.timestamp()], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get(2)?))
+        })?;
+        for row in rows {
+            let (session_id, adjusted_at, delta_secs) = row?;
+            if let Some(record) = records
+                .iter_mut()
+                .find(|record| record.id == Some(session_id))
+            {
+                record.adjustments.push(TimeAdjustment {
+                    at: session::from_timestamp(adjusted_at),
+                    delta_secs,
+                });
+            }
+        }
+        Ok(records)
+    }
+
+    /// Statistics for the last `days` days, including today
+    pub fn stats(&self, days: u32) -> Result<Stats, StorageError> {
+        let now = Local::now();
+        let today = now.date_naive();
+        let first_day = today - ChronoDuration::days(days.max(1) as i64 - 1);
+        let from = first_day
+            .and_time(NaiveTime::MIN)
+            .and_local_timezone(Local)
+            .earliest()
+            .unwrap_or(now - ChronoDuration::days(days as i64));
+
+        let to = now + ChronoDuration::seconds(1);
+        let records = self.sessions_between(from, to)?;
+        let mut stats = Stats::from_records(&records, today, days);
+
+        let interruptions = self.interruptions_between(from, to)?;
+        let mut titles = Vec::new();
+        for id in interruptions.iter().filter_map(|record| record.task_id) {
+            if !titles.iter().any(|(task_id, _)| *task_id == id) {
+                if let Some(task) = self.task(id)? {
+                    titles.push((id, task.title));
+                }
+            }
+        }
+        stats.add_interruptions(&interruptions, |id| {
+            titles
+                .iter()
+                .find(|(task_id, _)| *task_id == id)
+                .map(|(_, title)| title.clone())
+        });
+        Ok(stats)
+    }
+}
+
+/// Row as stored, before enum keys are parsed
+struct RawSession {
+    id: i64,
+    kind: String,
+    outcome: String,
+    started_at: i64,
+    ended_at: i64,
+    planned_secs: i64,
+    actual_secs: i64,
+    paused_secs: i64,
+    shortened: bool,
+    extended: bool,
+    break_activity: Option<String>,
+    breathing_pattern: Option<String>,
+    task_id: Option<i64>,
+    overtime_secs: i64,
+    pause_count: u32,
+    void_reason: Option<String>,
+    intention: Option<String>,
+    intention_result: Option<String>,
+    energy_before: Option<u8>,
+    mood_before: Option<u8>,
+    energy_after: Option<u8>,
+    mood_after: Option<u8>,
+}
+
+impl RawSession {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            kind: row.get(1)?,
+            outcome: row.get(2)?,
+            started_at: row.get(3)?,
+            ended_at: row.get(4)?,
+            planned_secs: row.get(5)?,
+            actual_secs: row.get(6)?,
+            paused_secs: row.get(7)?,
+            shortened: row.get(8)?,
+            extended: row.get(9)?,
+            break_activity: row.get(10)?,
+            breathing_pattern: row.get(11)?,
+            task_id: row.get(12)?,
+            overtime_secs: row.get(13)?,
+            pause_count: row.get(14)?,
+            void_reason: row.get(15)?,
+            intention: row.get(16)?,
+            intention_result: row.get(17)?,
+            energy_before: row.get(18)?,
+            mood_before: row.get(19)?,
+            energy_after: row.get(20)?,
+            mood_after: row.get(21)?,
+        })
+    }
+
+    fn into_record(self) -> Result<SessionRecord, StorageError> {
+        let kind = PhaseKind::from_key(&self.kind)
+            .ok_or_else(|| StorageError::InvalidData(format!("session kind '{}'", self.kind)))?;
+        let outcome = SessionOutcome::from_key(&self.outcome).ok_or_else(|| {
+            StorageError::InvalidData(format!("session outcome '{}'", self.outcome))
+        })?;
+
+        Ok(SessionRecord {
+            id: Some(self.id),
+            kind,
+            outcome,
+            started_at: session::from_timestamp(self.started_at),
+            ended_at: session::from_timestamp(self.ended_at),
+            planned: secs(self.planned_secs),
+            actual: secs(self.actual_secs),
+            paused: secs(self.paused_secs),
+            pause_count: self.pause_count,
+            overtime: secs(self.overtime_secs),
+            shortened: self.shortened,
+            extended: self.extended,
+            break_activity: self
+                .break_activity
+                .as_deref()
+                .and_then(BreakActivity::from_key),
+            breathing_pattern: self
+                .breathing_pattern
+                .as_deref()
+                .and_then(BreathingPattern::from_key),
+            task_id: self.task_id,
+            void_reason: self.void_reason.as_deref().and_then(VoidReason::from_key),
+            intention: self.intention,
+            intention_result: self
+                .intention_result
+                .as_deref()
+                .and_then(IntentionResult::from_key),
+            check_in_before: CheckIn::from_levels(self.energy_before, self.mood_before),
+            check_in_after: CheckIn::from_levels(self.energy_after, self.mood_after),
+            adjustments: Vec::new(),
+        })
+    }
+}
+
+fn secs(value: i64) -> Duration {
+    Duration::from_secs(value.max(0) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration as ChronoDuration;
+
+    fn sample_record(kind: PhaseKind, outcome: SessionOutcome) -> SessionRecord {
+        let ended_at = Local::now();
+        let started_at = ended_at - ChronoDuration::minutes(25);
+        let mut record = SessionRecord::new(
+            kind,
+            outcome,
+            started_at,
+            ended_at,
+            Duration::from_secs(25 * 60),
+        );
+        record.actual = Duration::from_secs(20 * 60);
+        record.paused = Duration::from_secs(5 * 60);
+        record
+    }
+
+    #[test]
+    fn test_migrations_reach_latest_version() {
+        let storage = Storage::open_in_memory().unwrap();
+        assert_eq!(
+            storage.schema_version().unwrap(),
+            migrations::latest_version()
+        );
+    }
+
+    #[test]
+    fn test_migrations_are_idempotent() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrations::run(&mut conn).unwrap();
+        migrations::run(&mut conn).unwrap();
+        assert_eq!(
+            migrations::current_version(&conn).unwrap(),
+            migrations::latest_version()
+        );
+    }
+
+    #[test]
+    fn test_rejects_newer_schema() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", migrations::latest_version() + 1)
+            .unwrap();
+        assert!(matches!(
+            migrations::run(&mut conn),
+            Err(StorageError::SchemaTooNew(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_record_and_read_back_session() {
+        let storage = Storage::open_in_memory().unwrap();
+        let mut record = sample_record(PhaseKind::ShortBreak, SessionOutcome::Completed);
+        record.shortened = true;
+        record.overtime = Duration::from_secs(3 * 60);
+        record.pause_count = 2;
+        record.break_activity = Some(BreakActivity::Breathing);
+        record.breathing_pattern = Some(BreathingPattern::Coherent);
+        record.check_in_before = Some(CheckIn { energy: 2, mood: 4 });
+        record.adjustments = vec![
+            TimeAdjustment {
+                at: record.started_at + ChronoDuration::minutes(20),
+                delta_secs: 300,
+            },
+            TimeAdjustment {
+                at: record.started_at + ChronoDuration::minutes(22),
+                delta_secs: -60,
+            },
+        ];
+
+        let id = storage.record_session(&record).unwrap();
+
+        let now = Local::now();
+        let sessions = storage
+            .sessions_between(
+                now - ChronoDuration::hours(1),
+                now + ChronoDuration::hours(1),
+            )
+            .unwrap();
+        assert_eq!(sessions.len(), 1);
+
+        let stored = &sessions[0];
+        assert_eq!(stored.id, Some(id));
+        assert_eq!(stored.kind, PhaseKind::ShortBreak);
+        assert_eq!(stored.outcome, SessionOutcome::Completed);
+        assert_eq!(stored.started_at.timestamp(), record.started_at.timestamp());
+        assert_eq!(stored.planned, Duration::from_secs(25 * 60));
+        assert_eq!(stored.actual, Duration::from_secs(20 * 60));
+        assert_eq!(stored.paused, Duration::from_secs(5 * 60));
+        assert_eq!(stored.overtime, Duration::from_secs(3 * 60));
+        assert_eq!(stored.pause_count, 2);
+        assert_eq!(stored.void_reason, None);
+        assert_eq!(stored.intention_result, None);
+        assert_eq!(stored.check_in_before, Some(CheckIn { energy: 2, mood: 4 }));
+        assert_eq!(stored.check_in_after, None);
+
+        assert!(stored.shortened);
+        assert!(!stored.extended);
+        assert_eq!(stored.break_activity, Some(BreakActivity::Breathing));
+        assert_eq!(stored.breathing_pattern, Some(BreathingPattern::Coherent));
+        let deltas: Vec<i64> = stored
+            .adjustments
+            .iter()
+            .map(|adjustment| adjustment.delta_secs)
+            .collect();
+        assert_eq!(deltas, vec![300, -60]);
+    }
+
+    #[test]
+    fn test_set_intention_result() {
+        let storage = Storage::open_in_memory().unwrap();
+        let mut record = sample_record(PhaseKind::Pomodoro, SessionOutcome::Completed);
+        record.intention = Some("Outline chapter 3".to_string());
+        let id = storage.record_session(&record).unwrap();
+        storage
+            .set_intention_result(id, IntentionResult::Partly)
+            .unwrap();
+
+        let now = Local::now();
+        let sessions = storage
+            .sessions_between(now - ChronoDuration::hours(1), now)
+            .unwrap();
+        assert_eq!(sessions[0].intention.as_deref(), Some("Outline chapter 3"));
+        assert_eq!(sessions[0].intention_result, Some(IntentionResult::Partly));
+    }
+
+    #[test]
+    fn test_set_check_in_after() {
+        let storage = Storage::open_in_memory().unwrap();
+        let mut record = sample_record(PhaseKind::Pomodoro, SessionOutcome::Completed);
+        record.check_in_before = Some(CheckIn { energy: 2, mood: 4 });
+        let id = storage.record_session(&record).unwrap();
+        storage
+            .set_check_in_after(id, CheckIn { energy: 1, mood: 5 })
+            .unwrap();
+
+        let now = Local::now();
+        let sessions = storage
+            .sessions_between(now - ChronoDuration::hours(1), now)
+            .unwrap();
+        assert_eq!(
+            sessions[0].check_in_before,
+            Some(CheckIn { energy: 2, mood: 4 })
+        );
+        assert_eq!(
+            sessions[0].check_in_after,
+            Some(CheckIn { energy: 1, mood: 5 })
+        );
+    }
+
+    #[test]
+    fn test_sessions_between_filters_by_start_time() {
+        let storage = Storage::open_in_memory().unwrap();
+        let mut old = sample_record(PhaseKind::Pomodoro, SessionOutcome::Reset);
+        old.started_at -= ChronoDuration::days(3);
+        storage.record_session(&old).unwrap();
+        storage
+            .record_session(&sample_record(PhaseKind::Pomodoro, SessionOutcome::Skipped))
+            .unwrap();
+
+        let now = Local::now();
+        let recent = storage
+            .sessions_between(now - ChronoDuration::days(1), now)
+            .unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].outcome, SessionOutcome::Skipped);
+    }
+
+    #[test]
+    fn test_open_creates_database_file() {
+        let dir = std::env::temp_dir().join(format!("pomo-tui-test-{}", std::process::id()));
+        let path = dir.join("nested").join("pomo.db");
+        let _ = fs::remove_dir_all(&dir);
+
+        {
+            let storage = Storage::open(&path).unwrap();
+            storage
+                .record_session(&sample_record(
+                    PhaseKind::Pomodoro,
+                    SessionOutcome::Completed,
+                ))
+                .unwrap();
+        }
+        assert!(path.exists());
+
+        // Reopening keeps existing data and does not re-run migrations
+        let storage = Storage::open(&path).unwrap();
+        let now = Local::now();
+        let sessions = storage
+            .sessions_between(now - ChronoDuration::days(1), now + ChronoDuration::days(1))
+            .unwrap();
+        assert_eq!(sessions.len(), 1);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
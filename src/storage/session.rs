@@ -0,0 +1,116 @@
+//! Session history records
+
+pub use crate::core::session::{
+    IntentionResult, PhaseKind, SessionOutcome, TimeAdjustment, VoidReason,
+};
+
+use crate::core::session::PhaseSummary;
+use crate::core::{BreakActivity, BreathingPattern, CheckIn};
+use chrono::{DateTime, Local, TimeZone};
+use std::time::Duration;
+
+/// One finished pomodoro or break
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionRecord {
+    /// Database id, `None` until the record is stored
+    pub id: Option<i64>,
+    pub kind: PhaseKind,
+    pub outcome: SessionOutcome,
+    pub started_at: DateTime<Local>,
+    pub ended_at: DateTime<Local>,
+    /// Duration the timer was set to
+    pub planned: Duration,
+    /// Time actually spent running, excluding pauses
+    pub actual: Duration,
+    /// Total time spent paused
+    pub paused: Duration,
+    /// Number of times the timer was paused
+    pub pause_count: u32,
+    /// Time spent past the planned duration in overtime
+    pub overtime: Duration,
+    /// Break was cut down to a short break
+    pub shortened: bool,
+    /// Shortened break was restored to full length
+    pub extended: bool,
+    pub break_activity: Option<BreakActivity>,
+    pub breathing_pattern: Option<BreathingPattern>,
+    /// Task the pomodoro was spent on
+    pub task_id: Option<i64>,
+    /// Why the pomodoro was voided, if the user said
+    pub void_reason: Option<VoidReason>,
+    /// What the pomodoro was meant to achieve
+    pub intention: Option<String>,
+    /// Whether it did, once the user has said
+    pub intention_result: Option<IntentionResult>,
+    /// Energy and mood as the pomodoro started
+    pub check_in_before: Option<CheckIn>,
+    /// Energy and mood once it was over
+    pub check_in_after: Option<CheckIn>,
+    /// Time added or taken off while the phase ran, oldest first
+    pub adjustments: Vec<TimeAdjustment>,
+}
+
+impl SessionRecord {
+    /// Create a record with no break details
+    pub fn new(
+        kind: PhaseKind,
+        outcome: SessionOutcome,
+        started_at: DateTime<Local>,
+        ended_at: DateTime<Local>,
+        planned: Duration,
+    ) -> Self {
+        Self {
+            id: None,
+            kind,
+            outcome,
+            started_at,
+            ended_at,
+            planned,
+            actual: Duration::ZERO,
+            paused: Duration::ZERO,
+            pause_count: 0,
+            overtime: Duration::ZERO,
+            shortened: false,
+            extended: false,
+            break_activity: None,
+            breathing_pattern: None,
+            task_id: None,
+            void_reason: None,
+            intention: None,
+            intention_result: None,
+            check_in_before: None,
+            check_in_after: None,
+            adjustments: Vec::new(),
+        }
+    }
+}
+
+impl From<&PhaseSummary> for SessionRecord {
+    fn from(summary: &PhaseSummary) -> Self {
+        let mut record = Self::new(
+            summary.kind,
+            summary.outcome,
+            summary.started_at,
+            summary.ended_at,
+            summary.planned,
+        );
+        record.actual = summary.actual;
+        record.paused = summary.paused;
+        record.pause_count = summary.pauses;
+        record.overtime = summary.overtime;
+        record.adjustments = summary.adjustments.clone();
+        if summary.kind.is_break() {
+            record.shortened = summary.shortened;
+            record.extended = summary.extended;
+        }
+        record
+    }
+}
+
+/// Convert a stored unix timestamp back to local time
+pub(crate) fn from_timestamp(secs: i64) -> DateTime<Local> {
+    Local
+        .timestamp_opt(secs, 0)
+        .single()
+        .unwrap_or_else(Local::now)
+}
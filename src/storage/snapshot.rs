@@ -0,0 +1,405 @@
+//! Snapshot of the in-progress session, used to resume after a restart
+
+use super::{session, PhaseKind, Storage, StorageError, TimeAdjustment};
+use crate::core::timer::TimerState;
+use crate::core::{BreakActivity, BreathingPattern, CheckIn};
+use chrono::{DateTime, Local};
+use rusqlite::{params, OptionalExtension, Row};
+use std::time::Duration;
+
+/// How to account for the time the app was closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResumeMode {
+    /// The timer was effectively paused while the app was closed
+    CountAsPaused,
+    /// The timer kept running while the app was closed
+    CountAsElapsed,
+    /// A running timer kept running and a paused one stayed paused
+    KeepState,
+}
+
+/// Everything needed to pick a session back up, with wall-clock timestamps
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionSnapshot {
+    pub saved_at: DateTime<Local>,
+    pub kind: PhaseKind,
+    pub timer_state: TimerState,
+    pub planned: Duration,
+    pub elapsed: Duration,
+    pub paused: Duration,
+    /// Times the current phase has been paused
+    pub pause_count: u32,
+    /// Time added to or taken off the current phase so far
+    pub adjustments: Vec<TimeAdjustment>,
+    pub session_count: u32,
+    pub focus_since_long_break: Duration,
+    pub shortened: bool,
+    pub extended: bool,
+    pub break_activity: Option<BreakActivity>,
+    pub breathing_pattern: Option<BreathingPattern>,
+    pub breathing_enabled: bool,
+    pub breathing_complete: bool,
+    pub phase_started_at: Option<DateTime<Local>>,
+    pub phase_recorded: bool,
+    pub task_id: Option<i64>,
+    pub plan: Option<String>,
+    pub plan_step: usize,
+    pub deadline: Option<DateTime<Local>>,
+    /// What the pomodoro under way is meant to achieve
+    pub intention: Option<String>,
+    /// Energy and mood as the pomodoro under way started
+    pub check_in: Option<CheckIn>,
+}
+
+impl SessionSnapshot {
+    /// Nothing has happened yet, so there is nothing to resume
+    pub fn is_fresh(&self) -> bool {
+        self.kind == PhaseKind::Pomodoro
+            && self.timer_state == TimerState::Idle
+            && self.session_count == 0
+    }
+
+    /// How long the app was closed, as of `now`
+    pub fn closed_for(&self, now: DateTime<Local>) -> Duration {
+        (now - self.saved_at).to_std().unwrap_or_default()
+    }
+
+    /// Elapsed time, paused time and timer state to resume with at `now`
+    pub fn resumed_timer(
+        &self,
+        now: DateTime<Local>,
+        mode: ResumeMode,
+    ) -> (Duration, Duration, TimerState) {
+        let closed = self.closed_for(now);
+        match (self.timer_state, mode) {
+            // The app never closed, so the timer simply carried on, into overtime if need be
+            (TimerState::Running, ResumeMode::KeepState) => {
+                (self.elapsed + closed, self.paused, TimerState::Running)
+            }
+            // This can run past the planned length - the session decides whether
+            // that finishes the phase or counts as overtime
+            (TimerState::Running | TimerState::Paused, ResumeMode::CountAsElapsed) => {
+                (self.elapsed + closed, self.paused, TimerState::Running)
+            }
+            (TimerState::Running | TimerState::Paused, ResumeMode::CountAsPaused)
+            | (TimerState::Paused, ResumeMode::KeepState) => {
+                (self.elapsed, self.paused + closed, TimerState::Paused)
+            }
+            (state, _) => (self.elapsed, self.paused, state),
+        }
+    }
+}
+
+impl Storage {
+    /// Replace the saved session snapshot
+    pub fn save_snapshot(&self, snapshot: &SessionSnapshot) -> Result<(), StorageError> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT OR REPLACE INTO session_snapshot (
+                id, saved_at, kind, timer_state, planned_ms, elapsed_ms, paused_ms,
+                session_count, focus_since_long_break_secs, shortened, extended,
+                break_activity, breathing_pattern, breathing_enabled, breathing_complete,
+                phase_started_at, phase_recorded, task_id, plan, plan_step, deadline, pause_count,
+                intention, energy, mood
+            ) VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17,
+                ?18, ?19, ?20, ?21, ?22, ?23, ?24)",
+            params![
+                snapshot.saved_at.timestamp(),
+                snapshot.kind.key(),
+                snapshot.timer_state.key(),
+                snapshot.planned.as_millis() as i64,
+                snapshot.elapsed.as_millis() as i64,
+                snapshot.paused.as_millis() as i64,
+                snapshot.session_count,
+                snapshot.focus_since_long_break.as_secs() as i64,
+                snapshot.shortened,
+                snapshot.extended,
+                snapshot.break_activity.map(|a| a.key()),
+                snapshot.breathing_pattern.map(|p| p.key()),
+                snapshot.breathing_enabled,
+                snapshot.breathing_complete,
+                snapshot.phase_started_at.map(|t| t.timestamp()),
+                snapshot.phase_recorded,
+                snapshot.task_id,
+                snapshot.plan,
+                snapshot.plan_step as i64,
+                snapshot.deadline.map(|t| t.timestamp()),
+                snapshot.pause_count,
+                snapshot.intention,
+                snapshot.check_in.map(|c| c.energy),
+                snapshot.check_in.map(|c| c.mood),
+            ],
+        )?;
+        tx.execute("DELETE FROM snapshot_adjustments", [])?;
+        for adjustment in &snapshot.adjustments {
+            tx.execute(
+                "INSERT INTO snapshot_adjustments (adjusted_at, delta_secs) VALUES (?1, ?2)",
+                params![adjustment.at.timestamp(), adjustment.delta_secs],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// The saved session snapshot, if there is one
+    pub fn load_snapshot(&self) -> Result<Option<SessionSnapshot>, StorageError> {
+        let raw = self
+            .conn
+            .query_row(
+                "SELECT saved_at, kind, timer_state, planned_ms, elapsed_ms, paused_ms,
+                        session_count, focus_since_long_break_secs, shortened, extended,
+                        break_activity, breathing_pattern, breathing_enabled, breathing_complete,
+                        phase_started_at, phase_recorded, task_id, plan, plan_step, deadline,
+                        pause_count, intention, energy, mood
+                 FROM session_snapshot WHERE id = 1",
+                [],
+                RawSnapshot::from_row,
+            )
+            .optional()?;
+        let Some(mut snapshot) = raw.map(RawSnapshot::into_snapshot).transpose()? else {
+            return Ok(None);
+        };
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT adjusted_at, delta_secs FROM snapshot_adjustments ORDER BY id")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(TimeAdjustment {
+                at: session::from_timestamp(row.get(0)?),
+                delta_secs: row.get(1)?,
+            })
+        })?;
+        snapshot.adjustments = rows.collect::<Result<_, _>>()?;
+        Ok(Some(snapshot))
+    }
+
+    pub fn clear_snapshot(&self) -> Result<(), StorageError> {
+        self.conn.execute_batch(
+            "DELETE FROM session_snapshot;
+             DELETE FROM snapshot_adjustments;",
+        )?;
+        Ok(())
+    }
+}
+
+/// Row as stored, before enum keys are parsed
+struct RawSnapshot {
+    saved_at: i64,
+    kind: String,
+    timer_state: String,
+    planned_ms: i64,
+    elapsed_ms: i64,
+    paused_ms: i64,
+    session_count: u32,
+    focus_since_long_break_secs: i64,
+    shortened: bool,
+    extended: bool,
+    break_activity: Option<String>,
+    breathing_pattern: Option<String>,
+    breathing_enabled: bool,
+    breathing_complete: bool,
+    phase_started_at: Option<i64>,
+    phase_recorded: bool,
+    task_id: Option<i64>,
+    plan: Option<String>,
+    plan_step: i64,
+    deadline: Option<i64>,
+    pause_count: u32,
+    intention: Option<String>,
+    energy: Option<u8>,
+    mood: Option<u8>,
+}
+
+impl RawSnapshot {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            saved_at: row.get(0)?,
+            kind: row.get(1)?,
+            timer_state: row.get(2)?,
+            planned_ms: row.get(3)?,
+            elapsed_ms: row.get(4)?,
+            paused_ms: row.get(5)?,
+            session_count: row.get(6)?,
+            focus_since_long_break_secs: row.get(7)?,
+            shortened: row.get(8)?,
+            extended: row.get(9)?,
+            break_activity: row.get(10)?,
+            breathing_pattern: row.get(11)?,
+            breathing_enabled: row.get(12)?,
+            breathing_complete: row.get(13)?,
+            phase_started_at: row.get(14)?,
+            phase_recorded: row.get(15)?,
+            task_id: row.get(16)?,
+            plan: row.get(17)?,
+            plan_step: row.get(18)?,
+            deadline: row.get(19)?,
+            pause_count: row.get(20)?,
+            intention: row.get(21)?,
+            energy: row.get(22)?,
+            mood: row.get(23)?,
+        })
+    }
+
+    fn into_snapshot(self) -> Result<SessionSnapshot, StorageError> {
+        let kind = PhaseKind::from_key(&self.kind)
+            .ok_or_else(|| StorageError::InvalidData(format!("snapshot kind '{}'", self.kind)))?;
+        let timer_state = TimerState::from_key(&self.timer_state).ok_or_else(|| {
+            StorageError::InvalidData(format!("snapshot timer state '{}'", self.timer_state))
+        })?;
+        let millis = |ms: i64| Duration::from_millis(ms.max(0) as u64);
+
+        Ok(SessionSnapshot {
+            saved_at: session::from_timestamp(self.saved_at),
+            kind,
+            timer_state,
+            planned: millis(self.planned_ms),
+            elapsed: millis(self.elapsed_ms),
+            paused: millis(self.paused_ms),
+            pause_count: self.pause_count,
+            adjustments: Vec::new(),
+            session_count: self.session_count,
+            focus_since_long_break: Duration::from_secs(
+                self.focus_since_long_break_secs.max(0) as u64
+            ),
+            shortened: self.shortened,
+            extended: self.extended,
+            break_activity: self
+                .break_activity
+                .as_deref()
+                .and_then(BreakActivity::from_key),
+            breathing_pattern: self
+                .breathing_pattern
+                .as_deref()
+                .and_then(BreathingPattern::from_key),
+            breathing_enabled: self.breathing_enabled,
+            breathing_complete: self.breathing_complete,
+            phase_started_at: self.phase_started_at.map(session::from_timestamp),
+            phase_recorded: self.phase_recorded,
+            task_id: self.task_id,
+            plan: self.plan,
+            plan_step: self.plan_step.max(0) as usize,
+            deadline: self.deadline.map(session::from_timestamp),
+            intention: self.intention,
+            check_in: CheckIn::from_levels(self.energy, self.mood),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration as ChronoDuration;
+
+    fn snapshot(timer_state: TimerState) -> SessionSnapshot {
+        let saved_at = session::from_timestamp(Local::now().timestamp());
+        SessionSnapshot {
+            saved_at,
+            kind: PhaseKind::ShortBreak,
+            timer_state,
+            planned: Duration::from_secs(5 * 60),
+            elapsed: Duration::from_secs(60),
+            paused: Duration::from_secs(30),
+            pause_count: 2,
+            adjustments: vec![TimeAdjustment {
+                at: saved_at - ChronoDuration::seconds(30),
+                delta_secs: -60,
+            }],
+            session_count: 3,
+            focus_since_long_break: Duration::from_secs(75 * 60),
+            shortened: false,
+            extended: false,
+            break_activity: Some(BreakActivity::Breathing),
+            breathing_pattern: Some(BreathingPattern::Coherent),
+            breathing_enabled: true,
+            breathing_complete: false,
+            phase_started_at: Some(saved_at - ChronoDuration::seconds(90)),
+            phase_recorded: false,
+            task_id: None,
+            plan: Some("study".to_string()),
+            plan_step: 3,
+            deadline: Some(saved_at + ChronoDuration::minutes(45)),
+            intention: Some("Outline chapter 3".to_string()),
+            check_in: Some(CheckIn { energy: 4, mood: 3 }),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_round_trip_and_clear() {
+        let storage = Storage::open_in_memory().unwrap();
+        assert!(storage.load_snapshot().unwrap().is_none());
+
+        let first = snapshot(TimerState::Running);
+        storage.save_snapshot(&first).unwrap();
+        assert_eq!(storage.load_snapshot().unwrap(), Some(first.clone()));
+        let mut second = first.clone();
+        second.timer_state = TimerState::Paused;
+        second.adjustments.push(TimeAdjustment {
+            at: second.saved_at,
+            delta_secs: 300,
+        });
+        storage.save_snapshot(&second).unwrap();
+
+        // Only the latest snapshot is kept, adjustments and all
+        assert_eq!(storage.load_snapshot().unwrap(), Some(second));
+
+        storage.clear_snapshot().unwrap();
+        assert!(storage.load_snapshot().unwrap().is_none());
+        let mut fresh = first;
+        fresh.adjustments.clear();
+        storage.save_snapshot(&fresh).unwrap();
+        assert_eq!(storage.load_snapshot().unwrap(), Some(fresh));
+    }
+
+    #[test]
+    fn test_resumed_timer() {
+        let mut snapshot = snapshot(TimerState::Running);
+        let now = snapshot.saved_at + ChronoDuration::minutes(2);
+
+        assert_eq!(
+            snapshot.resumed_timer(now, ResumeMode::CountAsPaused),
+            (
+                Duration::from_secs(60),
+                Duration::from_secs(150),
+                TimerState::Paused
+            )
+        );
+        assert_eq!(
+            snapshot.resumed_timer(now, ResumeMode::CountAsElapsed),
+            (
+                Duration::from_secs(180),
+                Duration::from_secs(30),
+                TimerState::Running
+            )
+        );
+
+        snapshot.timer_state = TimerState::Paused;
+        assert_eq!(
+            snapshot.resumed_timer(now, ResumeMode::KeepState),
+            snapshot.resumed_timer(now, ResumeMode::CountAsPaused)
+        );
+        snapshot.timer_state = TimerState::Running;
+
+        // Closed for longer than the phase had left, which is kept for overtime
+        let later = snapshot.saved_at + ChronoDuration::hours(1);
+        assert_eq!(
+            snapshot.resumed_timer(later, ResumeMode::CountAsElapsed),
+            snapshot.resumed_timer(later, ResumeMode::KeepState)
+        );
+        assert_eq!(
+            snapshot.resumed_timer(later, ResumeMode::KeepState),
+            (
+                Duration::from_secs(61 * 60),
+                Duration::from_secs(30),
+                TimerState::Running
+            )
+        );
+
+        // An idle timer has nothing to account for
+        snapshot.timer_state = TimerState::Idle;
+        assert_eq!(
+            snapshot.resumed_timer(later, ResumeMode::CountAsElapsed).2,
+            TimerState::Idle
+        );
+        assert!(!snapshot.is_fresh());
+    }
+}
@@ -0,0 +1,661 @@
+//! Aggregated statistics over the session history
+
+use super::{InterruptionRecord, PhaseKind, SessionOutcome, SessionRecord};
+use crate::core::{BreakActivity, InterruptionTally};
+use chrono::{Duration as ChronoDuration, NaiveDate, Timelike};
+use std::time::Duration;
+
+/// Totals for a single calendar day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayStats {
+    pub date: NaiveDate,
+    pub focus: Duration,
+    pub completed_pomodoros: u32,
+}
+
+/// Pomodoros completed out of those started, for one group of them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Completion {
+    pub completed: u32,
+    pub started: u32,
+}
+
+impl Completion {
+    fn add(&mut self, completed: bool) {
+        self.started += 1;
+        if completed {
+            self.completed += 1;
+        }
+    }
+
+    /// Share completed, from 0 to 1
+    pub fn rate(&self) -> f64 {
+        if self.started == 0 {
+            0.0
+        } else {
+            self.completed as f64 / self.started as f64
+        }
+    }
+}
+
+/// Parts of the day pomodoros are grouped into, in order
+pub const PARTS_OF_DAY: [&str; 4] = ["Morning", "Afternoon", "Evening", "Night"];
+
+/// Groups need this many pomodoros before they're compared
+const MIN_PATTERN_SAMPLES: u32 = 3;
+
+/// Smallest difference from the overall completion rate worth pointing out
+const MIN_PATTERN_DIFFERENCE: f64 = 0.1;
+
+/// Summary of the last `days` days of history
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    /// One entry per day, oldest first, including days without sessions
+    pub days: Vec<DayStats>,
+    pub completed_pomodoros: u32,
+    /// Pomodoros that were skipped, reset or voided before the timer ran out
+    pub abandoned_pomodoros: u32,
+    pub voided_pomodoros: u32,
+    /// Why pomodoros were voided, most common first
+    pub void_reasons: Vec<(String, u32)>,
+    /// Mean paused time per pomodoro
+    pub average_pause: Duration,
+    /// Times a pomodoro was paused
+    pub pauses: u32,
+    /// Warm-ups that weren't thrown away
+    pub warm_ups: u32,
+    /// Times minutes were added to a running pomodoro or break
+    pub snoozes: u32,
+    /// Total time added by snoozing
+    pub snoozed: Duration,
+    /// Times minutes were taken off instead
+    pub cuts: u32,
+    pub breaks_taken: u32,
+    pub breaks_skipped: u32,
+    pub breaks_shortened: u32,
+    pub breaks_extended: u32,
+    /// How often each break activity was used, most used first
+    pub activities: Vec<(String, u32)>,
+    pub interruptions: InterruptionTally,
+    /// Interruptions per hour of the day, most interrupted first
+    pub interrupted_hours: Vec<(u32, u32)>,
+    /// Interruptions per task, most interrupted first
+    pub interrupted_tasks: Vec<(String, u32)>,
+    /// Completion by energy as the pomodoro started, lowest first
+    pub completion_by_energy: Vec<(u8, Completion)>,
+    /// Completion by mood as the pomodoro started, lowest first
+    pub completion_by_mood: Vec<(u8, Completion)>,
+    /// Completion by part of the day, in the order of `PARTS_OF_DAY`
+    pub completion_by_part_of_day: Vec<(&'static str, Completion)>,
+    /// Completion of pomodoros that followed a break, by its activity
+    pub completion_after_activity: Vec<(String, Completion)>,
+}
+
+impl Stats {
+    /// Build statistics for the `days` days ending on `today` (inclusive)
+    pub fn from_records(records: &[SessionRecord], today: NaiveDate, days: u32) -> Self {
+        let days = days.max(1);
+        let first_day = today - ChronoDuration::days(days as i64 - 1);
+
+        let mut per_day: Vec<DayStats> = (0..days)
+            .map(|offset| DayStats {
+                date: first_day + ChronoDuration::days(offset as i64),
+                focus: Duration::ZERO,
+                completed_pomodoros: 0,
+            })
+            .collect();
+
+        let mut stats = Stats {
+            days: Vec::new(),
+            completed_pomodoros: 0,
+            abandoned_pomodoros: 0,
+            voided_pomodoros: 0,
+            void_reasons: Vec::new(),
+            average_pause: Duration::ZERO,
+            pauses: 0,
+            warm_ups: 0,
+            snoozes: 0,
+            snoozed: Duration::ZERO,
+            cuts: 0,
+            breaks_taken: 0,
+            breaks_skipped: 0,
+            breaks_shortened: 0,
+            breaks_extended: 0,
+            activities: Vec::new(),
+            interruptions: InterruptionTally::default(),
+            interrupted_hours: Vec::new(),
+            interrupted_tasks: Vec::new(),
+            completion_by_energy: Vec::new(),
+            completion_by_mood: Vec::new(),
+            completion_by_part_of_day: Vec::new(),
+            completion_after_activity: Vec::new(),
+        };
+
+        let mut total_pause = Duration::ZERO;
+        let mut pomodoros = 0u32;
+        // Activity of the break just taken, until the pomodoro after it
+        let mut last_break: Option<(NaiveDate, String)> = None;
+
+        for record in records {
+            let date = record.started_at.date_naive();
+            if date < first_day || date > today {
+                continue;
+            }
+            let day = &mut per_day[(date - first_day).num_days() as usize];
+
+            for adjustment in &record.adjustments {
+                if adjustment.is_snooze() {
+                    stats.snoozes += 1;
+                    stats.snoozed += Duration::from_secs(adjustment.delta_secs as u64);
+                } else {
+                    stats.cuts += 1;
+                }
+            }
+
+            match record.kind {
+                // Warm-ups are kept apart from focus time and pomodoro counts
+                PhaseKind::WarmUp => {
+                    if !record.outcome.is_discarded() {
+                        stats.warm_ups += 1;
+                    }
+                }
+                PhaseKind::Pomodoro => {
+                    let completed = record.outcome == SessionOutcome::Completed;
+                    if let Some(check_in) = record.check_in_before {
+                        add_completion(&mut stats.completion_by_energy, check_in.energy, completed);
+                        add_completion(&mut stats.completion_by_mood, check_in.mood, completed);
+                    }
+                    let part = part_of_day(record.started_at.hour());
+                    add_completion(&mut stats.completion_by_part_of_day, part, completed);
+                    if let Some((_, activity)) = last_break.take().filter(|(day, _)| *day == date) {
+                        add_completion(&mut stats.completion_after_activity, activity, completed);
+                    }
+
+                    pomodoros += 1;
+                    total_pause += record.paused;
+                    stats.pauses += record.pause_count;
+                    day.focus += record.actual + record.overtime;
+                    if record.outcome == SessionOutcome::Completed {
+                        day.completed_pomodoros += 1;
+                        stats.completed_pomodoros += 1;
+                    } else {
+                        stats.abandoned_pomodoros += 1;
+                    }
+                    if record.outcome == SessionOutcome::Voided {
+                        stats.voided_pomodoros += 1;
+                        let reason = record
+                            .void_reason
+                            .map_or("No reason given", |reason| reason.display_name());
+                        bump(&mut stats.void_reasons, reason.to_string());
+                    }
+                }
+                PhaseKind::ShortBreak | PhaseKind::LongBreak => {
+                    if record.outcome == SessionOutcome::Skipped {
+                        stats.breaks_skipped += 1;
+                    } else {
+                        stats.breaks_taken += 1;
+                    }
+                    if record.shortened {
+                        stats.breaks_shortened += 1;
+                    }
+                    if record.extended {
+                        stats.breaks_extended += 1;
+                    }
+                    if let Some(label) = activity_label(record) {
+                        bump(&mut stats.activities, label.clone());
+                        if record.outcome != SessionOutcome::Skipped {
+                            last_break = Some((date, label));
+                        }
+                    }
+                }
+            }
+        }
+
+        if pomodoros > 0 {
+            stats.average_pause = total_pause / pomodoros;
+        }
+        stats
+            .activities
+            .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        stats
+            .void_reasons
+            .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        stats.completion_by_energy.sort_by_key(|(level, _)| *level);
+        stats.completion_by_mood.sort_by_key(|(level, _)| *level);
+        stats
+            .completion_by_part_of_day
+            .sort_by_key(|(part, _)| PARTS_OF_DAY.iter().position(|p| p == part));
+        stats
+            .completion_after_activity
+            .sort_by(|a, b| b.1.started.cmp(&a.1.started).then_with(|| a.0.cmp(&b.0)));
+        stats.days = per_day;
+        stats
+    }
+
+    /// Completion across all pomodoros
+    pub fn completion(&self) -> Completion {
+        Completion {
+            completed: self.completed_pomodoros,
+            started: self.completed_pomodoros + self.abandoned_pomodoros,
+        }
+    }
+
+    /// Groups whose completion stands out from the overall rate, as
+    /// sentences, most striking first
+    pub fn patterns(&self) -> Vec<String> {
+        let overall = self.completion().rate();
+        if overall == 0.0 {
+            return Vec::new();
+        }
+
+        let mut patterns: Vec<(f64, String)> = Vec::new();
+        let mut consider = |completion: &Completion, describe: &dyn Fn(u32, &str) -> String| {
+            if completion.started < MIN_PATTERN_SAMPLES {
+                return;
+            }
+            let difference = completion.rate() / overall - 1.0;
+            if difference.abs() < MIN_PATTERN_DIFFERENCE {
+                return;
+            }
+            let percent = (difference.abs() * 100.0).round() as u32;
+            let sentence = describe(percent, if difference > 0.0 { "more" } else { "fewer" });
+            patterns.push((difference.abs(), sentence));
+        };
+
+        for (activity, completion) in &self.completion_after_activity {
+            consider(completion, &|percent, more| {
+                format!(
+                    "{} breaks are followed by {}% {} completed sessions",
+                    activity.trim_end_matches(" Break"),
+                    percent,
+                    more
+                )
+            });
+        }
+        for (level, completion) in &self.completion_by_energy {
+            consider(completion, &|percent, more| {
+                format!(
+                    "Starting at energy {}/5 leads to {}% {} completed sessions",
+                    level, percent, more
+                )
+            });
+        }
+        for (level, completion) in &self.completion_by_mood {
+            consider(completion, &|percent, more| {
+                format!(
+                    "Starting in mood {}/5 leads to {}% {} completed sessions",
+                    level, percent, more
+                )
+            });
+        }
+        for (part, completion) in &self.completion_by_part_of_day {
+            consider(completion, &|percent, more| {
+                format!("{}s see {}% {} completed sessions", part, percent, more)
+            });
+        }
+
+        patterns.sort_by(|a, b| b.0.total_cmp(&a.0));
+        patterns.into_iter().map(|(_, sentence)| sentence).collect()
+    }
+
+    /// Count interruptions by kind, hour and task, looking task titles up
+    /// with `title`
+    pub fn add_interruptions(
+        &mut self,
+        records: &[InterruptionRecord],
+        title: impl Fn(i64) -> Option<String>,
+    ) {
+        for record in records {
+            let interruption = &record.interruption;
+            self.interruptions.add(interruption.kind);
+            bump(&mut self.interrupted_hours, interruption.logged_at.hour());
+            if let Some(title) = record.task_id.and_then(&title) {
+                bump(&mut self.interrupted_tasks, title);
+            }
+        }
+        self.interrupted_hours
+            .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        self.interrupted_tasks
+            .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    }
+
+    /// Total focus time across all days
+    pub fn total_focus(&self) -> Duration {
+        self.days.iter().map(|day| day.focus).sum()
+    }
+}
+
+/// Add one to the count for `key`, starting it if it's new
+fn bump<K: PartialEq>(counts: &mut Vec<(K, u32)>, key: K) {
+    match counts.iter_mut().find(|(existing, _)| *existing == key) {
+        Some((_, count)) => *count += 1,
+        None => counts.push((key, 1)),
+    }
+}
+
+/// Count a pomodoro towards the completion of the group `key`
+fn add_completion<K: PartialEq>(groups: &mut Vec<(K, Completion)>, key: K, completed: bool) {
+    match groups.iter_mut().find(|(existing, _)| *existing == key) {
+        Some((_, completion)) => completion.add(completed),
+        None => {
+            let mut completion = Completion::default();
+            completion.add(completed);
+            groups.push((key, completion));
+        }
+    }
+}
+
+fn part_of_day(hour: u32) -> &'static str {
+    match hour {
+        5..=11 => PARTS_OF_DAY[0],
+        12..=16 => PARTS_OF_DAY[1],
+        17..=21 => PARTS_OF_DAY[2],
+        _ => PARTS_OF_DAY[3],
+    }
+}
+
+fn activity_label(record: &SessionRecord) -> Option<String> {
+    match (record.break_activity?, record.breathing_pattern) {
+        (BreakActivity::Breathing, Some(pattern)) => Some(pattern.display_name().to_string()),
+        (activity, _) => Some(activity.display_name().to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        BreathingPattern, CheckIn, Interruption, InterruptionKind, TimeAdjustment, VoidReason,
+    };
+    use chrono::{Local, TimeZone};
+
+    fn record(
+        kind: PhaseKind,
+        outcome: SessionOutcome,
+        day: u32,
+        actual_minutes: u64,
+    ) -> SessionRecord {
+        let started_at = Local.with_ymd_and_hms(2024, 3, day, 10, 0, 0).unwrap();
+        let mut record = SessionRecord::new(
+            kind,
+            outcome,
+            started_at,
+            started_at + ChronoDuration::minutes(actual_minutes as i64),
+            Duration::from_secs(25 * 60),
+        );
+        record.actual = Duration::from_secs(actual_minutes * 60);
+        record
+    }
+
+    #[test]
+    fn test_focus_minutes_per_day() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let records = vec![
+            record(PhaseKind::Pomodoro, SessionOutcome::Completed, 9, 25),
+            record(PhaseKind::Pomodoro, SessionOutcome::Completed, 10, 25),
+            record(PhaseKind::Pomodoro, SessionOutcome::Skipped, 10, 10),
+            // Outside the 7 day window
+            record(PhaseKind::Pomodoro, SessionOutcome::Completed, 1, 25),
+        ];
+
+        let stats = Stats::from_records(&records, today, 7);
+        assert_eq!(stats.days.len(), 7);
+        assert_eq!(
+            stats.days[0].date,
+            NaiveDate::from_ymd_opt(2024, 3, 4).unwrap()
+        );
+        assert_eq!(stats.days[5].focus, Duration::from_secs(25 * 60));
+        assert_eq!(stats.days[6].focus, Duration::from_secs(35 * 60));
+        assert_eq!(stats.days[6].completed_pomodoros, 1);
+        assert_eq!(stats.total_focus(), Duration::from_secs(60 * 60));
+        assert_eq!(stats.completed_pomodoros, 2);
+        assert_eq!(stats.abandoned_pomodoros, 1);
+    }
+
+    #[test]
+    fn test_average_pause() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let mut first = record(PhaseKind::Pomodoro, SessionOutcome::Completed, 10, 25);
+        first.paused = Duration::from_secs(120);
+        first.pause_count = 2;
+        let mut second = record(PhaseKind::Pomodoro, SessionOutcome::Voided, 10, 5);
+        second.pause_count = 1;
+
+        let stats = Stats::from_records(&[first, second], today, 7);
+        assert_eq!(stats.average_pause, Duration::from_secs(60));
+        assert_eq!(stats.pauses, 3);
+        assert_eq!(stats.abandoned_pomodoros, 1);
+    }
+
+    #[test]
+    fn test_void_reasons() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let voided = |reason| {
+            let mut record = record(PhaseKind::Pomodoro, SessionOutcome::Voided, 10, 5);
+            record.void_reason = reason;
+            record
+        };
+        let records = vec![
+            voided(Some(VoidReason::Emergency)),
+            voided(Some(VoidReason::Distracted)),
+            voided(None),
+            voided(Some(VoidReason::Distracted)),
+            record(PhaseKind::Pomodoro, SessionOutcome::Reset, 10, 5),
+        ];
+
+        let stats = Stats::from_records(&records, today, 7);
+        assert_eq!(stats.voided_pomodoros, 4);
+        assert_eq!(stats.abandoned_pomodoros, 5);
+        assert_eq!(
+            stats.void_reasons,
+            vec![
+                ("Distracted".to_string(), 2),
+                ("Emergency".to_string(), 1),
+                ("No reason given".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_completion_patterns() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let mut records = Vec::new();
+        // Three rounds after coherent breathing, all finished, and three after
+        // stretching with only one finished
+        for (pattern, outcome) in [
+            (Some(BreathingPattern::Coherent), SessionOutcome::Completed),
+            (None, SessionOutcome::Reset),
+            (Some(BreathingPattern::Coherent), SessionOutcome::Completed),
+            (None, SessionOutcome::Completed),
+            (Some(BreathingPattern::Coherent), SessionOutcome::Completed),
+            (None, SessionOutcome::Skipped),
+        ] {
+            let mut rest = record(PhaseKind::ShortBreak, SessionOutcome::Completed, 10, 5);
+            rest.break_activity = Some(match pattern {
+                Some(_) => BreakActivity::Breathing,
+                None => BreakActivity::Stretch,
+            });
+            rest.breathing_pattern = pattern;
+            let mut pomodoro = record(PhaseKind::Pomodoro, outcome, 10, 25);
+            let energy = if outcome == SessionOutcome::Completed {
+                4
+            } else {
+                2
+            };
+            pomodoro.check_in_before = Some(CheckIn { energy, mood: 3 });
+            records.push(rest);
+            records.push(pomodoro);
+        }
+        // A skipped break leaves nothing to follow
+        let mut skipped = record(PhaseKind::ShortBreak, SessionOutcome::Skipped, 10, 0);
+        skipped.break_activity = Some(BreakActivity::Stretch);
+        records.push(skipped);
+        records.push(record(
+            PhaseKind::Pomodoro,
+            SessionOutcome::Completed,
+            10,
+            25,
+        ));
+
+        let stats = Stats::from_records(&records, today, 7);
+        assert_eq!(
+            stats.completion(),
+            Completion {
+                completed: 5,
+                started: 7
+            }
+        );
+        let after = |label: &str| {
+            stats
+                .completion_after_activity
+                .iter()
+                .find(|(activity, _)| activity.starts_with(label))
+                .map(|(_, completion)| *completion)
+        };
+        assert_eq!(
+            after("Coherent"),
+            Some(Completion {
+                completed: 3,
+                started: 3
+            })
+        );
+        assert_eq!(
+            after("Stretch"),
+            Some(Completion {
+                completed: 1,
+                started: 3
+            })
+        );
+        assert_eq!(
+            stats.completion_by_energy,
+            vec![
+                (
+                    2,
+                    Completion {
+                        completed: 0,
+                        started: 2
+                    }
+                ),
+                (
+                    4,
+                    Completion {
+                        completed: 4,
+                        started: 4
+                    }
+                ),
+            ]
+        );
+        assert_eq!(
+            stats.completion_by_part_of_day,
+            vec![(
+                "Morning",
+                Completion {
+                    completed: 5,
+                    started: 7
+                }
+            )]
+        );
+
+        let patterns = stats.patterns();
+        assert_eq!(
+            patterns[0],
+            "Stretch breaks are followed by 53% fewer completed sessions"
+        );
+        assert!(patterns
+            .contains(&"Starting at energy 4/5 leads to 40% more completed sessions".to_string()));
+        assert!(patterns.iter().any(|pattern| pattern
+            .starts_with("Coherent Breathing (5-5) breaks are followed by 40% more")));
+    }
+
+    #[test]
+    fn test_snoozes_and_cuts() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let mut pomodoro = record(PhaseKind::Pomodoro, SessionOutcome::Completed, 10, 30);
+        let adjust = |delta_secs| TimeAdjustment {
+            at: pomodoro.started_at,
+            delta_secs,
+        };
+        pomodoro.adjustments = vec![adjust(300), adjust(60), adjust(-60)];
+        let mut short_break = record(PhaseKind::ShortBreak, SessionOutcome::Completed, 10, 10);
+        short_break.adjustments = vec![adjust(300)];
+
+        let stats = Stats::from_records(&[pomodoro, short_break], today, 7);
+        assert_eq!(stats.snoozes, 3);
+        assert_eq!(stats.snoozed, Duration::from_secs(11 * 60));
+        assert_eq!(stats.cuts, 1);
+    }
+
+    #[test]
+    fn test_warm_ups_are_counted_apart() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let records = vec![
+            record(PhaseKind::WarmUp, SessionOutcome::Completed, 10, 3),
+            record(PhaseKind::WarmUp, SessionOutcome::Skipped, 10, 1),
+            record(PhaseKind::WarmUp, SessionOutcome::Reset, 10, 0),
+            record(PhaseKind::Pomodoro, SessionOutcome::Completed, 10, 25),
+        ];
+
+        let stats = Stats::from_records(&records, today, 7);
+        assert_eq!(stats.warm_ups, 2);
+        assert_eq!(stats.completed_pomodoros, 1);
+        assert_eq!(stats.total_focus(), Duration::from_secs(25 * 60));
+    }
+
+    #[test]
+    fn test_break_counts_and_activities() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let mut coherent = record(PhaseKind::ShortBreak, SessionOutcome::Completed, 10, 5);
+        coherent.break_activity = Some(BreakActivity::Breathing);
+        coherent.breathing_pattern = Some(BreathingPattern::Coherent);
+        let mut stretch = record(PhaseKind::LongBreak, SessionOutcome::Completed, 10, 5);
+        stretch.break_activity = Some(BreakActivity::Stretch);
+        stretch.shortened = true;
+        let mut extended = coherent.clone();
+        extended.shortened = true;
+        extended.extended = true;
+        let skipped = record(PhaseKind::ShortBreak, SessionOutcome::Skipped, 10, 0);
+
+        let stats = Stats::from_records(&[coherent, stretch, extended, skipped], today, 7);
+        assert_eq!(stats.breaks_taken, 3);
+        assert_eq!(stats.breaks_skipped, 1);
+        assert_eq!(stats.breaks_shortened, 2);
+        assert_eq!(stats.breaks_extended, 1);
+        assert_eq!(
+            stats.activities,
+            vec![
+                (BreathingPattern::Coherent.display_name().to_string(), 2),
+                (BreakActivity::Stretch.display_name().to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interruptions_by_hour_and_task() {
+        let interruption = |hour, kind, task_id| InterruptionRecord {
+            interruption: Interruption {
+                logged_at: Local.with_ymd_and_hms(2024, 3, 10, hour, 15, 0).unwrap(),
+                kind,
+                note: None,
+                paused: false,
+            },
+            task_id,
+        };
+        let records = vec![
+            interruption(9, InterruptionKind::Internal, None),
+            interruption(14, InterruptionKind::External, Some(2)),
+            interruption(14, InterruptionKind::Internal, Some(1)),
+            interruption(14, InterruptionKind::External, Some(2)),
+        ];
+        let today = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let mut stats = Stats::from_records(&[], today, 7);
+        stats.add_interruptions(&records, |id| Some(format!("Task {}", id)));
+
+        assert_eq!(stats.interruptions.internal, 2);
+        assert_eq!(stats.interruptions.external, 2);
+        assert_eq!(stats.interrupted_hours, vec![(14, 3), (9, 1)]);
+        assert_eq!(
+            stats.interrupted_tasks,
+            vec![("Task 2".to_string(), 2), ("Task 1".to_string(), 1)]
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! Task persistence
+
+use super::{session, Storage, StorageError};
+use crate::core::{Priority, Task, TaskStatus};
+use rusqlite::{params, OptionalExtension, Row};
+
+const TASK_COLUMNS: &str = "id, title, notes, priority, estimated_pomodoros, completed_pomodoros,
+     status, tags, position, created_at";
+
+impl Storage {
+    /// Store a new task at the end of the list and return its id
+    pub fn add_task(&self, task: &Task) -> Result<i64, StorageError> {
+        let position: i64 = self.conn.query_row(
+            "SELECT COALESCE(MAX(position), 0) + 1 FROM tasks",
+            [],
+            |row| row.get(0),
+        )?;
+        let tags = serde_json::to_string(&task.tags)
+            .map_err(|e| StorageError::InvalidData(format!("task tags: {}", e)))?;
+
+        self.conn.execute(
+            "INSERT INTO tasks (
+                title, notes, priority, estimated_pomodoros, completed_pomodoros,
+                status, tags, position, created_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                task.title,
+                task.notes,
+                task.priority.key(),
+                task.estimated_pomodoros,
+                task.completed_pomodoros,
+                task.status.key(),
+                tags,
+                position,
+                task.created_at.timestamp(),
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Save changes to an existing task's editable fields
+    pub fn update_task(&self, task: &Task) -> Result<(), StorageError> {
+        let id = task
+            .id
+            .ok_or_else(|| StorageError::InvalidData("task has no id".to_string()))?;
+        let tags = serde_json::to_string(&task.tags)
+            .map_err(|e| StorageError::InvalidData(format!("task tags: {}", e)))?;
+
+        self.conn.execute(
+            "UPDATE tasks SET title = ?1, notes = ?2, priority = ?3, estimated_pomodoros = ?4,
+                status = ?5, tags = ?6
+             WHERE id = ?7",
+            params![
+                task.title,
+                task.notes,
+                task.priority.key(),
+                task.estimated_pomodoros,
+                task.status.key(),
+                tags,
+                id,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Look up a single task
+    pub fn task(&self, id: i64) -> Result<Option<Task>, StorageError> {
+        let sql = format!("SELECT {} FROM tasks WHERE id = ?1", TASK_COLUMNS);
+        let raw = self
+            .conn
+            .query_row(&sql, params![id], RawTask::from_row)
+            .optional()?;
+        raw.map(RawTask::into_task).transpose()
+    }
+
+    /// All tasks, open ones first, each group in manual order
+    pub fn tasks(&self) -> Result<Vec<Task>, StorageError> {
+        let sql = format!(
+            "SELECT {} FROM tasks ORDER BY status = 'done', position, id",
+            TASK_COLUMNS
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map([], RawTask::from_row)?;
+        rows.map(|row| row?.into_task()).collect()
+    }
+
+    /// Tasks that still need work, in manual order
+    pub fn open_tasks(&self) -> Result<Vec<Task>, StorageError> {
+        Ok(self
+            .tasks()?
+            .into_iter()
+            .filter(|task| !task.is_done())
+            .collect())
+    }
+
+    pub fn set_task_status(&self, id: i64, status: TaskStatus) -> Result<(), StorageError> {
+        self.conn.execute(
+            "UPDATE tasks SET status = ?1 WHERE id = ?2",
+            params![status.key(), id],
+        )?;
+        Ok(())
+    }
+
+    /// Exchange the list positions of two tasks
+    pub fn swap_task_positions(&mut self, first: i64, second: i64) -> Result<(), StorageError> {
+        let tx = self.conn.transaction()?;
+        let position = |id: i64| -> rusqlite::Result<i64> {
+            tx.query_row(
+                "SELECT position FROM tasks WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+        };
+        let first_position = position(first)?;
+        let second_position = position(second)?;
+        tx.execute(
+            "UPDATE tasks SET position = ?1 WHERE id = ?2",
+            params![second_position, first],
+        )?;
+        tx.execute(
+            "UPDATE tasks SET position = ?1 WHERE id = ?2",
+            params![first_position, second],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Count a finished pomodoro towards a task
+    pub fn add_completed_pomodoro(&self, id: i64) -> Result<(), StorageError> {
+        self.conn.execute(
+            "UPDATE tasks SET completed_pomodoros = completed_pomodoros + 1 WHERE id = ?1",
+            params![id],
+        )?;
+        Ok(())
+    }
+
+    /// Take back a pomodoro counted towards a task
+    pub fn remove_completed_pomodoro(&self, id: i64) -> Result<(), StorageError> {
+        self.conn.execute(
+            "UPDATE tasks SET completed_pomodoros = MAX(completed_pomodoros - 1, 0) WHERE id = ?1",
+            params![id],
+        )?;
+        Ok(())
+    }
+}
+
+/// Row as stored, before enum keys and tags are parsed
+struct RawTask {
+    id: i64,
+    title: String,
+    notes: String,
+    priority: String,
+    estimated_pomodoros: u32,
+    completed_pomodoros: u32,
+    status: String,
+    tags: String,
+    position: i64,
+    created_at: i64,
+}
+
+impl RawTask {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            title: row.get(1)?,
+            notes: row.get(2)?,
+            priority: row.get(3)?,
+            estimated_pomodoros: row.get(4)?,
+            completed_pomodoros: row.get(5)?,
+            status: row.get(6)?,
+            tags: row.get(7)?,
+            position: row.get(8)?,
+            created_at: row.get(9)?,
+        })
+    }
+
+    fn into_task(self) -> Result<Task, StorageError> {
+        let priority = Priority::from_key(&self.priority).ok_or_else(|| {
+            StorageError::InvalidData(format!("task priority '{}'", self.priority))
+        })?;
+        let status = TaskStatus::from_key(&self.status)
+            .ok_or_else(|| StorageError::InvalidData(format!("task status '{}'", self.status)))?;
+        let tags = serde_json::from_str(&self.tags)
+            .map_err(|e| StorageError::InvalidData(format!("task tags: {}", e)))?;
+
+        Ok(Task {
+            id: Some(self.id),
+            title: self.title,
+            notes: self.notes,
+            priority,
+            estimated_pomodoros: self.estimated_pomodoros,
+            completed_pomodoros: self.completed_pomodoros,
+            status,
+            tags,
+            position: self.position,
+            created_at: session::from_timestamp(self.created_at),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add(storage: &Storage, title: &str) -> i64 {
+        storage.add_task(&Task::new(title)).unwrap()
+    }
+
+    #[test]
+    fn test_add_and_read_task() {
+        let storage = Storage::open_in_memory().unwrap();
+        let mut task = Task::new("Write report");
+        task.notes = "Section 2 first".to_string();
+        task.priority = Priority::High;
+        task.estimated_pomodoros = 3;
+        task.tags = vec!["work".to_string(), "writing".to_string()];
+
+        let id = storage.add_task(&task).unwrap();
+        let stored = storage.task(id).unwrap().unwrap();
+
+        assert_eq!(stored.id, Some(id));
+        assert_eq!(stored.title, "Write report");
+        assert_eq!(stored.notes, "Section 2 first");
+        assert_eq!(stored.priority, Priority::High);
+        assert_eq!(stored.estimated_pomodoros, 3);
+        assert_eq!(stored.status, TaskStatus::Open);
+        assert_eq!(stored.tags, vec!["work", "writing"]);
+        assert!(storage.task(id + 100).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_update_and_complete_task() {
+        let storage = Storage::open_in_memory().unwrap();
+        let id = add(&storage, "Draft");
+
+        let mut task = storage.task(id).unwrap().unwrap();
+        task.title = "Draft v2".to_string();
+        task.priority = Priority::Low;
+        storage.update_task(&task).unwrap();
+        storage.add_completed_pomodoro(id).unwrap();
+        storage.add_completed_pomodoro(id).unwrap();
+        storage.remove_completed_pomodoro(id).unwrap();
+        storage.set_task_status(id, TaskStatus::Done).unwrap();
+
+        let stored = storage.task(id).unwrap().unwrap();
+        assert_eq!(stored.title, "Draft v2");
+        assert_eq!(stored.priority, Priority::Low);
+        assert_eq!(stored.completed_pomodoros, 1);
+        assert!(stored.is_done());
+        assert!(storage.open_tasks().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_task_order_and_reordering() {
+        let mut storage = Storage::open_in_memory().unwrap();
+        let first = add(&storage, "First");
+        let second = add(&storage, "Second");
+        let third = add(&storage, "Third");
+        storage.set_task_status(first, TaskStatus::Done).unwrap();
+
+        // Done tasks sink to the bottom
+        let titles: Vec<String> = storage
+            .tasks()
+            .unwrap()
+            .into_iter()
+            .map(|t| t.title)
+            .collect();
+        assert_eq!(titles, vec!["Second", "Third", "First"]);
+
+        storage.swap_task_positions(second, third).unwrap();
+        let titles: Vec<String> = storage
+            .open_tasks()
+            .unwrap()
+            .into_iter()
+            .map(|t| t.title)
+            .collect();
+        assert_eq!(titles, vec!["Third", "Second"]);
+    }
+}
@@ -1,26 +1,163 @@
 #[cfg(feature = "audio")]
 use crate::audio::{AudioManager, SoundType};
-use crate::core::{BreakActivity, BreakAnimation, BreathingExercise, BreathingPattern, Timer};
+use crate::core::deadline;
+use crate::core::timer::TimerState;
+use crate::core::{
+    pick_weighted, BreakActivity, BreakAnimation, BreathingExercise, BreathingPattern, CheckIn,
+    Clock, ClockGap, Config, FocusStreak, GapDetector, GapUse, GuardLevel, InboxItem,
+    IntentionResult, Interruption, InterruptionKind, InterruptionTally, PauseLimitAction,
+    PhaseSummary, Session, SessionCommand, SessionEvent, SessionOutcome, SessionState, SystemClock,
+    Task, TaskStatus, Timer, VoidReason,
+};
 use crate::integrations::{DndState, MacOSDndController};
+use crate::storage::{
+    Breadcrumb, GapRecord, GuardRecord, InterruptionRecord, PhaseKind, ResumeMode, SessionRecord,
+    SessionSnapshot, Stats, Storage,
+};
+use crate::tui::inbox_panel::InboxPanel;
+use crate::tui::input::TextInput;
+use crate::tui::plan_picker::{PlanAction, PlanPicker};
+use crate::tui::task_panel::{FormAction, TaskForm, TaskPanel};
+use crate::tui::task_picker::{PickerAction, TaskPicker};
+use chrono::NaiveTime;
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent},
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::{error::Error, io, time::Duration};
+use std::{
+    collections::hash_map::RandomState,
+    error::Error,
+    hash::{BuildHasher, Hasher},
+    io,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::time;
 
+/// How many actions `u` can take back
+const UNDO_LIMIT: usize = 20;
+
+/// State from before an action, to go back to with undo
+struct UndoEntry {
+    /// What was done, e.g. "the skip"
+    label: &'static str,
+    snapshot: SessionSnapshot,
+    /// Sessions the action wrote to the history
+    recorded: Vec<i64>,
+    /// Tasks the action counted a finished pomodoro towards
+    credited: Vec<i64>,
+    streak: FocusStreak,
+}
+
+/// An interruption being logged, waiting for its note
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterruptionInput {
+    pub kind: InterruptionKind,
+    pub note: TextInput,
+}
+
+/// Asking whether the pomodoro that just ended did what it was meant to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntentionCheck {
+    /// Recorded session to note the answer on
+    session_id: i64,
+    pub intention: String,
+}
+
+/// Asking for energy and then mood, one key press each
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckInPrompt {
+    /// Recorded session the answers belong to, or `None` while a pomodoro
+    /// is about to start
+    session_id: Option<i64>,
+    /// Energy once it's been given, with mood still to come
+    pub energy: Option<u8>,
+}
+
+impl CheckInPrompt {
+    pub fn is_start(&self) -> bool {
+        self.session_id.is_none()
+    }
+}
+
+/// The hyperfocus guard's "take a real break" screen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GuardScreen {
+    pub shown_at: Instant,
+    /// Focus time since the last real break
+    pub focus: Duration,
+    pub skipped_breaks: u32,
+    /// The guard paused the running pomodoro to show the screen
+    paused_work: bool,
+}
+
 pub struct App {
-    timer: Timer,
+    session: Session,
+    clock: Arc<dyn Clock>,
     breathing_exercise: Option<BreathingExercise>,
     breathing_duration: Option<Duration>,
     breathing_enabled: bool,
     breathing_complete: bool,
-    mode: AppMode,
     should_quit: bool,
-    session_count: u32,
-    break_was_shortened: bool,
+    // Session history
+    storage: Option<Storage>,
+    /// Session left over from the last run, waiting for the user to decide
+    resume_prompt: Option<SessionSnapshot>,
+    // Screens
+    screen: Screen,
+    stats_days: u32,
+    /// Showing how completion goes with check-ins, time of day and breaks
+    stats_patterns: bool,
+    stats: Option<Stats>,
+    // Tasks
+    task_panel: TaskPanel,
+    active_task: Option<Task>,
+    task_picker: Option<TaskPicker>,
+    plan_picker: Option<PlanPicker>,
+    /// "Work until" time being typed in
+    deadline_input: Option<TextInput>,
+    interruption_input: Option<InterruptionInput>,
+    /// Interruptions logged in the current pomodoro
+    interruptions: InterruptionTally,
+    /// Asking why the pomodoro is being voided
+    void_prompt: bool,
+    /// Reason for the void under way, for the history
+    void_reason: Option<VoidReason>,
+    /// Thought being parked in the inbox, typed in while the timer runs on
+    inbox_input: Option<TextInput>,
+    inbox: InboxPanel,
+    /// What the next or current pomodoro is meant to achieve, being typed in
+    intention_input: Option<TextInput>,
+    /// What the current pomodoro is meant to achieve
+    intention: Option<String>,
+    intention_check: Option<IntentionCheck>,
+    check_in_prompt: Option<CheckInPrompt>,
+    /// Energy and mood as the current pomodoro started
+    check_in: Option<CheckIn>,
+    /// Where the pomodoro left off, being typed in as the break starts
+    breadcrumb_input: Option<TextInput>,
+    /// Where the last pomodoro on this task left off today
+    breadcrumb: Option<Breadcrumb>,
+    /// The warm-up ran out and we're asking whether to roll into a pomodoro
+    warm_up_done: bool,
+    /// When the ready phase starts by itself, unless a key is pressed first
+    auto_start_at: Option<Instant>,
+    gap_detector: GapDetector,
+    /// Time the running timer missed, waiting to be counted as something
+    gap_prompt: Option<ClockGap>,
+    /// Work since the last break that was actually taken
+    focus_streak: FocusStreak,
+    /// Strongest step the hyperfocus guard has taken in this streak
+    guard_fired: Option<GuardLevel>,
+    guard_screen: Option<GuardScreen>,
+    /// Most recent undoable action last
+    undo_stack: Vec<UndoEntry>,
+    /// Ids of sessions recorded since the last undo checkpoint
+    recorded_since_checkpoint: Vec<i64>,
+    /// Ids of tasks credited with a pomodoro since the last undo checkpoint
+    credited_since_checkpoint: Vec<i64>,
     dnd_controller: Option<MacOSDndController>,
     dnd_auto_enabled: bool,
     dnd_state: DndState,
@@ -29,6 +166,7 @@ pub struct App {
     break_activity: BreakActivity,
     break_animation: Option<BreakAnimation>,
     break_activity_selecting: bool,
+    breathing_pattern: Option<BreathingPattern>,
     selected_option: u8, // 1-4 for the current highlighted option
     // Pause menu system
     pause_menu_active: bool,
@@ -40,10 +178,33 @@ pub struct App {
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AppMode {
+    /// Short session before the first pomodoro
+    WarmUp,
     Pomodoro,
     Break,
 }
 
+/// Top-level view shown in the main area
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Screen {
+    Timer,
+    Stats,
+    Tasks,
+    /// Distraction inbox, reached with `I` rather than Tab
+    Inbox,
+}
+
+impl Screen {
+    /// Screen that Tab switches to
+    pub fn next(&self) -> Self {
+        match self {
+            Screen::Timer => Screen::Stats,
+            Screen::Stats => Screen::Tasks,
+            Screen::Tasks | Screen::Inbox => Screen::Timer,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ConfirmationDialog {
     ResetTimer,
@@ -51,6 +212,38 @@ pub enum ConfirmationDialog {
 
 impl App {
     pub fn new() -> Result<Self, Box<dyn Error>> {
+        Self::with_config(Config::load()?)
+    }
+
+    pub fn with_config(config: Config) -> Result<Self, Box<dyn Error>> {
+        Self::with_clock(config, SystemClock::shared())
+    }
+
+    /// Create an app whose timers and history timestamps follow `clock`
+    pub fn with_clock(config: Config, clock: Arc<dyn Clock>) -> Result<Self, Box<dyn Error>> {
+        // Session history is optional - the timer works without it. A time-warped
+        // demo gets a throwaway history so it can't pollute the real one.
+        let storage = if clock.speed() == 1.0 {
+            Storage::open_default()
+        } else {
+            Storage::open_in_memory()
+        };
+        match storage {
+            Ok(storage) => Self::with_storage(config, clock, Some(storage)),
+            Err(err) => {
+                let mut app = Self::with_storage(config, clock, None)?;
+                app.status_message = Some(format!("⚠️ Session history unavailable: {}", err));
+                Ok(app)
+            }
+        }
+    }
+
+    /// Create an app that keeps its history in `storage`, if any
+    pub fn with_storage(
+        config: Config,
+        clock: Arc<dyn Clock>,
+        storage: Option<Storage>,
+    ) -> Result<Self, Box<dyn Error>> {
         let mut dnd_controller = if MacOSDndController::is_supported() {
             Some(MacOSDndController::new())
         } else {
@@ -82,20 +275,65 @@ impl App {
             }
         }
 
+        let resume_prompt = storage
+            .as_ref()
+            .and_then(|storage| storage.load_snapshot().ok().flatten())
+            .filter(|snapshot| !snapshot.is_fresh());
+
+        let mut inbox = InboxPanel::default();
+        if let Some(items) = storage
+            .as_ref()
+            .and_then(|storage| storage.inbox_items().ok())
+        {
+            inbox.set_items(items);
+        }
+
         // Initialize audio manager
         #[cfg(feature = "audio")]
         let audio_manager = AudioManager::default();
 
         Ok(Self {
-            timer: Timer::new(25 * 60), // 25 minute pomodoro
+            session: Session::with_clock(config, clock.clone()),
+            clock,
             breathing_exercise: None,
             breathing_duration: Some(Duration::from_secs(90)), // 1.5 minutes default
             breathing_enabled: true,
             breathing_complete: false,
-            mode: AppMode::Pomodoro,
             should_quit: false,
-            session_count: 0,
-            break_was_shortened: false,
+            storage,
+            resume_prompt,
+            screen: Screen::Timer,
+            stats_days: 7,
+            stats_patterns: false,
+            stats: None,
+            task_panel: TaskPanel::default(),
+            active_task: None,
+            task_picker: None,
+            plan_picker: None,
+            deadline_input: None,
+            interruption_input: None,
+            interruptions: InterruptionTally::default(),
+            void_prompt: false,
+            void_reason: None,
+            inbox_input: None,
+            inbox,
+            intention_input: None,
+            intention: None,
+            intention_check: None,
+            check_in_prompt: None,
+            check_in: None,
+            breadcrumb_input: None,
+            breadcrumb: None,
+            warm_up_done: false,
+            auto_start_at: None,
+            gap_detector: GapDetector::default(),
+            gap_prompt: None,
+            focus_streak: FocusStreak::default(),
+            guard_fired: None,
+            guard_screen: None,
+            undo_stack: Vec::new(),
+            recorded_since_checkpoint: Vec::new(),
+            credited_since_checkpoint: Vec::new(),
             dnd_controller,
             dnd_auto_enabled: true, // Default to auto-enable DND
             dnd_state,
@@ -104,6 +342,7 @@ impl App {
             break_activity: BreakActivity::Breathing, // Default to breathing exercise
             break_animation: None,
             break_activity_selecting: false,
+            breathing_pattern: None,
             selected_option: 1, // Default to first breathing pattern
             // Pause menu defaults
             pause_menu_active: false,
@@ -141,6 +380,7 @@ impl App {
             if event::poll(Duration::from_millis(50))? {
                 if let Event::Key(key) = event::read()? {
                     self.handle_key(key);
+                    self.save_snapshot();
                 }
             }
 
@@ -155,6 +395,8 @@ impl App {
             interval.tick().await;
         }
 
+        self.save_snapshot();
+
         // Restore terminal
         disable_raw_mode()?;
         io::stdout().execute(LeaveAlternateScreen)?;
@@ -162,7 +404,80 @@ impl App {
         Ok(())
     }
 
-    fn handle_key(&mut self, key: KeyEvent) {
+    pub(crate) fn handle_key(&mut self, key: KeyEvent) {
+        if self.resume_prompt.is_some() {
+            self.handle_resume_key(key);
+            return;
+        }
+        if self.gap_prompt.is_some() {
+            self.handle_gap_key(key);
+            return;
+        }
+        if self.guard_screen.is_some() {
+            self.handle_guard_key(key);
+            return;
+        }
+        // Whatever is being typed is finished before any question that came
+        // up in the meantime gets the keys
+        if self.interruption_input.is_some() || self.inbox_input.is_some() {
+            self.auto_start_at = None;
+        }
+        if self.interruption_input.is_some() {
+            self.handle_interruption_key(key);
+            return;
+        }
+        if self.inbox_input.is_some() {
+            self.handle_inbox_input_key(key);
+            return;
+        }
+        if self.intention_check.is_some() && self.screen == Screen::Timer {
+            self.handle_intention_check_key(key);
+            return;
+        }
+        if self.check_in_prompt.is_some() && self.screen == Screen::Timer {
+            self.handle_check_in_key(key);
+            return;
+        }
+        if self.breadcrumb_input.is_some() && self.screen == Screen::Timer {
+            self.handle_breadcrumb_key(key);
+            return;
+        }
+        if self.auto_start_at.take().is_some() {
+            // Someone's at the keyboard, so they can start it themselves
+            self.status_message = Some("Auto-start cancelled".to_string());
+            return;
+        }
+        match self.screen {
+            Screen::Stats => return self.handle_stats_key(key),
+            Screen::Tasks => return self.handle_tasks_key(key),
+            Screen::Inbox => return self.handle_inbox_key(key),
+            Screen::Timer => {}
+        }
+        if self.task_picker.is_some() {
+            self.handle_picker_key(key);
+            return;
+        }
+        if self.plan_picker.is_some() {
+            self.handle_plan_picker_key(key);
+            return;
+        }
+        if self.deadline_input.is_some() {
+            self.handle_deadline_key(key);
+            return;
+        }
+        if self.void_prompt {
+            self.handle_void_key(key);
+            return;
+        }
+        if self.intention_input.is_some() {
+            self.handle_intention_key(key);
+            return;
+        }
+        if self.warm_up_done {
+            self.handle_warm_up_key(key);
+            return;
+        }
+
         match key.code {
             KeyCode::Char('q') => {
                 // Restore DND state before quitting
@@ -176,7 +491,7 @@ impl App {
                 } else if self.pause_menu_active {
                     // Resume from pause menu (like pressing Resume)
                     self.pause_menu_active = false;
-                    self.timer.resume();
+                    self.send(SessionCommand::Resume);
                 } else if self.status_message.is_some() {
                     // Clear status message
                     self.status_message = None;
@@ -189,23 +504,24 @@ impl App {
             KeyCode::Char(' ') => {
                 if self.confirmation_dialog.is_some() {
                     // Ignore space in confirmation dialog - only Y/N should work
-                    return;
                 } else if self.pause_menu_active {
                     // Handle pause menu selection
                     match self.pause_menu_selection {
                         1 => {
                             // Resume break
                             self.pause_menu_active = false;
-                            self.timer.resume();
+                            self.send(SessionCommand::Resume);
                         }
                         2 => {
                             // Change break activity
                             self.pause_menu_active = false;
-                            self.timer.reset();
-                            self.breathing_exercise = None;
-                            self.breathing_complete = false;
-                            self.break_animation = None;
-                            self.start_break_activity_selection();
+                            self.undoable("changing the activity", |app| {
+                                app.send(SessionCommand::Restart);
+                                app.breathing_exercise = None;
+                                app.breathing_complete = false;
+                                app.break_animation = None;
+                                app.start_break_activity_selection();
+                            });
                         }
                         3 => {
                             // Reset timer (show confirmation)
@@ -215,7 +531,8 @@ impl App {
                     }
                 } else if self.break_activity_selecting {
                     // Confirm the selected break option
-                    self.select_break_option(self.selected_option);
+                    let option = self.selected_option;
+                    self.undoable("the activity choice", |app| app.select_break_option(option));
                 } else {
                     self.toggle_timer();
                 }
@@ -228,10 +545,23 @@ impl App {
                 // Show confirmation dialog instead of immediate reset
                 self.confirmation_dialog = Some(ConfirmationDialog::ResetTimer);
             }
+            KeyCode::Char('P') => self.toggle_pause(),
+            KeyCode::Char('p') => self.open_plan_picker(),
+            KeyCode::Char('w') => self.open_deadline_input(),
+            KeyCode::Char('\'') => self.open_interruption_input(InterruptionKind::Internal),
+            KeyCode::Char('-') => self.open_interruption_input(InterruptionKind::External),
+            KeyCode::Char('V') => self.open_void_prompt(),
+            KeyCode::Char('i') => self.inbox_input = Some(TextInput::new()),
+            KeyCode::Char('I') => self.show_screen(Screen::Inbox),
+            KeyCode::Char('u') if self.confirmation_dialog.is_none() => self.undo(),
             KeyCode::Char('s') => self.skip_to_break(),
             KeyCode::Char('b') => self.skip_break(),
             KeyCode::Char('h') => self.shorten_break(),
             KeyCode::Char('e') => self.extend_break(),
+            KeyCode::Char(']') => self.adjust_time(1),
+            KeyCode::Char('}') => self.adjust_time(5),
+            KeyCode::Char('[') => self.adjust_time(-1),
+            KeyCode::Char('{') => self.adjust_time(-5),
             KeyCode::Char('t') => self.toggle_breathing(),
             KeyCode::Char('x') => self.skip_breathing(),
             KeyCode::Char('d') => {
@@ -249,6 +579,7 @@ impl App {
                 }
             }
             KeyCode::Char('a') => self.toggle_dnd_auto_enable(),
+            KeyCode::Tab => self.show_screen(self.screen.next()),
             KeyCode::Char('c') => {
                 // Clear status message
                 self.status_message = None;
@@ -307,11 +638,7 @@ impl App {
                     self.set_breathing_pattern(BreathingPattern::ShortBox);
                 }
             }
-            KeyCode::Char('4') => {
-                if self.break_activity_selecting {
-                    self.highlight_option(4);
-                }
-            }
+            KeyCode::Char('4') if self.break_activity_selecting => self.highlight_option(4),
             KeyCode::Char('y') | KeyCode::Char('Y') => {
                 if let Some(dialog) = self.confirmation_dialog {
                     match dialog {
@@ -319,335 +646,1735 @@ impl App {
                             // Confirmed - reset the timer
                             self.confirmation_dialog = None;
                             self.pause_menu_active = false;
-                            self.reset_timer();
+                            self.undoable("the reset", Self::reset_timer);
                         }
                     }
                 }
             }
-            KeyCode::Char('n') | KeyCode::Char('N') => {
-                if self.confirmation_dialog.is_some() {
-                    // Cancel confirmation dialog
-                    self.confirmation_dialog = None;
-                }
+            KeyCode::Char('n') | KeyCode::Char('N') if self.confirmation_dialog.is_some() => {
+                // Cancel confirmation dialog
+                self.confirmation_dialog = None;
             }
             #[cfg(feature = "audio")]
             KeyCode::Char('m') => self.toggle_audio_mute(),
             #[cfg(feature = "audio")]
             KeyCode::Char('+') | KeyCode::Char('=') => self.increase_volume(),
             #[cfg(feature = "audio")]
-            KeyCode::Char('-') => self.decrease_volume(),
+            KeyCode::Char('_') => self.decrease_volume(),
             #[cfg(feature = "audio")]
             KeyCode::Char('v') => self.play_test_sound(),
             _ => {}
         }
     }
 
-    fn toggle_timer(&mut self) {
-        match self.timer.state() {
-            crate::core::timer::TimerState::Idle => {
-                self.timer.start();
-                // Enable DND when starting a Pomodoro session
-                if self.mode == AppMode::Pomodoro {
-                    self.auto_enable_dnd();
-                }
+    /// Keys while asking whether to resume the previous session
+    fn handle_resume_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('p') | KeyCode::Char('P') | KeyCode::Char('y') | KeyCode::Char('Y') => {
+                self.resume_session(ResumeMode::CountAsPaused)
             }
-            crate::core::timer::TimerState::Running => {
-                self.timer.pause();
-                // Disable Focus mode when pausing to allow interruptions
-                if self.mode == AppMode::Pomodoro {
-                    self.auto_disable_dnd();
-                } else if self.mode == AppMode::Break {
-                    // In break mode, show pause menu when paused
-                    self.pause_menu_active = true;
-                    self.pause_menu_selection = 1; // Default to Resume
-                }
+            KeyCode::Char('e') | KeyCode::Char('E') => {
+                self.resume_session(ResumeMode::CountAsElapsed)
             }
-            crate::core::timer::TimerState::Paused => {
-                if self.mode == AppMode::Break && self.pause_menu_active {
-                    // If pause menu is active, this space press should be handled by menu logic
-                    // Don't resume immediately - let the menu handle it
-                    return;
-                }
-
-                self.timer.resume();
-                // Re-enable Focus mode when resuming Pomodoro
-                if self.mode == AppMode::Pomodoro {
-                    self.auto_enable_dnd();
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                self.resume_prompt = None;
+                if let Some(ref storage) = self.storage {
+                    let _ = storage.clear_snapshot();
                 }
             }
-            crate::core::timer::TimerState::Completed => self.start_next_phase(),
+            KeyCode::Char('q') | KeyCode::Esc => {
+                // Leave the snapshot alone so the prompt comes back next time
+                self.restore_dnd_state();
+                self.should_quit = true;
+            }
+            _ => {}
         }
     }
 
-    fn reset_timer(&mut self) {
-        self.timer.reset();
-        if self.mode == AppMode::Break {
-            self.breathing_exercise = None;
-            self.breathing_complete = false;
-            // Reset break activity selection state
-            self.break_activity_selecting = false;
-            self.break_animation = None;
-            self.selected_option = 1;
-            // Re-enable break activity selection
-            self.start_break_activity_selection();
-        }
+    /// Keys while asking what the time the timer missed counts as
+    fn handle_gap_key(&mut self, key: KeyEvent) {
+        let counted_as = match key.code {
+            KeyCode::Char('w') | KeyCode::Char('W') => GapUse::Work,
+            KeyCode::Char('b') | KeyCode::Char('B') => GapUse::Break,
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => GapUse::Neither,
+            _ => return,
+        };
+        self.resolve_gap(counted_as);
     }
 
-    pub fn skip_to_break(&mut self) {
-        if self.mode == AppMode::Pomodoro {
-            // Increment session count when skipping pomodoro
-            self.session_count += 1;
-            self.start_break();
-            // DND is automatically disabled in start_break()
+    /// Record what the gap was, and if it was spent on the phase that was
+    /// running, count it towards that phase and carry on
+    fn resolve_gap(&mut self, counted_as: GapUse) {
+        let Some(gap) = self.gap_prompt.take() else {
+            return;
+        };
+        let phase = self.session.kind();
+        let minutes = gap.duration().as_secs() / 60;
+        let spent_on_phase = match counted_as {
+            GapUse::Work => !phase.is_break(),
+            GapUse::Break => phase.is_break(),
+            GapUse::Neither => false,
+        };
+        if spent_on_phase {
+            self.send(SessionCommand::Credit(gap.duration()));
+            self.send(SessionCommand::Resume);
+            self.status_message = Some(format!(
+                "▶️ {}m away counted as {} - carrying on",
+                minutes,
+                counted_as.key()
+            ));
+        } else {
+            self.status_message = Some(format!(
+                "⏸️ {}m away counted as {} - press Space to carry on",
+                minutes,
+                counted_as.key()
+            ));
         }
-    }
 
-    fn skip_break(&mut self) {
-        if self.mode == AppMode::Break {
-            self.start_pomodoro();
+        if let Some(ref storage) = self.storage {
+            let record = GapRecord {
+                gap,
+                phase,
+                counted_as,
+            };
+            if let Err(err) = storage.record_gap(&record) {
+                self.status_message = Some(format!("⚠️ Could not save to history: {}", err));
+            }
         }
     }
 
-    fn shorten_break(&mut self) {
-        if self.mode == AppMode::Break {
-            let current_duration = self.timer.duration().as_secs();
-            let short_break_duration = 5 * 60; // 5 minutes
-
-            // Only shorten if current duration is longer than short break
-            if current_duration > short_break_duration {
-                self.timer = Timer::new(short_break_duration);
-                self.break_was_shortened = true;
-                // Maintain breathing exercise if present and enabled
-                if self.breathing_enabled && self.breathing_exercise.is_none() {
-                    if let Some(duration) = self.breathing_duration {
-                        self.breathing_exercise = Some(BreathingExercise::new_from_duration(
-                            BreathingPattern::ExtendedExhale,
-                            duration,
-                        ));
-                    } else {
-                        self.breathing_exercise =
-                            Some(BreathingExercise::new(BreathingPattern::ExtendedExhale));
-                    }
+    /// Keys on the break screen, which only lets go after a delay
+    fn handle_guard_key(&mut self, key: KeyEvent) {
+        if key.code == KeyCode::Char('q') {
+            self.restore_dnd_state();
+            self.should_quit = true;
+            return;
+        }
+        if self.guard_dismiss_in().is_some() {
+            return;
+        }
+        let Some(guard) = self.guard_screen else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char('b') | KeyCode::Char('B') | KeyCode::Enter => {
+                self.guard_screen = None;
+                if self.mode() != AppMode::Break
+                    && matches!(
+                        self.timer().state(),
+                        TimerState::Running | TimerState::Paused
+                    )
+                {
+                    self.send(SessionCommand::Skip);
+                    self.status_message = Some("🌿 Good call - enjoy the break".to_string());
+                } else {
+                    self.status_message = Some(
+                        "🌿 Step away for a bit - start the next pomodoro when you're back"
+                            .to_string(),
+                    );
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('k') | KeyCode::Char('K') => {
+                self.guard_screen = None;
+                if guard.paused_work {
+                    self.send(SessionCommand::Resume);
                 }
+                self.status_message =
+                    Some("Carrying on - no more reminders until your next real break".to_string());
             }
+            _ => {}
         }
     }
 
-    fn extend_break(&mut self) {
-        if self.mode == AppMode::Break && self.break_was_shortened {
-            // Check if we're in a break after 4th session (which should be long break)
-            if (self.session_count % 4) == 0 {
-                let long_break_duration = 15 * 60; // 15 minutes
-                self.timer = Timer::new(long_break_duration);
-                self.break_was_shortened = false;
-                // Maintain breathing exercise if present and enabled
-                if self.breathing_enabled && self.breathing_exercise.is_none() {
-                    if let Some(duration) = self.breathing_duration {
-                        self.breathing_exercise = Some(BreathingExercise::new_from_duration(
-                            BreathingPattern::ExtendedExhale,
-                            duration,
-                        ));
-                    } else {
-                        self.breathing_exercise =
-                            Some(BreathingExercise::new(BreathingPattern::ExtendedExhale));
-                    }
-                }
-            }
+    /// Pick the previous session back up from its snapshot
+    pub fn resume_session(&mut self, mode: ResumeMode) {
+        let Some(mut snapshot) = self.resume_prompt.take() else {
+            return;
+        };
+        // A deadline set before the user decided (from --until) wins over the saved one
+        let deadline = self.session.deadline();
+        if deadline.is_some() {
+            snapshot.deadline = deadline;
         }
+        let state = self.restore_snapshot(&snapshot, mode);
+
+        let away_minutes = snapshot.closed_for(self.clock.wall_now()).as_secs() / 60;
+        let until = deadline
+            .map(|deadline| format!(" - working until {}", deadline.format("%H:%M")))
+            .unwrap_or_default();
+        self.status_message = Some(if state == TimerState::Running {
+            format!("▶️ Resumed after {}m away{}", away_minutes, until)
+        } else {
+            format!(
+                "▶️ Resumed after {}m away{} - press Space to continue",
+                away_minutes, until
+            )
+        });
     }
 
-    fn set_breathing_pattern(&mut self, pattern: BreathingPattern) {
-        if self.mode == AppMode::Break && self.breathing_enabled {
-            if let Some(duration) = self.breathing_duration {
-                self.breathing_exercise =
-                    Some(BreathingExercise::new_from_duration(pattern, duration));
-            } else {
-                self.breathing_exercise = Some(BreathingExercise::new(pattern));
+    /// Put the session, break activity and active task back the way the
+    /// snapshot has them, returning the timer's state
+    fn restore_snapshot(&mut self, snapshot: &SessionSnapshot, mode: ResumeMode) -> TimerState {
+        let (elapsed, paused, state) = snapshot.resumed_timer(self.clock.wall_now(), mode);
+
+        let session_state = SessionState {
+            kind: snapshot.kind,
+            planned: snapshot.planned,
+            session_count: snapshot.session_count,
+            focus_since_long_break: snapshot.focus_since_long_break,
+            shortened: snapshot.shortened,
+            extended: snapshot.extended,
+            started_at: snapshot.phase_started_at,
+            ended: snapshot.phase_recorded,
+            plan: snapshot.plan.clone(),
+            plan_step: snapshot.plan_step,
+            deadline: snapshot.deadline,
+            pauses: snapshot.pause_count,
+            adjustments: snapshot.adjustments.clone(),
+        };
+        self.session.restore(session_state, elapsed, paused, state);
+        self.breathing_enabled = snapshot.breathing_enabled;
+        self.breathing_complete = snapshot.breathing_complete;
+        self.breathing_pattern = snapshot.breathing_pattern;
+
+        self.active_task = match (snapshot.task_id, self.storage.as_ref()) {
+            (Some(id), Some(storage)) => storage
+                .task(id)
+                .ok()
+                .flatten()
+                .filter(|task| !task.is_done()),
+            _ => None,
+        };
+
+        self.intention = snapshot.intention.clone();
+        self.intention_check = None;
+        self.check_in = snapshot.check_in;
+        self.check_in_prompt = None;
+        self.breadcrumb_input = None;
+        self.breadcrumb = (snapshot.kind == PhaseKind::Pomodoro && state != TimerState::Idle)
+            .then(|| self.todays_breadcrumb())
+            .flatten();
+
+        // Tally what was logged in the pomodoro so far
+        self.interruptions = InterruptionTally::default();
+        if let (Some(started_at), Some(storage)) = (
+            snapshot
+                .phase_started_at
+                .filter(|_| !snapshot.phase_recorded),
+            self.storage.as_ref(),
+        ) {
+            let until = snapshot.saved_at + chrono::Duration::seconds(1);
+            for record in storage
+                .interruptions_between(started_at, until)
+                .unwrap_or_default()
+            {
+                self.interruptions.add(record.interruption.kind);
             }
         }
-    }
 
-    fn toggle_breathing(&mut self) {
-        self.breathing_enabled = !self.breathing_enabled;
-        if !self.breathing_enabled {
-            self.breathing_exercise = None;
-            self.breathing_complete = true;
-        } else if self.mode == AppMode::Break && !self.breathing_complete {
-            if let Some(duration) = self.breathing_duration {
-                self.breathing_exercise = Some(BreathingExercise::new_from_duration(
-                    BreathingPattern::ExtendedExhale,
-                    duration,
-                ));
-            } else {
-                self.breathing_exercise =
-                    Some(BreathingExercise::new(BreathingPattern::ExtendedExhale));
+        if self.mode() == AppMode::Break {
+            if state == TimerState::Idle {
+                self.start_break_activity_selection();
+            } else if let Some(activity) = snapshot.break_activity {
+                self.break_activity = activity;
+                self.restore_break_activity(elapsed);
             }
         }
-    }
 
-    fn skip_breathing(&mut self) {
-        if self.mode == AppMode::Break {
-            self.breathing_exercise = None;
-            self.breathing_complete = true;
+        if self.mode() != AppMode::Break && state == TimerState::Running {
+            self.auto_enable_dnd();
         }
+        self.warm_up_done = self.mode() == AppMode::WarmUp && state == TimerState::Completed;
+        state
     }
 
-    #[cfg(feature = "audio")]
-    fn toggle_audio_mute(&mut self) {
-        let is_muted = self.audio_manager.toggle_mute();
-        if is_muted {
-            self.status_message = Some("🔇 Audio muted".to_string());
-        } else {
-            self.status_message = Some("🔊 Audio unmuted".to_string());
+    /// Do `action`, remembering the state before it so `u` can take it back
+    fn undoable(&mut self, label: &'static str, action: impl FnOnce(&mut Self)) {
+        let before = self.snapshot();
+        let streak_before = self.focus_streak;
+        self.recorded_since_checkpoint.clear();
+        self.credited_since_checkpoint.clear();
+        action(self);
+
+        // Only actions that changed something are worth undoing
+        let mut after = self.snapshot();
+        after.saved_at = before.saved_at;
+        after.elapsed = before.elapsed;
+        after.paused = before.paused;
+        if after == before {
+            return;
+        }
+        if self.undo_stack.len() == UNDO_LIMIT {
+            self.undo_stack.remove(0);
         }
+        self.undo_stack.push(UndoEntry {
+            label,
+            snapshot: before,
+            recorded: std::mem::take(&mut self.recorded_since_checkpoint),
+            credited: std::mem::take(&mut self.credited_since_checkpoint),
+            streak: streak_before,
+        });
     }
 
-    #[cfg(feature = "audio")]
-    fn increase_volume(&mut self) {
-        let current_volume = self.audio_manager.volume();
-        let new_volume = (current_volume + 0.1).min(1.0);
-        self.audio_manager.set_volume(new_volume);
-        let percentage = (new_volume * 100.0).round() as u8;
-        self.status_message = Some(format!("🔊 Volume: {}%", percentage));
-    }
+    /// Go back to the state before the last skip, reset or break change
+    fn undo(&mut self) {
+        let Some(entry) = self.undo_stack.pop() else {
+            self.status_message = Some("Nothing to undo".to_string());
+            return;
+        };
+        if let Some(ref storage) = self.storage {
+            for id in &entry.recorded {
+                if let Err(err) = storage.delete_session(*id) {
+                    self.status_message = Some(format!("⚠️ Could not update history: {}", err));
+                    return;
+                }
+            }
+            for id in &entry.credited {
+                if let Err(err) = storage.remove_completed_pomodoro(*id) {
+                    self.status_message = Some(format!("⚠️ Could not update task: {}", err));
+                    return;
+                }
+            }
+        }
 
-    #[cfg(feature = "audio")]
-    fn decrease_volume(&mut self) {
-        let current_volume = self.audio_manager.volume();
-        let new_volume = (current_volume - 0.1).max(0.0);
-        self.audio_manager.set_volume(new_volume);
-        let percentage = (new_volume * 100.0).round() as u8;
-        self.status_message = Some(format!("🔉 Volume: {}%", percentage));
+        self.breathing_exercise = None;
+        self.break_animation = None;
+        self.break_activity_selecting = false;
+        self.pause_menu_active = false;
+        self.auto_start_at = None;
+        self.focus_streak = entry.streak;
+        self.restore_snapshot(&entry.snapshot, ResumeMode::KeepState);
+        match self.screen {
+            Screen::Stats => self.refresh_stats(),
+            Screen::Tasks => self.refresh_tasks(None),
+            Screen::Inbox | Screen::Timer => {}
+        }
+        self.status_message = Some(format!("↩️ Undid {}", entry.label));
     }
 
-    #[cfg(feature = "audio")]
-    fn play_test_sound(&mut self) {
-        match self.audio_manager.play_test_sound() {
-            Ok(()) => {
-                let volume = (self.audio_manager.volume() * 100.0).round() as u8;
-                let mute_status = if self.audio_manager.is_muted() {
-                    " (muted)"
-                } else {
-                    ""
-                };
-                self.status_message = Some(format!(
-                    "🎵 Test sound played - Volume: {}{}",
-                    volume, mute_status
-                ));
+    /// Rebuild the break's breathing exercise or animation after a resume
+    fn restore_break_activity(&mut self, elapsed: Duration) {
+        match self.break_activity {
+            BreakActivity::Stretch => {
+                let mut animation = BreakAnimation::new(BreakActivity::Stretch);
+                animation.update(elapsed);
+                self.break_animation = Some(animation);
             }
-            Err(_) => {
-                self.status_message = Some("❌ Audio not available".to_string());
+            BreakActivity::Breathing => {
+                let Some(pattern) = self.breathing_pattern else {
+                    return;
+                };
+                if !self.breathing_enabled || self.breathing_complete {
+                    return;
+                }
+                let mut exercise = match self.breathing_duration {
+                    Some(duration) => BreathingExercise::new_from_duration(pattern, duration),
+                    None => BreathingExercise::new(pattern),
+                };
+                exercise.update(elapsed);
+                if exercise.should_complete_session() {
+                    self.breathing_complete = true;
+                    return;
+                }
+                self.breathing_exercise = Some(exercise);
             }
         }
     }
 
-    fn update(&mut self) {
-        // Check if timer expired
-        if self.timer.is_expired() {
-            self.timer.stop();
+    /// Current session state, for resuming after a restart
+    pub fn snapshot(&self) -> SessionSnapshot {
+        let timer = self.session.timer();
+        let session = self.session.state();
+        let state = timer.state();
+        let break_activity = match (self.mode(), state) {
+            (AppMode::Break, TimerState::Running | TimerState::Paused) => Some(self.break_activity),
+            _ => None,
+        };
+        SessionSnapshot {
+            saved_at: self.clock.wall_now(),
+            kind: session.kind,
+            timer_state: state,
+            planned: session.planned,
+            elapsed: timer.elapsed(),
+            paused: timer.total_pause_duration(),
+            pause_count: session.pauses,
+            adjustments: session.adjustments,
+            session_count: session.session_count,
+            focus_since_long_break: session.focus_since_long_break,
+            shortened: session.shortened,
+            extended: session.extended,
+            break_activity,
+            breathing_pattern: self.breathing_pattern,
+            breathing_enabled: self.breathing_enabled,
+            breathing_complete: self.breathing_complete,
+            phase_started_at: session.started_at,
+            phase_recorded: session.ended,
+            task_id: self.active_task.as_ref().and_then(|task| task.id),
+            plan: session.plan,
+            plan_step: session.plan_step,
+            deadline: session.deadline,
+            intention: self.intention.clone(),
+            check_in: self.check_in,
+        }
+    }
 
-            // Play appropriate notification sound
-            #[cfg(feature = "audio")]
-            {
-                match self.mode {
-                    AppMode::Pomodoro => {
-                        // Session completed - time for a break
-                        let _ = self
-                            .audio_manager
-                            .play_notification(SoundType::SessionComplete);
-                    }
-                    AppMode::Break => {
-                        // Break completed - time to work
-                        let _ = self
-                            .audio_manager
-                            .play_notification(SoundType::BreakComplete);
-                    }
-                }
+    /// Write the current session state to storage
+    fn save_snapshot(&mut self) {
+        // Keep the old session until the user has decided what to do with it
+        if self.resume_prompt.is_some() {
+            return;
+        }
+        if let Some(ref storage) = self.storage {
+            if let Err(err) = storage.save_snapshot(&self.snapshot()) {
+                self.status_message = Some(format!("⚠️ Could not save session state: {}", err));
             }
+        }
+    }
 
-            // Update session count
-            if self.mode == AppMode::Pomodoro {
-                self.session_count += 1;
+    /// Keys on the statistics screen - the timer keeps running underneath
+    fn handle_stats_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('q') => {
+                self.restore_dnd_state();
+                self.should_quit = true;
+            }
+            KeyCode::Esc => self.show_screen(Screen::Timer),
+            KeyCode::Tab => self.show_screen(self.screen.next()),
+            KeyCode::Char(' ') => {
+                self.stats_days = if self.stats_days == 7 { 30 } else { 7 };
+                self.refresh_stats();
             }
+            KeyCode::Char('p') | KeyCode::Char('P') => self.stats_patterns = !self.stats_patterns,
+            _ => {}
         }
+    }
 
-        // Update breathing exercise if active and timer is running
-        if self.timer.state() == crate::core::timer::TimerState::Running {
-            if let Some(ref mut exercise) = self.breathing_exercise {
-                exercise.update(Duration::from_millis(100));
-
-                // Check if breathing session should complete (ends on exhale/post-exhale)
-                if exercise.should_complete_session() && !self.breathing_complete {
-                    self.breathing_exercise = None;
-                    self.breathing_complete = true;
-                }
+    /// Keys on the task screen - the timer keeps running underneath
+    fn handle_tasks_key(&mut self, key: KeyEvent) {
+        if let Some(ref mut form) = self.task_panel.form {
+            match form.handle_key(key) {
+                FormAction::Save => self.save_task_form(),
+                FormAction::Cancel => self.task_panel.form = None,
+                FormAction::None => {}
             }
+            return;
+        }
 
-            // Update break animation if active
-            if let Some(ref mut animation) = self.break_animation {
-                animation.update(Duration::from_millis(100));
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Char('q') => {
+                self.restore_dnd_state();
+                self.should_quit = true;
+            }
+            KeyCode::Esc => self.show_screen(Screen::Timer),
+            KeyCode::Tab => self.show_screen(self.screen.next()),
+            KeyCode::Up if shift => self.move_selected_task(true),
+            KeyCode::Down if shift => self.move_selected_task(false),
+            KeyCode::Char('K') => self.move_selected_task(true),
+            KeyCode::Char('J') => self.move_selected_task(false),
+            KeyCode::Up | KeyCode::Char('k') => self.task_panel.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.task_panel.select_next(),
+            KeyCode::Char('a') if self.storage.is_some() => {
+                self.task_panel.form = Some(TaskForm::new());
+            }
+            KeyCode::Char('e') => {
+                if let Some(task) = self.task_panel.selected_task() {
+                    self.task_panel.form = Some(TaskForm::from_task(task));
+                }
             }
+            KeyCode::Char('c') => self.toggle_selected_task_done(),
+            KeyCode::Enter => self.toggle_active_task(),
+            _ => {}
         }
     }
 
-    fn start_next_phase(&mut self) {
-        match self.mode {
-            AppMode::Pomodoro => self.start_break(),
-            AppMode::Break => {
-                // Play gentle break end sound
-                #[cfg(feature = "audio")]
-                {
-                    let _ = self.audio_manager.play_notification(SoundType::BreakEnd);
-                }
-
-                // Show ready message when break completes
-                self.status_message = Some("Break complete! Press Space when you're ready for your next pomodoro".to_string());
-                self.start_pomodoro();
+    /// Keys on the inbox screen - the timer keeps running underneath
+    fn handle_inbox_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('q') => {
+                self.restore_dnd_state();
+                self.should_quit = true;
             }
+            KeyCode::Esc => self.show_screen(Screen::Timer),
+            KeyCode::Tab => self.show_screen(self.screen.next()),
+            KeyCode::Up | KeyCode::Char('k') => self.inbox.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.inbox.select_next(),
+            KeyCode::Char('t') => self.inbox_item_to_task(),
+            KeyCode::Char('s') => self.schedule_inbox_item(),
+            KeyCode::Char('d') => self.delete_inbox_item(),
+            _ => {}
         }
     }
 
-    fn start_break(&mut self) {
-        let is_long_break = self.session_count % 4 == 0;
-        let break_duration = if is_long_break {
-            15 * 60 // Long break after 4 sessions
-        } else {
-            5 * 60 // Short break
+    /// Keys while parking a thought in the inbox
+    fn handle_inbox_input_key(&mut self, key: KeyEvent) {
+        let Some(ref mut input) = self.inbox_input else {
+            return;
         };
 
-        self.mode = AppMode::Break;
-        self.timer = Timer::new(break_duration);
-        self.break_was_shortened = false; // Reset shortened state for new break
-        self.breathing_complete = false;
-
-        // Start break activity selection
-        self.start_break_activity_selection();
-
-        // Play special sound for long break
-        #[cfg(feature = "audio")]
-        if is_long_break {
-            let _ = self
-                .audio_manager
-                .play_notification(SoundType::LongBreakStart);
-        }
-
-        // Disable DND when starting a break
-        self.auto_disable_dnd();
-        // Don't auto-start - wait for user to press space
+        match key.code {
+            KeyCode::Esc => self.inbox_input = None,
+            KeyCode::Enter => {
+                let text = input.value().trim().to_string();
+                self.inbox_input = None;
+                if text.is_empty() {
+                    return;
+                }
+                let Some(ref storage) = self.storage else {
+                    self.status_message =
+                        Some("⚠️ The inbox needs the session history".to_string());
+                    return;
+                };
+                if let Err(err) =
+                    storage.add_inbox_item(&InboxItem::new(text, self.clock.wall_now()))
+                {
+                    self.status_message = Some(format!("⚠️ Could not save to the inbox: {}", err));
+                    return;
+                }
+                self.refresh_inbox();
+                self.status_message = Some(format!(
+                    "📥 Parked in the inbox ({} to sort out on your break)",
+                    self.inbox_due()
+                ));
+            }
+            _ => {
+                input.handle_key(key);
+            }
+        }
+    }
+
+    /// Turn the selected inbox item into a task
+    fn inbox_item_to_task(&mut self) {
+        let (Some(item), Some(storage)) = (self.inbox.selected_item(), self.storage.as_ref())
+        else {
+            return;
+        };
+        let Some(id) = item.id else {
+            return;
+        };
+        let result = storage
+            .add_task(&Task::new(item.text.clone()))
+            .and_then(|_| storage.delete_inbox_item(id));
+        if let Err(err) = result {
+            self.status_message = Some(format!("⚠️ Could not make a task: {}", err));
+            return;
+        }
+        self.status_message = Some(format!("📋 Added to your tasks: {}", item.text));
+        self.refresh_inbox();
+    }
+
+    /// Put the selected inbox item off by another day
+    fn schedule_inbox_item(&mut self) {
+        let (Some(item), Some(storage)) = (self.inbox.selected_item(), self.storage.as_ref())
+        else {
+            return;
+        };
+        let Some(id) = item.id else {
+            return;
+        };
+        let day = item.next_schedule(self.clock.wall_now().date_naive());
+        if let Err(err) = storage.schedule_inbox_item(id, day) {
+            self.status_message = Some(format!("⚠️ Could not schedule it: {}", err));
+            return;
+        }
+        self.status_message = Some(format!(
+            "📅 Back in the inbox on {}",
+            day.format("%a %-d %b")
+        ));
+        self.refresh_inbox();
+    }
+
+    fn delete_inbox_item(&mut self) {
+        let (Some(item), Some(storage)) = (self.inbox.selected_item(), self.storage.as_ref())
+        else {
+            return;
+        };
+        let Some(id) = item.id else {
+            return;
+        };
+        if let Err(err) = storage.delete_inbox_item(id) {
+            self.status_message = Some(format!("⚠️ Could not delete it: {}", err));
+            return;
+        }
+        self.refresh_inbox();
+    }
+
+    /// Reload the inbox from storage
+    fn refresh_inbox(&mut self) {
+        let Some(ref storage) = self.storage else {
+            return;
+        };
+        match storage.inbox_items() {
+            Ok(items) => self.inbox.set_items(items),
+            Err(err) => self.status_message = Some(format!("⚠️ Could not load the inbox: {}", err)),
+        }
+    }
+
+    /// Keys while the task picker is open before a pomodoro
+    fn handle_picker_key(&mut self, key: KeyEvent) {
+        let Some(ref mut picker) = self.task_picker else {
+            return;
+        };
+
+        match picker.handle_key(key) {
+            PickerAction::None => return,
+            PickerAction::Cancel => {
+                self.task_picker = None;
+                return;
+            }
+            PickerAction::Choose => self.active_task = picker.selected_task().cloned(),
+            PickerAction::DecideForMe => {
+                let tasks = picker.matching_tasks();
+                self.active_task =
+                    pick_weighted(&tasks, self.clock.wall_now(), random_roll()).cloned();
+                if let Some(ref task) = self.active_task {
+                    self.status_message = Some(format!("🎲 Picked for you: {}", task.title));
+                }
+            }
+            PickerAction::NoTask => self.active_task = None,
+        }
+
+        self.task_picker = None;
+        self.start_pomodoro();
+    }
+
+    /// Offer the task picker before a pomodoro, returning whether it was shown
+    fn open_task_picker(&mut self) -> bool {
+        let tasks = match self.storage {
+            Some(ref storage) => storage.open_tasks().unwrap_or_default(),
+            None => return false,
+        };
+        if tasks.is_empty() {
+            return false;
+        }
+
+        let active = self.active_task.as_ref().and_then(|task| task.id);
+        self.task_picker = Some(TaskPicker::new(tasks, active));
+        true
+    }
+
+    /// Offer the session plans, only between phases so nothing is lost
+    fn open_plan_picker(&mut self) {
+        if self.timer().state() != TimerState::Idle {
+            self.status_message =
+                Some("⚠️ Finish or reset the current phase before switching plans".to_string());
+            return;
+        }
+        let current = self.session.plan().map(|plan| plan.name.as_str());
+        self.plan_picker = Some(PlanPicker::new(self.config().plans.clone(), current));
+    }
+
+    /// Keys while asking whether to roll from the warm-up into a pomodoro
+    fn handle_warm_up_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter | KeyCode::Char(' ') => {
+                self.send(SessionCommand::Next);
+                self.warm_up_done = false;
+                self.start_pomodoro();
+                if self.timer().state() == TimerState::Running {
+                    self.status_message = Some("🍅 Warmed up - full pomodoro running".to_string());
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Esc => {
+                self.send(SessionCommand::Next);
+                self.warm_up_done = false;
+                self.status_message =
+                    Some("Pomodoro ready - press Space whenever you are".to_string());
+            }
+            KeyCode::Char('q') => {
+                self.restore_dnd_state();
+                self.should_quit = true;
+            }
+            _ => {}
+        }
+    }
+
+    /// Keys while the plan picker is open
+    fn handle_plan_picker_key(&mut self, key: KeyEvent) {
+        let Some(ref mut picker) = self.plan_picker else {
+            return;
+        };
+
+        match picker.handle_key(key) {
+            PlanAction::None => {}
+            PlanAction::Cancel => self.plan_picker = None,
+            PlanAction::Choose => {
+                let plan = picker.selected_plan().cloned();
+                self.plan_picker = None;
+                if self
+                    .session
+                    .select_plan(plan.as_ref().map(|plan| plan.name.as_str()))
+                {
+                    self.handle_session_events();
+                    self.status_message = Some(match plan {
+                        Some(plan) => format!("📋 Following '{}': {}", plan.name, plan.summary()),
+                        None => "📋 Back to the standard cycle".to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Ask for a time to work until, only between phases like plans
+    fn open_deadline_input(&mut self) {
+        if self.timer().state() != TimerState::Idle {
+            self.status_message =
+                Some("⚠️ Finish or reset the current phase before setting a deadline".to_string());
+            return;
+        }
+        let current = self
+            .session
+            .deadline()
+            .map(|deadline| deadline.format("%H:%M").to_string())
+            .unwrap_or_default();
+        self.deadline_input = Some(TextInput::with_value(current));
+    }
+
+    /// Keys while typing a time to work until
+    fn handle_deadline_key(&mut self, key: KeyEvent) {
+        let Some(ref mut input) = self.deadline_input else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => self.deadline_input = None,
+            KeyCode::Enter => {
+                let value = input.value().trim().to_string();
+                if value.is_empty() {
+                    self.deadline_input = None;
+                    if self.session.deadline().is_some() && self.session.set_deadline(None) {
+                        self.handle_session_events();
+                        self.status_message = Some("🎯 Deadline cleared".to_string());
+                    }
+                    return;
+                }
+                match deadline::parse_time(&value) {
+                    Some(time) => {
+                        self.deadline_input = None;
+                        if !self.work_until(time) {
+                            self.status_message =
+                                Some(format!("⚠️ Less than a minute left before {}", value));
+                        }
+                    }
+                    None => {
+                        self.status_message =
+                            Some("⚠️ Enter a time like 12:30, or nothing to clear".to_string())
+                    }
+                }
+            }
+            _ => {
+                input.handle_key(key);
+            }
+        }
+    }
+
+    /// Start logging an interruption, only while a pomodoro is under way
+    fn open_interruption_input(&mut self, kind: InterruptionKind) {
+        let state = self.timer().state();
+        if self.mode() == AppMode::Break
+            || !matches!(state, TimerState::Running | TimerState::Paused)
+        {
+            self.status_message =
+                Some("⚠️ Interruptions are logged while a pomodoro is under way".to_string());
+            return;
+        }
+        self.interruption_input = Some(InterruptionInput {
+            kind,
+            note: TextInput::new(),
+        });
+    }
+
+    /// Keys while typing an interruption's note
+    fn handle_interruption_key(&mut self, key: KeyEvent) {
+        let Some(ref mut input) = self.interruption_input else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => self.interruption_input = None,
+            KeyCode::Enter => self.log_interruption(false),
+            KeyCode::Tab => self.log_interruption(true),
+            _ => {
+                input.note.handle_key(key);
+            }
+        }
+    }
+
+    /// Log the interruption being typed in, pausing the timer to deal with it
+    /// if asked to
+    fn log_interruption(&mut self, pause: bool) {
+        let Some(input) = self.interruption_input.take() else {
+            return;
+        };
+        let pause = pause && self.timer().state() == TimerState::Running;
+        if pause {
+            self.send(SessionCommand::Pause);
+        }
+        let note = input.note.value().trim();
+        let interruption = Interruption {
+            logged_at: self.clock.wall_now(),
+            kind: input.kind,
+            note: (!note.is_empty()).then(|| note.to_string()),
+            paused: pause,
+        };
+        self.interruptions.add(input.kind);
+        self.status_message = Some(format!(
+            "{} {} interruption logged - {} this pomodoro{}",
+            input.kind.mark(),
+            input.kind.label(),
+            self.interruptions.total(),
+            if pause {
+                ", press Space to carry on"
+            } else {
+                ""
+            }
+        ));
+
+        if let Some(ref storage) = self.storage {
+            let record = InterruptionRecord {
+                interruption,
+                task_id: self.active_task.as_ref().and_then(|task| task.id),
+            };
+            if let Err(err) = storage.record_interruption(&record) {
+                self.status_message = Some(format!("⚠️ Could not save to history: {}", err));
+            }
+        }
+    }
+
+    /// Ask why the pomodoro under way is being thrown away
+    fn open_void_prompt(&mut self) {
+        let state = self.timer().state();
+        if self.mode() != AppMode::Pomodoro
+            || !matches!(state, TimerState::Running | TimerState::Paused)
+        {
+            self.status_message = Some("⚠️ Only a pomodoro under way can be voided".to_string());
+            return;
+        }
+        self.void_prompt = true;
+    }
+
+    /// Keys while picking the reason for a void
+    fn handle_void_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => self.void_prompt = false,
+            KeyCode::Char(c) => {
+                let reason = c
+                    .to_digit(10)
+                    .and_then(|digit| VoidReason::ALL.get((digit as usize).checked_sub(1)?));
+                if let Some(&reason) = reason {
+                    self.void_pomodoro(reason);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Throw the pomodoro under way away without counting it towards the
+    /// break cycle, keeping it in the history with `reason`
+    pub fn void_pomodoro(&mut self, reason: VoidReason) {
+        self.void_prompt = false;
+        if self.mode() != AppMode::Pomodoro {
+            return;
+        }
+        self.void_reason = Some(reason);
+        self.undoable("the void", |app| app.send(SessionCommand::Void));
+        self.void_reason = None;
+        self.status_message = Some(format!(
+            "🗑️ Pomodoro voided ({}) - it won't count towards your next long break",
+            reason.display_name().to_lowercase()
+        ));
+    }
+
+    /// Fit the session into the time left before the next `time` of day
+    ///
+    /// Returns false if there isn't enough time left or a phase is under way.
+    pub fn work_until(&mut self, time: NaiveTime) -> bool {
+        let Some(deadline) = deadline::next_occurrence(time, self.clock.wall_now()) else {
+            return false;
+        };
+        if !self.session.set_deadline(Some(deadline)) {
+            return false;
+        }
+        self.handle_session_events();
+
+        let pomodoros = self
+            .session
+            .schedule()
+            .iter()
+            .filter(|phase| phase.kind == PhaseKind::Pomodoro)
+            .count();
+        self.status_message = Some(format!(
+            "🎯 Working until {} - {} pomodoro{} fit",
+            deadline.format("%H:%M"),
+            pomodoros,
+            if pomodoros == 1 { "" } else { "s" }
+        ));
+        true
+    }
+
+    pub fn show_screen(&mut self, screen: Screen) {
+        self.screen = screen;
+        match screen {
+            Screen::Stats => self.refresh_stats(),
+            Screen::Tasks => self.refresh_tasks(None),
+            Screen::Inbox => self.refresh_inbox(),
+            Screen::Timer => {}
+        }
+    }
+
+    fn refresh_stats(&mut self) {
+        self.stats = self
+            .storage
+            .as_ref()
+            .and_then(|storage| storage.stats(self.stats_days).ok());
+    }
+
+    /// Reload the task list from storage
+    fn refresh_tasks(&mut self, select: Option<i64>) {
+        let tasks = match self.storage {
+            Some(ref storage) => match storage.tasks() {
+                Ok(tasks) => tasks,
+                Err(err) => {
+                    self.status_message = Some(format!("⚠️ Could not load tasks: {}", err));
+                    return;
+                }
+            },
+            None => return,
+        };
+
+        // Keep the active task in sync with edits and completed pomodoros
+        if let Some(id) = self.active_task.as_ref().and_then(|task| task.id) {
+            self.active_task = tasks
+                .iter()
+                .find(|task| task.id == Some(id) && !task.is_done())
+                .cloned();
+        }
+        self.task_panel.set_tasks(tasks, select);
+    }
+
+    fn save_task_form(&mut self) {
+        let Some(form) = self.task_panel.form.take() else {
+            return;
+        };
+        let Some(ref storage) = self.storage else {
+            return;
+        };
+
+        let result = match form.editing {
+            Some(id) => storage.task(id).and_then(|task| match task {
+                Some(mut task) => {
+                    form.apply_to(&mut task);
+                    storage.update_task(&task).map(|_| id)
+                }
+                None => Ok(id),
+            }),
+            None => {
+                let mut task = Task::new("");
+                form.apply_to(&mut task);
+                storage.add_task(&task)
+            }
+        };
+
+        match result {
+            Ok(id) => self.refresh_tasks(Some(id)),
+            Err(err) => self.status_message = Some(format!("⚠️ Could not save task: {}", err)),
+        }
+    }
+
+    fn toggle_selected_task_done(&mut self) {
+        let Some(task) = self.task_panel.selected_task() else {
+            return;
+        };
+        let (Some(id), Some(storage)) = (task.id, self.storage.as_ref()) else {
+            return;
+        };
+        let status = if task.is_done() {
+            TaskStatus::Open
+        } else {
+            TaskStatus::Done
+        };
+
+        if let Err(err) = storage.set_task_status(id, status) {
+            self.status_message = Some(format!("⚠️ Could not update task: {}", err));
+            return;
+        }
+        self.refresh_tasks(None);
+    }
+
+    fn move_selected_task(&mut self, up: bool) {
+        let (Some(selected), Some(neighbor)) = (
+            self.task_panel.selected_task().and_then(|task| task.id),
+            self.task_panel.reorder_neighbor(up),
+        ) else {
+            return;
+        };
+        let Some(ref mut storage) = self.storage else {
+            return;
+        };
+
+        if let Err(err) = storage.swap_task_positions(selected, neighbor) {
+            self.status_message = Some(format!("⚠️ Could not move task: {}", err));
+            return;
+        }
+        self.refresh_tasks(Some(selected));
+    }
+
+    /// Work on the selected task, or stop working on it if it is already active
+    fn toggle_active_task(&mut self) {
+        let Some(task) = self.task_panel.selected_task() else {
+            return;
+        };
+        if task.is_done() {
+            return;
+        }
+        if self.active_task.as_ref().map(|active| active.id) == Some(task.id) {
+            self.active_task = None;
+        } else {
+            self.active_task = Some(task.clone());
+        }
+    }
+
+    fn toggle_timer(&mut self) {
+        match self.session.timer().state() {
+            crate::core::timer::TimerState::Idle => {
+                if self.mode() != AppMode::Break && self.open_task_picker() {
+                    // The timer starts once a task has been chosen
+                    return;
+                }
+                self.start_pomodoro();
+            }
+            // Space stops a pomodoro that counts up (flowtime or overtime) rather than pausing it
+            crate::core::timer::TimerState::Running if self.session.timer().is_counting_up() => {
+                self.undoable("moving on", |app| app.send(SessionCommand::Next))
+            }
+            crate::core::timer::TimerState::Running => {
+                self.send(SessionCommand::Pause);
+                if self.mode() == AppMode::Break {
+                    // In break mode, show pause menu when paused
+                    self.pause_menu_active = true;
+                    self.pause_menu_selection = 1; // Default to Resume
+                }
+            }
+            crate::core::timer::TimerState::Paused => {
+                if self.mode() == AppMode::Break && self.pause_menu_active {
+                    // If pause menu is active, this space press should be handled by menu logic
+                    // Don't resume immediately - let the menu handle it
+                    return;
+                }
+                self.send(SessionCommand::Resume);
+            }
+            crate::core::timer::TimerState::Completed => {
+                self.undoable("moving on", |app| app.send(SessionCommand::Next))
+            }
+        }
+    }
+
+    /// Pause or resume without moving on - the only way to pause a pomodoro
+    /// that Space would stop (flowtime or overtime)
+    fn toggle_pause(&mut self) {
+        match self.session.timer().state() {
+            TimerState::Running if self.session.timer().is_counting_up() => {
+                self.send(SessionCommand::Pause)
+            }
+            TimerState::Running | TimerState::Paused => self.toggle_timer(),
+            TimerState::Idle | TimerState::Completed => {}
+        }
+    }
+
+    /// Start an idle timer
+    fn start_timer(&mut self) {
+        self.send(SessionCommand::Start);
+    }
+
+    /// Start an idle timer, first checking in on energy and mood if the
+    /// config says to
+    fn start_pomodoro(&mut self) {
+        if self.mode() == AppMode::Pomodoro && self.config().check_in {
+            self.check_in_prompt = Some(CheckInPrompt {
+                session_id: None,
+                energy: None,
+            });
+            return;
+        }
+        self.ask_intention_or_start();
+    }
+
+    /// Start an idle timer, first asking what a pomodoro is for if the
+    /// config says to
+    fn ask_intention_or_start(&mut self) {
+        if self.mode() == AppMode::Pomodoro && self.config().ask_intention {
+            self.intention_input = Some(TextInput::with_value(
+                self.intention.clone().unwrap_or_default(),
+            ));
+            return;
+        }
+        self.start_timer();
+    }
+
+    /// Keys while typing what the pomodoro is for
+    fn handle_intention_key(&mut self, key: KeyEvent) {
+        let Some(ref mut input) = self.intention_input else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => {
+                // The pomodoro isn't starting, so neither answer belongs to one
+                self.intention_input = None;
+                self.check_in = None;
+            }
+            KeyCode::Enter => {
+                let intention = input.value().trim().to_string();
+                self.intention = (!intention.is_empty()).then_some(intention);
+                self.intention_input = None;
+                self.start_timer();
+            }
+            _ => {
+                input.handle_key(key);
+            }
+        }
+    }
+
+    /// Keys while asking whether the pomodoro did what it was meant to
+    fn handle_intention_check_key(&mut self, key: KeyEvent) {
+        let result = match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => IntentionResult::Yes,
+            KeyCode::Char('p') | KeyCode::Char('P') => IntentionResult::Partly,
+            KeyCode::Char('n') | KeyCode::Char('N') => IntentionResult::No,
+            KeyCode::Esc => {
+                self.intention_check = None;
+                return;
+            }
+            _ => return,
+        };
+        let Some(check) = self.intention_check.take() else {
+            return;
+        };
+        if let Some(ref storage) = self.storage {
+            if let Err(err) = storage.set_intention_result(check.session_id, result) {
+                self.status_message = Some(format!("⚠️ Could not save to history: {}", err));
+                return;
+            }
+        }
+        self.status_message = Some(
+            match result {
+                IntentionResult::Yes => "🎯 Done as intended - nice",
+                IntentionResult::Partly => "🎯 Partly there - noted",
+                IntentionResult::No => "🎯 Not this time - noted",
+            }
+            .to_string(),
+        );
+    }
+
+    /// Keys while checking in on energy and mood
+    fn handle_check_in_key(&mut self, key: KeyEvent) {
+        let Some(mut prompt) = self.check_in_prompt else {
+            return;
+        };
+        let level = match key.code {
+            KeyCode::Esc => {
+                // Skipping the check-in doesn't hold the pomodoro up
+                self.check_in_prompt = None;
+                if prompt.is_start() {
+                    self.ask_intention_or_start();
+                }
+                return;
+            }
+            KeyCode::Char(c) => match CheckIn::level_for_key(c) {
+                Some(level) => level,
+                None => return,
+            },
+            _ => return,
+        };
+        let Some(energy) = prompt.energy else {
+            prompt.energy = Some(level);
+            self.check_in_prompt = Some(prompt);
+            return;
+        };
+
+        self.check_in_prompt = None;
+        let check_in = CheckIn {
+            energy,
+            mood: level,
+        };
+        match prompt.session_id {
+            None => {
+                self.check_in = Some(check_in);
+                self.ask_intention_or_start();
+            }
+            Some(id) => {
+                if let Some(ref storage) = self.storage {
+                    if let Err(err) = storage.set_check_in_after(id, check_in) {
+                        self.status_message =
+                            Some(format!("⚠️ Could not save to history: {}", err));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Keys while noting where the pomodoro left off
+    fn handle_breadcrumb_key(&mut self, key: KeyEvent) {
+        // Someone's at the keyboard, so the break can wait for them
+        self.auto_start_at = None;
+        let Some(ref mut input) = self.breadcrumb_input else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => self.breadcrumb_input = None,
+            KeyCode::Enter => {
+                let note = input.value().trim().to_string();
+                self.breadcrumb_input = None;
+                if note.is_empty() {
+                    return;
+                }
+                let breadcrumb = Breadcrumb {
+                    written_at: self.clock.wall_now(),
+                    task_id: self.active_task.as_ref().and_then(|task| task.id),
+                    note,
+                };
+                if let Some(ref storage) = self.storage {
+                    if let Err(err) = storage.record_breadcrumb(&breadcrumb) {
+                        self.status_message =
+                            Some(format!("⚠️ Could not save to history: {}", err));
+                        return;
+                    }
+                }
+                self.status_message =
+                    Some("📌 Noted - it'll be waiting when the next pomodoro starts".to_string());
+            }
+            _ => {
+                input.handle_key(key);
+            }
+        }
+    }
+
+    /// Latest breadcrumb left today for the active task
+    fn todays_breadcrumb(&self) -> Option<Breadcrumb> {
+        let task_id = self.active_task.as_ref().and_then(|task| task.id);
+        self.storage
+            .as_ref()?
+            .last_breadcrumb(task_id, self.clock.wall_now().date_naive())
+            .ok()
+            .flatten()
+    }
+
+    fn reset_timer(&mut self) {
+        self.send(SessionCommand::Reset);
+    }
+
+    /// Skip the current pomodoro, or the warm-up straight to a pomodoro
+    pub fn skip_to_break(&mut self) {
+        if self.mode() != AppMode::Break {
+            self.undoable("the skip", |app| app.send(SessionCommand::Skip));
+        }
+    }
+
+    fn skip_break(&mut self) {
+        if self.mode() == AppMode::Break {
+            self.undoable("the break skip", |app| app.send(SessionCommand::Skip));
+        }
+    }
+
+    fn shorten_break(&mut self) {
+        self.undoable("shortening the break", |app| {
+            app.send(SessionCommand::Shorten)
+        });
+    }
+
+    fn extend_break(&mut self) {
+        self.undoable("extending the break", |app| {
+            app.send(SessionCommand::Extend)
+        });
+    }
+
+    /// Add minutes to the running or paused phase, or take them off
+    fn adjust_time(&mut self, minutes: i64) {
+        let timer = self.session.timer();
+        if !matches!(timer.state(), TimerState::Running | TimerState::Paused) {
+            self.status_message =
+                Some("⚠️ Start the timer before adding or taking off time".to_string());
+            return;
+        }
+        if timer.is_open_ended() {
+            self.status_message = Some("⚠️ Flowtime has no end to move".to_string());
+            return;
+        }
+        let before = timer.duration();
+        self.send(SessionCommand::Adjust(minutes));
+        if self.session.timer().duration() == before {
+            self.status_message = Some(if minutes > 0 {
+                "⚠️ No time left to add before the deadline".to_string()
+            } else {
+                "⚠️ No time left to take off".to_string()
+            });
+        }
+    }
+
+    /// Send a command to the session and react to whatever it did
+    fn send(&mut self, command: SessionCommand) {
+        self.session.handle(command);
+        self.handle_session_events();
+    }
+
+    /// Pass the session's events on to the audio, Focus mode and UI handlers
+    fn handle_session_events(&mut self) {
+        for event in self.session.drain_events() {
+            #[cfg(feature = "audio")]
+            self.play_event_sound(&event);
+            self.update_focus_mode(&event);
+            self.apply_session_event(event);
+        }
+    }
+
+    #[cfg(feature = "audio")]
+    fn play_event_sound(&mut self, event: &SessionEvent) {
+        let sound = match event {
+            SessionEvent::PhaseCompleted(PhaseKind::Pomodoro) => SoundType::SessionComplete,
+            SessionEvent::PhaseCompleted(PhaseKind::WarmUp) => SoundType::WarmUpEnd,
+            SessionEvent::PhaseCompleted(_) => SoundType::BreakComplete,
+            SessionEvent::OvertimeStarted => SoundType::OvertimeStart,
+            SessionEvent::OvertimeReminder(_) => SoundType::BreakEnd,
+            SessionEvent::PauseLimitReached(_) => SoundType::BreakEnd,
+            SessionEvent::PhaseReady {
+                kind: PhaseKind::LongBreak,
+                after: SessionOutcome::Completed | SessionOutcome::Skipped,
+            } => SoundType::LongBreakStart,
+            // Gentle break end sound once a finished break gives way to work
+            SessionEvent::PhaseReady {
+                kind: PhaseKind::Pomodoro,
+                after: SessionOutcome::Completed,
+            } if !self.warm_up_done => SoundType::BreakEnd,
+            _ => return,
+        };
+        let _ = self.audio_manager.play_notification(sound);
+    }
+
+    /// Focus mode is on while a pomodoro or warm-up runs and off for pauses
+    /// and breaks
+    fn update_focus_mode(&mut self, event: &SessionEvent) {
+        match event {
+            SessionEvent::PhaseStarted(kind) | SessionEvent::Resumed(kind) if !kind.is_break() => {
+                self.auto_enable_dnd()
+            }
+            // Pausing allows interruptions
+            SessionEvent::Paused(kind) if !kind.is_break() => self.auto_disable_dnd(),
+            SessionEvent::PhaseReady { kind, after }
+                if kind.is_break() && !after.is_discarded() =>
+            {
+                self.auto_disable_dnd()
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_session_event(&mut self, event: SessionEvent) {
+        match event {
+            SessionEvent::PhaseEnded(summary) => self.record_phase(&summary),
+            SessionEvent::PhaseStarted(PhaseKind::Pomodoro) => {
+                self.breadcrumb = self.todays_breadcrumb();
+            }
+            SessionEvent::PhaseCompleted(PhaseKind::WarmUp) => {
+                self.warm_up_done = true;
+                self.status_message =
+                    Some("🌱 Warm-up done - roll straight into a full pomodoro? (Y/n)".to_string());
+            }
+            SessionEvent::PhaseCompleted(_) => {
+                self.schedule_auto_start(self.session.upcoming_kind());
+            }
+            SessionEvent::DeadlineReached => {
+                self.auto_start_at = None;
+                self.status_message = Some("🏁 Deadline reached - time to wrap up".to_string());
+            }
+            SessionEvent::TimeAdjusted(adjustment) => {
+                let secs = adjustment.delta_secs.unsigned_abs();
+                let remaining = self.session.timer().remaining().as_secs();
+                self.status_message = Some(format!(
+                    "⏱️ {}{}:{:02} - {}:{:02} left",
+                    if adjustment.is_snooze() { "+" } else { "−" },
+                    secs / 60,
+                    secs % 60,
+                    remaining / 60,
+                    remaining % 60
+                ));
+            }
+            SessionEvent::PlanCompleted(name) => {
+                self.status_message = Some(format!(
+                    "🎉 Plan '{}' complete - back to the usual cycle",
+                    name
+                ));
+            }
+            SessionEvent::OvertimeStarted => {
+                self.status_message = Some(
+                    "⏰ Pomodoro done - keep going, or press Space for your break".to_string(),
+                );
+            }
+            SessionEvent::PauseLimitReached(pause) => {
+                let minutes = pause.as_secs() / 60;
+                // Only pomodoros are voided, anything else just gets the reminder
+                self.status_message = Some(match self.config().on_max_pause {
+                    PauseLimitAction::Void if self.mode() == AppMode::Pomodoro => format!(
+                        "🗑️ Pomodoro voided after a {}-minute pause - press Space to start afresh",
+                        minutes
+                    ),
+                    PauseLimitAction::Void | PauseLimitAction::Remind => format!(
+                        "⏸️ Paused for {} minutes - press Space to carry on",
+                        minutes
+                    ),
+                });
+            }
+            SessionEvent::OvertimeReminder(overtime) => {
+                self.status_message = Some(format!(
+                    "⏰ {} minutes over - press Space for your break",
+                    overtime.as_secs() / 60
+                ));
+            }
+            SessionEvent::PhaseReady {
+                kind: PhaseKind::Pomodoro,
+                after,
+            } => {
+                if after.is_discarded() {
+                    return;
+                }
+                if after == SessionOutcome::Completed && !self.warm_up_done {
+                    self.status_message = Some(
+                        "Break complete! Press Space when you're ready for your next pomodoro"
+                            .to_string(),
+                    );
+                }
+                self.breathing_exercise = None;
+                self.breathing_complete = false;
+                if !self.warm_up_done {
+                    self.schedule_auto_start(PhaseKind::Pomodoro);
+                }
+            }
+            SessionEvent::PhaseReady {
+                kind: PhaseKind::WarmUp,
+                ..
+            } => {}
+            SessionEvent::PhaseReady { kind, after } => {
+                self.breathing_complete = false;
+                if after.is_discarded() {
+                    self.breathing_exercise = None;
+                    self.break_activity_selecting = false;
+                    self.break_animation = None;
+                } else {
+                    self.breathing_pattern = None;
+                }
+                self.start_break_activity_selection();
+                let due = self.inbox_due();
+                if !after.is_discarded() && due > 0 {
+                    self.status_message = Some(format!(
+                        "📥 Inbox: {} to sort out - press I to go through it on your break",
+                        due
+                    ));
+                }
+                if after == SessionOutcome::Completed && self.config().ask_breadcrumb {
+                    self.breadcrumb_input = Some(TextInput::new());
+                }
+                if !after.is_discarded() {
+                    self.schedule_auto_start(kind);
+                }
+            }
+            // Maintain breathing exercise if present and enabled
+            SessionEvent::BreakShortened | SessionEvent::BreakExtended
+                if self.breathing_enabled && self.breathing_exercise.is_none() =>
+            {
+                if let Some(duration) = self.breathing_duration {
+                    self.breathing_exercise = Some(BreathingExercise::new_from_duration(
+                        BreathingPattern::ExtendedExhale,
+                        duration,
+                    ));
+                } else {
+                    self.breathing_exercise =
+                        Some(BreathingExercise::new(BreathingPattern::ExtendedExhale));
+                }
+            }
+            _ => {}
+        }
     }
 
-    fn start_pomodoro(&mut self) {
-        self.mode = AppMode::Pomodoro;
-        self.timer = Timer::new(25 * 60);
-        self.break_was_shortened = false; // Reset shortened state for new pomodoro
-        self.breathing_exercise = None;
-        self.breathing_complete = false;
-        // DND will be enabled when timer starts (in toggle_timer)
-        // Don't auto-start - wait for user to press space
+    fn set_breathing_pattern(&mut self, pattern: BreathingPattern) {
+        if self.mode() == AppMode::Break && self.breathing_enabled {
+            self.breathing_pattern = Some(pattern);
+            if let Some(duration) = self.breathing_duration {
+                self.breathing_exercise =
+                    Some(BreathingExercise::new_from_duration(pattern, duration));
+            } else {
+                self.breathing_exercise = Some(BreathingExercise::new(pattern));
+            }
+        }
+    }
+
+    fn toggle_breathing(&mut self) {
+        self.breathing_enabled = !self.breathing_enabled;
+        if !self.breathing_enabled {
+            self.breathing_exercise = None;
+            self.breathing_complete = true;
+        } else if self.mode() == AppMode::Break && !self.breathing_complete {
+            if let Some(duration) = self.breathing_duration {
+                self.breathing_exercise = Some(BreathingExercise::new_from_duration(
+                    BreathingPattern::ExtendedExhale,
+                    duration,
+                ));
+            } else {
+                self.breathing_exercise =
+                    Some(BreathingExercise::new(BreathingPattern::ExtendedExhale));
+            }
+        }
+    }
+
+    fn skip_breathing(&mut self) {
+        if self.mode() == AppMode::Break {
+            self.breathing_exercise = None;
+            self.breathing_complete = true;
+        }
+    }
+
+    #[cfg(feature = "audio")]
+    fn toggle_audio_mute(&mut self) {
+        let is_muted = self.audio_manager.toggle_mute();
+        if is_muted {
+            self.status_message = Some("🔇 Audio muted".to_string());
+        } else {
+            self.status_message = Some("🔊 Audio unmuted".to_string());
+        }
+    }
+
+    #[cfg(feature = "audio")]
+    fn increase_volume(&mut self) {
+        let current_volume = self.audio_manager.volume();
+        let new_volume = (current_volume + 0.1).min(1.0);
+        self.audio_manager.set_volume(new_volume);
+        let percentage = (new_volume * 100.0).round() as u8;
+        self.status_message = Some(format!("🔊 Volume: {}%", percentage));
+    }
+
+    #[cfg(feature = "audio")]
+    fn decrease_volume(&mut self) {
+        let current_volume = self.audio_manager.volume();
+        let new_volume = (current_volume - 0.1).max(0.0);
+        self.audio_manager.set_volume(new_volume);
+        let percentage = (new_volume * 100.0).round() as u8;
+        self.status_message = Some(format!("🔉 Volume: {}%", percentage));
+    }
+
+    #[cfg(feature = "audio")]
+    fn play_test_sound(&mut self) {
+        match self.audio_manager.play_test_sound() {
+            Ok(()) => {
+                let volume = (self.audio_manager.volume() * 100.0).round() as u8;
+                let mute_status = if self.audio_manager.is_muted() {
+                    " (muted)"
+                } else {
+                    ""
+                };
+                self.status_message = Some(format!(
+                    "🎵 Test sound played - Volume: {}{}",
+                    volume, mute_status
+                ));
+            }
+            Err(_) => {
+                self.status_message = Some("❌ Audio not available".to_string());
+            }
+        }
+    }
+
+    fn update(&mut self) {
+        if let Some(gap) = self.gap_detector.check(self.clock.as_ref()) {
+            if gap.is_backwards() {
+                self.note_clock_set_back(gap);
+            } else {
+                self.pause_for_gap(gap);
+            }
+        }
+        let ended = self.session.tick();
+        // Reminders can come in without the phase ending
+        self.handle_session_events();
+        if ended {
+            self.save_snapshot();
+        }
+        if self.auto_start_at.is_some_and(|at| self.clock.now() >= at) {
+            self.auto_start();
+        }
+        self.check_hyperfocus();
+
+        // Update breathing exercise if active and timer is running
+        if self.session.timer().state() == crate::core::timer::TimerState::Running {
+            if let Some(ref mut exercise) = self.breathing_exercise {
+                exercise.tick(self.clock.as_ref());
+
+                // Check if breathing session should complete (ends on exhale/post-exhale)
+                if exercise.should_complete_session() && !self.breathing_complete {
+                    self.breathing_exercise = None;
+                    self.breathing_complete = true;
+                }
+            }
+
+            // Update break animation if active
+            if let Some(ref mut animation) = self.break_animation {
+                animation.tick(self.clock.as_ref());
+            }
+        } else {
+            if let Some(ref mut exercise) = self.breathing_exercise {
+                exercise.stop_ticking();
+            }
+            if let Some(ref mut animation) = self.break_animation {
+                animation.stop_ticking();
+            }
+        }
     }
 
     pub fn timer(&self) -> &Timer {
-        &self.timer
+        self.session.timer()
+    }
+
+    /// The machine slept (or the clock jumped) under a running timer, so stop
+    /// and ask what that time was
+    fn pause_for_gap(&mut self, gap: ClockGap) {
+        if self.timer().state() != TimerState::Running || self.gap_prompt.is_some() {
+            return;
+        }
+        self.send(SessionCommand::Hold);
+        self.gap_prompt = Some(gap);
+        self.auto_start_at = None;
+        self.screen = Screen::Timer;
+        self.save_snapshot();
+    }
+
+    /// The wall clock was set back under a running timer. The timer didn't
+    /// lose any time, so there's nothing to ask - just keep it in the history
+    fn note_clock_set_back(&mut self, gap: ClockGap) {
+        if self.timer().state() != TimerState::Running {
+            return;
+        }
+        self.status_message = Some(format!(
+            "🕒 The clock went back {}m - the timer carried on",
+            gap.duration().as_secs() / 60
+        ));
+        if let Some(ref storage) = self.storage {
+            let record = GapRecord {
+                gap,
+                phase: self.session.kind(),
+                counted_as: GapUse::Neither,
+            };
+            if let Err(err) = storage.record_gap(&record) {
+                self.status_message = Some(format!("⚠️ Could not save to history: {}", err));
+            }
+        }
+    }
+
+    /// Step in if work has gone on too long since the last real break
+    fn check_hyperfocus(&mut self) {
+        let running_work = self.mode() != AppMode::Break
+            && matches!(
+                self.timer().state(),
+                TimerState::Running | TimerState::Paused
+            );
+        let current = if running_work {
+            self.timer().elapsed()
+        } else {
+            Duration::ZERO
+        };
+        let Some(level) = self.focus_streak.level(&self.config().hyperfocus, current) else {
+            return;
+        };
+        if self.guard_fired.is_some_and(|fired| fired >= level) {
+            return;
+        }
+        self.guard_fired = Some(level);
+
+        let focus = self.focus_streak.focus + current;
+        let skipped_breaks = self.focus_streak.skipped_breaks;
+        match level {
+            GuardLevel::Note => {
+                self.status_message = Some(format!(
+                    "🧠 {} of focus without a real break - take the next one?",
+                    format_focus(focus)
+                ));
+            }
+            GuardLevel::Block => {
+                let paused_work = self.timer().state() == TimerState::Running && running_work;
+                if paused_work {
+                    self.send(SessionCommand::Hold);
+                }
+                self.guard_screen = Some(GuardScreen {
+                    shown_at: self.clock.now(),
+                    focus,
+                    skipped_breaks,
+                    paused_work,
+                });
+                self.auto_start_at = None;
+                self.screen = Screen::Timer;
+            }
+        }
+
+        if let Some(ref storage) = self.storage {
+            let record = GuardRecord {
+                fired_at: self.clock.wall_now(),
+                level,
+                focus,
+                skipped_breaks,
+            };
+            if let Err(err) = storage.record_guard(&record) {
+                self.status_message = Some(format!("⚠️ Could not save to history: {}", err));
+            }
+        }
+    }
+
+    /// Time left before the break screen can be dismissed
+    pub fn guard_dismiss_in(&self) -> Option<Duration> {
+        let shown_for = self
+            .clock
+            .now()
+            .saturating_duration_since(self.guard_screen?.shown_at);
+        let wait = self.config().hyperfocus.dismiss_after();
+        (shown_for < wait).then(|| wait - shown_for)
+    }
+
+    /// Time left before the ready phase starts by itself
+    pub fn auto_start_remaining(&self) -> Option<Duration> {
+        self.auto_start_at
+            .map(|at| at.saturating_duration_since(self.clock.now()))
+    }
+
+    /// Start the grace countdown if phases of this kind start by themselves
+    fn schedule_auto_start(&mut self, kind: PhaseKind) {
+        let auto_start = self.config().auto_start;
+        self.auto_start_at = auto_start
+            .applies_to(kind)
+            .then(|| self.clock.now() + auto_start.grace());
+    }
+
+    /// The grace countdown ran out without a key press
+    fn auto_start(&mut self) {
+        if self.timer().state() == TimerState::Completed {
+            self.send(SessionCommand::Next);
+        }
+        // Moving on counts down again for the phase it readied, but that's the one we start now
+        self.auto_start_at = None;
+        if self.timer().state() != TimerState::Idle {
+            return;
+        }
+        if self.mode() == AppMode::Break {
+            // Nobody's there to pick, so go with the highlighted activity
+            self.select_break_option(self.selected_option);
+            self.status_message = Some("☕ Break started by itself".to_string());
+        } else {
+            // Check-in and intention questions still come first, and the
+            // pomodoro starts once they're answered
+            self.start_pomodoro();
+            if self.timer().state() == TimerState::Running {
+                self.status_message = Some("🍅 Pomodoro started by itself".to_string());
+            }
+        }
+        self.save_snapshot();
+    }
+
+    /// The session engine behind the timer screen
+    pub fn session(&self) -> &Session {
+        &self.session
     }
 
     pub fn breathing_exercise(&self) -> Option<&BreathingExercise> {
@@ -667,15 +2394,33 @@ impl App {
     }
 
     pub fn mode(&self) -> AppMode {
-        self.mode
+        match self.session.kind() {
+            PhaseKind::WarmUp => AppMode::WarmUp,
+            kind if kind.is_break() => AppMode::Break,
+            _ => AppMode::Pomodoro,
+        }
     }
 
     pub fn session_count(&self) -> u32 {
-        self.session_count
+        self.session.session_count()
+    }
+
+    /// Whether the current break is a long break under the configured rule
+    pub fn is_long_break(&self) -> bool {
+        self.session.kind() == PhaseKind::LongBreak
+    }
+
+    /// Speed-up factor of the app's clock, above 1.0 in time warp
+    pub fn time_warp(&self) -> f64 {
+        self.clock.speed()
+    }
+
+    pub fn config(&self) -> &Config {
+        self.session.config()
     }
 
     pub fn break_was_shortened(&self) -> bool {
-        self.break_was_shortened
+        self.session.was_shortened()
     }
 
     pub fn dnd_state(&self) -> DndState {
@@ -869,14 +2614,14 @@ impl App {
 
     // Break activity management
     fn start_break_activity_selection(&mut self) {
-        if self.mode == AppMode::Break {
+        if self.mode() == AppMode::Break {
             self.break_activity_selecting = true;
             self.selected_option = 1; // Start with first breathing pattern selected
         }
     }
 
     fn highlight_option(&mut self, option: u8) {
-        if self.break_activity_selecting && option >= 1 && option <= 4 {
+        if self.break_activity_selecting && (1..=4).contains(&option) {
             self.selected_option = option;
         }
     }
@@ -918,19 +2663,256 @@ impl App {
                 self.break_animation = None;
             }
         }
-        // Start the timer immediately after selection
-        self.timer.start();
+        // Start the timer immediately after selection
+        self.send(SessionCommand::Start);
+    }
+
+    // Session history
+    pub fn storage(&self) -> Option<&Storage> {
+        self.storage.as_ref()
+    }
+
+    pub fn screen(&self) -> Screen {
+        self.screen
+    }
+
+    pub fn stats(&self) -> Option<&Stats> {
+        self.stats.as_ref()
+    }
+
+    pub fn stats_days(&self) -> u32 {
+        self.stats_days
+    }
+
+    pub fn stats_patterns(&self) -> bool {
+        self.stats_patterns
+    }
+
+    pub fn task_panel(&self) -> &TaskPanel {
+        &self.task_panel
+    }
+
+    /// Task that pomodoros are currently counted towards
+    pub fn active_task(&self) -> Option<&Task> {
+        self.active_task.as_ref()
+    }
+
+    pub fn task_picker(&self) -> Option<&TaskPicker> {
+        self.task_picker.as_ref()
+    }
+
+    pub fn plan_picker(&self) -> Option<&PlanPicker> {
+        self.plan_picker.as_ref()
+    }
+
+    pub fn deadline_input(&self) -> Option<&TextInput> {
+        self.deadline_input.as_ref()
+    }
+
+    pub fn intention_input(&self) -> Option<&TextInput> {
+        self.intention_input.as_ref()
+    }
+
+    /// What the current pomodoro is meant to achieve
+    pub fn intention(&self) -> Option<&str> {
+        self.intention.as_deref()
+    }
+
+    pub fn intention_check(&self) -> Option<&IntentionCheck> {
+        self.intention_check.as_ref()
+    }
+
+    pub fn check_in_prompt(&self) -> Option<&CheckInPrompt> {
+        self.check_in_prompt.as_ref()
+    }
+
+    pub fn inbox_input(&self) -> Option<&TextInput> {
+        self.inbox_input.as_ref()
+    }
+
+    pub fn inbox(&self) -> &InboxPanel {
+        &self.inbox
+    }
+
+    /// Inbox items waiting to be sorted out today
+    pub fn inbox_due(&self) -> usize {
+        self.inbox.due(self.clock.wall_now().date_naive())
+    }
+
+    pub fn breadcrumb_input(&self) -> Option<&TextInput> {
+        self.breadcrumb_input.as_ref()
+    }
+
+    pub fn breadcrumb(&self) -> Option<&Breadcrumb> {
+        self.breadcrumb.as_ref()
+    }
+
+    /// Asking why the pomodoro is being voided
+    pub fn is_void_prompt_open(&self) -> bool {
+        self.void_prompt
+    }
+
+    pub fn interruption_input(&self) -> Option<&InterruptionInput> {
+        self.interruption_input.as_ref()
+    }
+
+    /// Interruptions logged in the current pomodoro
+    pub fn interruptions(&self) -> InterruptionTally {
+        self.interruptions
+    }
+
+    /// Waiting to hear whether to roll from the warm-up into a pomodoro
+    pub fn warm_up_done(&self) -> bool {
+        self.warm_up_done
+    }
+
+    /// Previous session waiting to be resumed or discarded
+    pub fn resume_prompt(&self) -> Option<&SessionSnapshot> {
+        self.resume_prompt.as_ref()
+    }
+
+    pub fn gap_prompt(&self) -> Option<&ClockGap> {
+        self.gap_prompt.as_ref()
+    }
+
+    pub fn guard_screen(&self) -> Option<&GuardScreen> {
+        self.guard_screen.as_ref()
+    }
+
+    /// Ask whether to resume `snapshot` before doing anything else
+    pub fn set_resume_prompt(&mut self, snapshot: SessionSnapshot) {
+        self.resume_prompt = Some(snapshot);
+    }
+
+    /// Write a finished phase to the session history
+    fn record_phase(&mut self, summary: &PhaseSummary) {
+        self.interruptions = InterruptionTally::default();
+        self.focus_streak.record(summary);
+        if self.focus_streak == FocusStreak::default() {
+            self.guard_fired = None;
+        }
+        let kind = summary.kind;
+        let outcome = summary.outcome;
+        let mut record = SessionRecord::from(summary);
+        let task_id = match kind {
+            PhaseKind::Pomodoro => self.active_task.as_ref().and_then(|task| task.id),
+            _ => None,
+        };
+        record.task_id = task_id;
+        if outcome == SessionOutcome::Voided {
+            record.void_reason = self.void_reason.take();
+        }
+        if kind == PhaseKind::Pomodoro {
+            record.intention = self.intention.take();
+            record.check_in_before = self.check_in.take();
+            self.breadcrumb = None;
+        }
+        if kind.is_break() && summary.started {
+            record.break_activity = Some(self.break_activity);
+            if self.break_activity == BreakActivity::Breathing {
+                record.breathing_pattern = self.breathing_pattern;
+            }
+        }
+
+        // A pomodoro skipped before it started leaves nothing worth keeping,
+        // while a break skipped that way still counts as a skipped break
+        let storage = self
+            .storage
+            .as_ref()
+            .filter(|_| summary.started || kind.is_break());
+        if let Some(storage) = storage {
+            match storage.record_session(&record) {
+                Ok(id) => {
+                    self.recorded_since_checkpoint.push(id);
+                    if let (Some(intention), false) = (record.intention, outcome.is_discarded()) {
+                        self.intention_check = Some(IntentionCheck {
+                            session_id: id,
+                            intention,
+                        });
+                    }
+                    if kind == PhaseKind::Pomodoro
+                        && !outcome.is_discarded()
+                        && self.config().check_in
+                    {
+                        self.check_in_prompt = Some(CheckInPrompt {
+                            session_id: Some(id),
+                            energy: None,
+                        });
+                    }
+                }
+                Err(err) => {
+                    self.status_message = Some(format!("⚠️ Could not save session: {}", err))
+                }
+            }
+            if let (Some(id), SessionOutcome::Completed) = (task_id, outcome) {
+                match storage.add_completed_pomodoro(id) {
+                    Ok(()) => self.credited_since_checkpoint.push(id),
+                    Err(err) => {
+                        self.status_message = Some(format!("⚠️ Could not update task: {}", err))
+                    }
+                }
+            }
+        }
+        match self.screen {
+            Screen::Stats => self.refresh_stats(),
+            Screen::Tasks => self.refresh_tasks(None),
+            Screen::Timer if task_id.is_some() => self.refresh_tasks(None),
+            Screen::Inbox | Screen::Timer => {}
+        }
+    }
+}
+
+/// Focus time as "2h 40m" or "50m"
+fn format_focus(focus: Duration) -> String {
+    let minutes = focus.as_secs() / 60;
+    if minutes >= 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Uniform random number in `[0, 1)` for "decide for me"
+fn random_roll() -> f64 {
+    // RandomState is seeded randomly per process and per instance
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+impl App {
+    /// App on the built-in defaults with a throwaway history, so tests never
+    /// touch the user's config file or database
+    pub(crate) fn for_test() -> Self {
+        Self::for_test_with(Config::default(), SystemClock::shared())
+    }
+
+    pub(crate) fn for_test_with(config: Config, clock: Arc<dyn Clock>) -> Self {
+        Self::with_storage(config, clock, Storage::open_in_memory().ok()).unwrap()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::{AutoStart, HyperfocusGuard, ManualClock};
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
+    /// Put the app in the long break that follows the fourth pomodoro
+    fn start_long_break(app: &mut App) {
+        let state = SessionState {
+            kind: PhaseKind::LongBreak,
+            planned: app.config().long_break_duration(),
+            session_count: 4,
+            ..app.session.state()
+        };
+        app.session
+            .restore(state, Duration::ZERO, Duration::ZERO, TimerState::Idle);
+    }
+
     #[test]
     fn test_skip_break_only_works_in_break_mode() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         // Start in Pomodoro mode
         assert_eq!(app.mode(), AppMode::Pomodoro);
@@ -950,7 +2932,7 @@ mod tests {
 
     #[test]
     fn test_skip_break_resets_timer_to_pomodoro() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         // Start a break
         app.skip_to_break();
@@ -970,7 +2952,7 @@ mod tests {
 
     #[test]
     fn test_key_handler_skip_break() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         // Start in break mode
         app.skip_to_break();
@@ -986,7 +2968,7 @@ mod tests {
 
     #[test]
     fn test_skip_break_maintains_session_count() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         let initial_count = app.session_count();
 
@@ -1002,11 +2984,10 @@ mod tests {
 
     #[test]
     fn test_shorten_break_reduces_timer_duration() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         // Start a long break (after 4 sessions)
-        app.session_count = 4;
-        app.start_break();
+        start_long_break(&mut app);
         assert_eq!(app.mode(), AppMode::Break);
         assert_eq!(app.timer().duration().as_secs(), 15 * 60); // 15 min long break
 
@@ -1021,7 +3002,7 @@ mod tests {
 
     #[test]
     fn test_shorten_break_only_works_in_break_mode() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         // Start in Pomodoro mode
         assert_eq!(app.mode(), AppMode::Pomodoro);
@@ -1035,7 +3016,7 @@ mod tests {
 
     #[test]
     fn test_shorten_break_with_short_break_has_no_effect() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         // Start a short break (first session)
         app.skip_to_break();
@@ -1050,11 +3031,10 @@ mod tests {
 
     #[test]
     fn test_key_handler_shorten_break() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         // Start in long break mode (after 4 sessions)
-        app.session_count = 4;
-        app.start_break();
+        start_long_break(&mut app);
         assert_eq!(app.mode(), AppMode::Break);
         assert_eq!(app.timer().duration().as_secs(), 15 * 60);
 
@@ -1069,7 +3049,7 @@ mod tests {
 
     #[test]
     fn test_extended_break_automation_complete_workflow() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         // Complete 3 pomodoro sessions (should be short breaks)
         for i in 1..=3 {
@@ -1103,11 +3083,10 @@ mod tests {
 
     #[test]
     fn test_extend_break_restores_full_duration() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         // Start a long break (after 4 sessions)
-        app.session_count = 4;
-        app.start_break();
+        start_long_break(&mut app);
         assert_eq!(app.timer().duration().as_secs(), 15 * 60); // 15 min long break
         assert!(!app.break_was_shortened());
 
@@ -1125,11 +3104,10 @@ mod tests {
 
     #[test]
     fn test_extend_break_only_works_on_shortened_breaks() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         // Start a regular long break (not shortened)
-        app.session_count = 4;
-        app.start_break();
+        start_long_break(&mut app);
         assert_eq!(app.timer().duration().as_secs(), 15 * 60);
         assert!(!app.break_was_shortened());
 
@@ -1141,7 +3119,7 @@ mod tests {
 
     #[test]
     fn test_extend_break_only_works_in_break_mode() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         // Start in Pomodoro mode
         assert_eq!(app.mode(), AppMode::Pomodoro);
@@ -1156,14 +3134,19 @@ mod tests {
 
     #[test]
     fn test_extend_break_only_works_for_long_breaks() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         // Start a short break (first session)
         app.skip_to_break();
         assert_eq!(app.timer().duration().as_secs(), 5 * 60); // 5 min short break
 
         // Manually set shortened flag (simulate a broken state)
-        app.break_was_shortened = true;
+        let state = SessionState {
+            shortened: true,
+            ..app.session.state()
+        };
+        app.session
+            .restore(state, Duration::ZERO, Duration::ZERO, TimerState::Idle);
 
         // Try to extend - should have no effect since it's not after 4th session
         app.extend_break();
@@ -1173,11 +3156,10 @@ mod tests {
 
     #[test]
     fn test_key_handler_extend_break() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         // Start a long break and shorten it
-        app.session_count = 4;
-        app.start_break();
+        start_long_break(&mut app);
         app.shorten_break();
         assert_eq!(app.timer().duration().as_secs(), 5 * 60);
         assert!(app.break_was_shortened());
@@ -1194,11 +3176,10 @@ mod tests {
 
     #[test]
     fn test_shortened_state_resets_on_new_cycles() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         // Start a long break and shorten it
-        app.session_count = 4;
-        app.start_break();
+        start_long_break(&mut app);
         app.shorten_break();
         assert!(app.break_was_shortened());
 
@@ -1213,7 +3194,7 @@ mod tests {
 
     #[test]
     fn test_complete_shorten_extend_workflow() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         // Build up to 4th session for long break
         for i in 1..=3 {
@@ -1261,9 +3242,1277 @@ mod tests {
         assert!(!app.break_was_shortened());
     }
 
+    #[test]
+    fn test_custom_config_durations() {
+        let config = Config {
+            work_minutes: 50,
+            short_break_minutes: 10,
+            long_break_minutes: 30,
+            long_break_rule: crate::core::LongBreakRule::EverySessions(2),
+            ..Config::default()
+        };
+        let mut app = App::for_test_with(config, SystemClock::shared());
+        assert_eq!(app.timer().duration().as_secs(), 50 * 60);
+
+        app.skip_to_break();
+        assert!(!app.is_long_break());
+        assert_eq!(app.timer().duration().as_secs(), 10 * 60);
+
+        app.skip_break();
+        assert_eq!(app.timer().duration().as_secs(), 50 * 60);
+
+        app.skip_to_break();
+        assert!(app.is_long_break());
+        assert_eq!(app.timer().duration().as_secs(), 30 * 60);
+    }
+
+    fn recorded_sessions(app: &App) -> Vec<SessionRecord> {
+        let now = chrono::Local::now();
+        app.storage()
+            .unwrap()
+            .sessions_between(
+                now - chrono::Duration::hours(1),
+                now + chrono::Duration::hours(1),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_skips_are_recorded_in_history() {
+        let mut app = App::for_test();
+
+        app.toggle_timer();
+        app.skip_to_break();
+        app.skip_break();
+
+        let sessions = recorded_sessions(&app);
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].kind, PhaseKind::Pomodoro);
+        assert_eq!(sessions[0].outcome, SessionOutcome::Skipped);
+        assert_eq!(sessions[0].planned.as_secs(), 25 * 60);
+        assert_eq!(sessions[1].kind, PhaseKind::ShortBreak);
+        assert_eq!(sessions[1].outcome, SessionOutcome::Skipped);
+        assert_eq!(sessions[1].break_activity, None);
+    }
+
+    #[test]
+    fn test_skipping_an_unstarted_pomodoro_is_not_recorded() {
+        let mut app = App::for_test();
+
+        app.skip_to_break();
+        assert_eq!(app.mode(), AppMode::Break);
+        assert!(recorded_sessions(&app).is_empty());
+
+        // The break skipped without starting still counts as a skipped break
+        app.skip_break();
+        let sessions = recorded_sessions(&app);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].kind, PhaseKind::ShortBreak);
+    }
+
+    #[test]
+    fn test_reset_is_recorded_only_for_started_phases() {
+        let mut app = App::for_test();
+
+        // Resetting an idle timer throws nothing away
+        app.reset_timer();
+        assert!(recorded_sessions(&app).is_empty());
+
+        app.toggle_timer();
+        app.toggle_timer(); // pause
+        app.reset_timer();
+
+        let sessions = recorded_sessions(&app);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].outcome, SessionOutcome::Reset);
+    }
+
+    #[test]
+    fn test_break_activity_and_adjustments_are_recorded() {
+        let mut app = App::for_test();
+        start_long_break(&mut app);
+        app.shorten_break();
+        app.extend_break();
+
+        app.highlight_option(2);
+        app.select_break_option(2);
+        app.skip_break();
+
+        let sessions = recorded_sessions(&app);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].kind, PhaseKind::LongBreak);
+        assert!(sessions[0].extended);
+        assert_eq!(sessions[0].break_activity, Some(BreakActivity::Breathing));
+        assert_eq!(
+            sessions[0].breathing_pattern,
+            Some(BreathingPattern::Coherent)
+        );
+    }
+
+    #[test]
+    fn test_stats_screen_navigation() {
+        let mut app = App::for_test();
+        app.toggle_timer();
+        app.skip_to_break();
+
+        app.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+        assert_eq!(app.screen(), Screen::Stats);
+        assert_eq!(app.stats_days(), 7);
+        let stats = app.stats().unwrap();
+        assert_eq!(stats.days.len(), 7);
+        assert_eq!(stats.abandoned_pomodoros, 1);
+
+        // Timer keys are ignored while the stats screen is shown
+        app.handle_key(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE));
+        assert_eq!(app.mode(), AppMode::Break);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        assert_eq!(app.stats_days(), 30);
+        assert_eq!(app.stats().unwrap().days.len(), 30);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE));
+        assert!(app.stats_patterns());
+
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(app.screen(), Screen::Timer);
+        assert!(!app.should_quit);
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            press(app, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn test_task_screen_add_reorder_and_complete() {
+        let mut app = App::for_test();
+        press(&mut app, KeyCode::Tab);
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.screen(), Screen::Tasks);
+
+        for title in ["Write report", "Reply to email"] {
+            press(&mut app, KeyCode::Char('a'));
+            type_text(&mut app, title);
+            press(&mut app, KeyCode::Enter);
+        }
+        let titles: Vec<&str> = app
+            .task_panel()
+            .tasks
+            .iter()
+            .map(|t| t.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["Write report", "Reply to email"]);
+        // The new task is selected after saving
+        assert_eq!(app.task_panel().selected, 1);
+
+        press(&mut app, KeyCode::Char('K'));
+        assert_eq!(app.task_panel().tasks[0].title, "Reply to email");
+        assert_eq!(app.task_panel().selected, 0);
+
+        press(&mut app, KeyCode::Char('e'));
+        type_text(&mut app, " now");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.task_panel().tasks[0].title, "Reply to email now");
+
+        press(&mut app, KeyCode::Char('c'));
+        assert!(app.task_panel().tasks[1].is_done());
+        assert_eq!(app.task_panel().tasks[0].title, "Write report");
+
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.screen(), Screen::Timer);
+    }
+
+    #[test]
+    fn test_active_task_is_attached_to_pomodoros() {
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(Config::default(), clock.clone());
+        let id = app
+            .storage()
+            .unwrap()
+            .add_task(&Task::new("Write report"))
+            .unwrap();
+        app.show_screen(Screen::Tasks);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.active_task().unwrap().id, Some(id));
+        press(&mut app, KeyCode::Esc);
+
+        // The picker opens with the active task highlighted
+        app.toggle_timer();
+        press(&mut app, KeyCode::Enter);
+        clock.advance(app.timer().duration());
+        app.update();
+        app.toggle_timer();
+        app.skip_break();
+
+        let sessions = recorded_sessions(&app);
+        assert_eq!(sessions[0].task_id, Some(id));
+        assert_eq!(sessions[1].task_id, None);
+        assert_eq!(app.active_task().unwrap().completed_pomodoros, 1);
+
+        // Abandoned pomodoros are linked but not counted
+        app.toggle_timer();
+        press(&mut app, KeyCode::Enter);
+        app.skip_to_break();
+        assert_eq!(recorded_sessions(&app)[2].task_id, Some(id));
+        assert_eq!(app.active_task().unwrap().completed_pomodoros, 1);
+    }
+
+    #[test]
+    fn test_task_picker_before_pomodoro() {
+        let mut app = App::for_test();
+        let storage = app.storage().unwrap();
+        let report = storage.add_task(&Task::new("Write report")).unwrap();
+        let email = storage.add_task(&Task::new("Reply to email")).unwrap();
+
+        app.toggle_timer();
+        assert!(app.task_picker().is_some());
+        assert_eq!(app.timer().state(), crate::core::timer::TimerState::Idle);
+
+        // Escape closes the picker without starting
+        press(&mut app, KeyCode::Esc);
+        assert!(app.task_picker().is_none());
+        assert_eq!(app.timer().state(), crate::core::timer::TimerState::Idle);
+
+        app.toggle_timer();
+        type_text(&mut app, "email");
+        assert_eq!(app.task_picker().unwrap().match_count(), 1);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.active_task().unwrap().id, Some(email));
+        assert_eq!(app.timer().state(), crate::core::timer::TimerState::Running);
+
+        // "Decide for me" only picks among the filtered tasks
+        app.reset_timer();
+        app.toggle_timer();
+        type_text(&mut app, "report");
+        app.handle_key(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL));
+        assert_eq!(app.active_task().unwrap().id, Some(report));
+        assert_eq!(app.timer().state(), crate::core::timer::TimerState::Running);
+
+        app.reset_timer();
+        app.toggle_timer();
+        app.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL));
+        assert!(app.active_task().is_none());
+        assert_eq!(app.timer().state(), crate::core::timer::TimerState::Running);
+    }
+
+    /// Snapshot `app` as if it had been saved `minutes_ago`
+    fn snapshot_from_past(app: &App, minutes_ago: i64) -> SessionSnapshot {
+        let mut snapshot = app.snapshot();
+        snapshot.saved_at -= chrono::Duration::minutes(minutes_ago);
+        snapshot
+    }
+
+    #[test]
+    fn test_undo_skip_and_reset() {
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(Config::default(), clock.clone());
+        press(&mut app, KeyCode::Char('u'));
+        assert_eq!(app.status_message(), Some("Nothing to undo"));
+
+        app.toggle_timer();
+        clock.advance(Duration::from_secs(10 * 60));
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.mode(), AppMode::Break);
+        assert_eq!(recorded_sessions(&app).len(), 1);
+
+        // The pomodoro picks up where it was and the skip leaves no trace
+        clock.advance(Duration::from_secs(30));
+        press(&mut app, KeyCode::Char('u'));
+        assert_eq!(app.status_message(), Some("↩️ Undid the skip"));
+        assert_eq!(app.mode(), AppMode::Pomodoro);
+        assert_eq!(app.timer().state(), TimerState::Running);
+        assert_eq!(app.timer().elapsed(), Duration::from_secs(10 * 60 + 30));
+        assert_eq!(app.session_count(), 0);
+        assert!(recorded_sessions(&app).is_empty());
+
+        // A reset of a paused pomodoro comes back paused, pauses and all
+        app.toggle_timer();
+        clock.advance(Duration::from_secs(2 * 60));
+        press(&mut app, KeyCode::Char('r'));
+        press(&mut app, KeyCode::Char('y'));
+        assert_eq!(app.timer().state(), TimerState::Idle);
+        press(&mut app, KeyCode::Char('u'));
+        assert_eq!(app.timer().state(), TimerState::Paused);
+        assert_eq!(app.timer().elapsed(), Duration::from_secs(10 * 60 + 30));
+        assert_eq!(
+            app.timer().total_pause_duration(),
+            Duration::from_secs(2 * 60)
+        );
+        assert!(recorded_sessions(&app).is_empty());
+    }
+
+    #[test]
+    fn test_undo_takes_back_task_credit_and_keeps_adjustments() {
+        let config = Config {
+            overtime: true,
+            ..Config::default()
+        };
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(config, clock.clone());
+        let id = app
+            .storage()
+            .unwrap()
+            .add_task(&Task::new("Write report"))
+            .unwrap();
+        app.show_screen(Screen::Tasks);
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Esc);
+
+        app.toggle_timer();
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char(']'));
+        clock.advance(app.timer().duration() + Duration::from_secs(2 * 60));
+        app.update();
+        assert!(app.session().in_overtime());
+
+        // Space ends the pomodoro in overtime, which counts towards the task
+        press(&mut app, KeyCode::Char(' '));
+        assert_eq!(app.mode(), AppMode::Break);
+        assert_eq!(app.active_task().unwrap().completed_pomodoros, 1);
+
+        press(&mut app, KeyCode::Char('u'));
+        assert_eq!(app.status_message(), Some("↩️ Undid moving on"));
+        assert!(app.session().in_overtime());
+        assert_eq!(app.active_task().unwrap().id, Some(id));
+        assert_eq!(app.active_task().unwrap().completed_pomodoros, 0);
+        assert_eq!(app.session().state().adjustments.len(), 1);
+        assert!(recorded_sessions(&app).is_empty());
+    }
+
+    #[test]
+    fn test_undo_break_changes() {
+        let mut app = App::for_test();
+        start_long_break(&mut app);
+        press(&mut app, KeyCode::Char('h'));
+        assert!(app.break_was_shortened());
+        press(&mut app, KeyCode::Char('u'));
+        assert!(!app.break_was_shortened());
+        assert!(app.is_long_break());
+        assert_eq!(app.timer().duration().as_secs(), 15 * 60);
+
+        // Picking an activity starts the break, and undo puts the choice back
+        press(&mut app, KeyCode::Char(' '));
+        assert_eq!(app.timer().state(), TimerState::Running);
+        press(&mut app, KeyCode::Char('u'));
+        assert_eq!(app.timer().state(), TimerState::Idle);
+        assert!(app.is_break_activity_selecting());
+
+        // Extending a break that was never shortened does nothing, so there's nothing to undo
+        press(&mut app, KeyCode::Char('e'));
+        press(&mut app, KeyCode::Char('u'));
+        assert_eq!(app.status_message(), Some("Nothing to undo"));
+    }
+
+    #[test]
+    fn test_auto_start_after_grace_countdown() {
+        let config = Config {
+            auto_start: AutoStart {
+                breaks: true,
+                pomodoros: true,
+                grace_seconds: 10,
+            },
+            ..Config::default()
+        };
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(config, clock.clone());
+        assert!(app.auto_start_remaining().is_none());
+
+        app.toggle_timer();
+        clock.advance(Duration::from_secs(25 * 60));
+        app.update();
+        assert_eq!(app.auto_start_remaining(), Some(Duration::from_secs(10)));
+        clock.advance(Duration::from_secs(9));
+        app.update();
+        assert_eq!(app.timer().state(), TimerState::Completed);
+
+        // The break starts with the highlighted activity
+        clock.advance(Duration::from_secs(1));
+        app.update();
+        assert_eq!(app.mode(), AppMode::Break);
+        assert_eq!(app.timer().state(), TimerState::Running);
+        assert!(!app.is_break_activity_selecting());
+        assert!(app.auto_start_remaining().is_none());
+
+        // Any key stops the next pomodoro from starting
+        clock.advance(Duration::from_secs(5 * 60));
+        app.update();
+        assert!(app.auto_start_remaining().is_some());
+        press(&mut app, KeyCode::Char('z'));
+        assert_eq!(app.status_message(), Some("Auto-start cancelled"));
+        clock.advance(Duration::from_secs(30));
+        app.update();
+        assert_eq!(app.mode(), AppMode::Break);
+        assert_eq!(app.timer().state(), TimerState::Completed);
+
+        // Moving on by hand still gets a countdown, which starts the pomodoro
+        press(&mut app, KeyCode::Char(' '));
+        assert_eq!(app.mode(), AppMode::Pomodoro);
+        clock.advance(Duration::from_secs(10));
+        app.update();
+        assert_eq!(app.timer().state(), TimerState::Running);
+        assert_eq!(recorded_sessions(&app).len(), 2);
+    }
+
+    #[test]
+    fn test_suspend_asks_what_the_gap_was() {
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(Config::default(), clock.clone());
+        app.toggle_timer();
+        app.update();
+        clock.advance(Duration::from_secs(5 * 60));
+        app.update();
+        assert!(app.gap_prompt().is_none());
+
+        // Asleep for 10 minutes that count as neither - the pomodoro waits
+        clock.suspend(Duration::from_secs(10 * 60));
+        app.update();
+        assert_eq!(app.timer().state(), TimerState::Paused);
+        assert_eq!(
+            app.gap_prompt().unwrap().duration(),
+            Duration::from_secs(10 * 60)
+        );
+        press(&mut app, KeyCode::Char('n'));
+        assert!(app.gap_prompt().is_none());
+        assert_eq!(app.timer().state(), TimerState::Paused);
+        assert_eq!(app.timer().elapsed(), Duration::from_secs(5 * 60));
+        // Pausing to ask isn't one of the user's pauses
+        assert_eq!(app.session().state().pauses, 0);
+
+        // Working through a sleep finishes the pomodoro
+        app.toggle_timer();
+        clock.suspend(Duration::from_secs(30 * 60));
+        app.update();
+        press(&mut app, KeyCode::Char('w'));
+        assert_eq!(app.timer().elapsed(), Duration::from_secs(35 * 60));
+        app.update();
+        assert_eq!(app.session_count(), 1);
+        assert_eq!(recorded_sessions(&app)[0].pause_count, 0);
+
+        let now = clock.wall_now();
+        let gaps = app
+            .storage()
+            .unwrap()
+            .gaps_between(now - chrono::Duration::days(1), now)
+            .unwrap();
+        let counted: Vec<_> = gaps.iter().map(|gap| gap.counted_as).collect();
+        assert_eq!(counted, vec![GapUse::Neither, GapUse::Work]);
+        assert!(gaps.iter().all(|gap| gap.phase == PhaseKind::Pomodoro));
+
+        // Setting the clock back doesn't stop the break, but is kept too
+        press(&mut app, KeyCode::Char(' '));
+        press(&mut app, KeyCode::Char(' '));
+        assert_eq!(app.timer().state(), TimerState::Running);
+        clock.set_wall_back(Duration::from_secs(2 * 3600));
+        app.update();
+        assert!(app.gap_prompt().is_none());
+        assert_eq!(app.timer().state(), TimerState::Running);
+        assert_eq!(
+            app.status_message(),
+            Some("🕒 The clock went back 120m - the timer carried on")
+        );
+        let gaps = app
+            .storage()
+            .unwrap()
+            .gaps_between(
+                now - chrono::Duration::days(1),
+                now + chrono::Duration::minutes(1),
+            )
+            .unwrap();
+        assert_eq!(gaps.len(), 3);
+        assert!(gaps[2].gap.is_backwards());
+        assert_eq!(gaps[2].phase, PhaseKind::ShortBreak);
+    }
+
+    #[test]
+    fn test_waiting_on_the_gap_prompt_is_not_a_long_pause() {
+        let config = Config {
+            on_max_pause: PauseLimitAction::Void,
+            ..Config::default()
+        };
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(config, clock.clone());
+        app.toggle_timer();
+        app.update();
+        clock.advance(Duration::from_secs(5 * 60));
+        clock.suspend(Duration::from_secs(10 * 60));
+        app.update();
+        assert!(app.gap_prompt().is_some());
+
+        // Nobody answers for longer than a pause may last
+        clock.advance(Duration::from_secs(15 * 60));
+        app.update();
+        assert_eq!(app.mode(), AppMode::Pomodoro);
+        assert_eq!(app.timer().state(), TimerState::Paused);
+
+        press(&mut app, KeyCode::Char('w'));
+        assert_eq!(app.timer().state(), TimerState::Running);
+        assert_eq!(app.timer().elapsed(), Duration::from_secs(15 * 60));
+        assert!(recorded_sessions(&app).is_empty());
+    }
+
+    #[test]
+    fn test_log_interruptions_with_and_without_pausing() {
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(Config::default(), clock.clone());
+        press(&mut app, KeyCode::Char('\''));
+        assert!(app.interruption_input().is_none());
+
+        app.toggle_timer();
+        press(&mut app, KeyCode::Char('\''));
+        type_text(&mut app, "check the build ");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.timer().state(), TimerState::Running);
+
+        press(&mut app, KeyCode::Char('-'));
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.timer().state(), TimerState::Paused);
+        assert_eq!(app.interruptions().to_string(), "1' 1-");
+
+        let now = clock.wall_now();
+        let logged: Vec<_> = app
+            .storage()
+            .unwrap()
+            .interruptions_between(
+                now - chrono::Duration::hours(1),
+                now + chrono::Duration::hours(1),
+            )
+            .unwrap()
+            .into_iter()
+            .map(|record| record.interruption)
+            .collect();
+        assert_eq!(logged.len(), 2);
+        assert_eq!(logged[0].kind, InterruptionKind::Internal);
+        assert_eq!(logged[0].note.as_deref(), Some("check the build"));
+        assert!(!logged[0].paused);
+        assert_eq!(logged[1].kind, InterruptionKind::External);
+        assert_eq!(logged[1].note, None);
+        assert!(logged[1].paused);
+
+        // The tally starts over with the next pomodoro
+        app.skip_to_break();
+        assert_eq!(app.interruptions().total(), 0);
+    }
+
+    #[test]
+    fn test_intention_is_asked_for_and_checked() {
+        let config = Config {
+            ask_intention: true,
+            ..Config::default()
+        };
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(config, clock.clone());
+        app.toggle_timer();
+        assert!(app.intention_input().is_some());
+        assert_eq!(app.timer().state(), TimerState::Idle);
+        type_text(&mut app, " Outline chapter 3 ");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.timer().state(), TimerState::Running);
+        assert_eq!(app.intention(), Some("Outline chapter 3"));
+
+        clock.advance(Duration::from_secs(25 * 60));
+        app.update();
+        assert_eq!(
+            app.intention_check().map(|check| check.intention.as_str()),
+            Some("Outline chapter 3")
+        );
+        assert_eq!(app.intention(), None);
+
+        // Other keys wait for an answer
+        press(&mut app, KeyCode::Char('s'));
+        assert!(app.intention_check().is_some());
+        press(&mut app, KeyCode::Char('p'));
+        assert!(app.intention_check().is_none());
+
+        let sessions = recorded_sessions(&app);
+        assert_eq!(sessions[0].intention.as_deref(), Some("Outline chapter 3"));
+        assert_eq!(sessions[0].intention_result, Some(IntentionResult::Partly));
+    }
+
+    #[test]
+    fn test_typing_carries_on_when_the_pomodoro_ends() {
+        let config = Config {
+            ask_intention: true,
+            ..Config::default()
+        };
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(config, clock.clone());
+        app.toggle_timer();
+        type_text(&mut app, "Write");
+        press(&mut app, KeyCode::Enter);
+        press(&mut app, KeyCode::Char('i'));
+        type_text(&mut app, "Call the ");
+
+        // The question waits until the thought is parked
+        clock.advance(Duration::from_secs(25 * 60));
+        app.update();
+        assert!(app.intention_check().is_some());
+        type_text(&mut app, "bank");
+        press(&mut app, KeyCode::Enter);
+        assert!(app.inbox_input().is_none());
+        assert_eq!(app.inbox_due(), 1);
+        assert!(app.intention_check().is_some());
+
+        press(&mut app, KeyCode::Char('y'));
+        assert!(app.intention_check().is_none());
+        assert_eq!(
+            recorded_sessions(&app)[0].intention_result,
+            Some(IntentionResult::Yes)
+        );
+    }
+
+    #[test]
+    fn test_pomodoros_that_start_without_space_still_ask() {
+        let config = Config {
+            warm_up: true,
+            ask_intention: true,
+            auto_start: AutoStart {
+                breaks: false,
+                pomodoros: true,
+                grace_seconds: 10,
+            },
+            ..Config::default()
+        };
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(config, clock.clone());
+        app.toggle_timer();
+        clock.advance(app.timer().duration());
+        app.update();
+        assert!(app.warm_up_done());
+
+        // Rolling in from the warm-up
+        press(&mut app, KeyCode::Char('y'));
+        assert_eq!(app.mode(), AppMode::Pomodoro);
+        assert!(app.intention_input().is_some());
+        assert_eq!(app.timer().state(), TimerState::Idle);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.timer().state(), TimerState::Running);
+
+        // Starting by itself after a break
+        app.skip_to_break();
+        app.skip_break();
+        clock.advance(Duration::from_secs(10));
+        app.update();
+        assert!(app.intention_input().is_some());
+        assert_eq!(app.timer().state(), TimerState::Idle);
+        type_text(&mut app, "Review");
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.timer().state(), TimerState::Running);
+        assert_eq!(app.intention(), Some("Review"));
+    }
+
+    #[test]
+    fn test_breadcrumb_is_left_and_picked_up_after_the_break() {
+        let config = Config {
+            ask_breadcrumb: true,
+            ..Config::default()
+        };
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(config, clock.clone());
+        app.toggle_timer();
+        clock.advance(Duration::from_secs(25 * 60));
+        app.update();
+        assert!(app.breadcrumb_input().is_none());
+        app.toggle_timer();
+        assert_eq!(app.mode(), AppMode::Break);
+        assert!(app.breadcrumb_input().is_some());
+        type_text(&mut app, "Next: wire up the export button");
+        press(&mut app, KeyCode::Enter);
+        assert!(app.breadcrumb_input().is_none());
+
+        app.skip_break();
+        assert_eq!(app.breadcrumb(), None);
+        app.toggle_timer();
+        assert_eq!(
+            app.breadcrumb().map(|breadcrumb| breadcrumb.note.as_str()),
+            Some("Next: wire up the export button")
+        );
+
+        // Only a completed pomodoro asks, and the note goes once it's over
+        app.skip_to_break();
+        assert!(app.breadcrumb_input().is_none());
+        assert_eq!(app.breadcrumb(), None);
+    }
+
+    #[test]
+    fn test_check_in_before_and_after_a_pomodoro() {
+        let config = Config {
+            check_in: true,
+            ..Config::default()
+        };
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(config, clock.clone());
+        app.toggle_timer();
+        assert!(app
+            .check_in_prompt()
+            .is_some_and(|prompt| prompt.is_start()));
+        press(&mut app, KeyCode::Char('9'));
+        assert_eq!(app.check_in_prompt().unwrap().energy, None);
+        press(&mut app, KeyCode::Char('2'));
+        assert_eq!(app.check_in_prompt().unwrap().energy, Some(2));
+        press(&mut app, KeyCode::Char('4'));
+        assert_eq!(app.timer().state(), TimerState::Running);
+
+        clock.advance(Duration::from_secs(25 * 60));
+        app.update();
+        assert!(app
+            .check_in_prompt()
+            .is_some_and(|prompt| !prompt.is_start()));
+        press(&mut app, KeyCode::Char('1'));
+        press(&mut app, KeyCode::Char('5'));
+        assert!(app.check_in_prompt().is_none());
+
+        let sessions = recorded_sessions(&app);
+        assert_eq!(
+            sessions[0].check_in_before,
+            Some(CheckIn { energy: 2, mood: 4 })
+        );
+        assert_eq!(
+            sessions[0].check_in_after,
+            Some(CheckIn { energy: 1, mood: 5 })
+        );
+
+        // Skipping the check-in still starts the next pomodoro
+        app.toggle_timer();
+        app.skip_break();
+        app.toggle_timer();
+        press(&mut app, KeyCode::Esc);
+        assert!(app.check_in_prompt().is_none());
+        assert_eq!(app.timer().state(), TimerState::Running);
+    }
+
+    #[test]
+    fn test_cancelled_start_drops_the_check_in() {
+        let config = Config {
+            check_in: true,
+            ask_intention: true,
+            ..Config::default()
+        };
+        let mut app = App::for_test_with(config, Arc::new(ManualClock::new()));
+        app.toggle_timer();
+        press(&mut app, KeyCode::Char('2'));
+        press(&mut app, KeyCode::Char('4'));
+        assert!(app.intention_input().is_some());
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.timer().state(), TimerState::Idle);
+        assert_eq!(app.snapshot().check_in, None);
+
+        // Starting again later asks afresh rather than reusing the old answer
+        app.toggle_timer();
+        press(&mut app, KeyCode::Esc);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.timer().state(), TimerState::Running);
+        assert_eq!(app.snapshot().check_in, None);
+        app.skip_to_break();
+        assert_eq!(recorded_sessions(&app)[0].check_in_before, None);
+    }
+
+    #[test]
+    fn test_park_thoughts_and_sort_them_out_on_the_break() {
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(Config::default(), clock.clone());
+        app.toggle_timer();
+        for thought in ["Book the dentist", "Water the plants", "Look up that song"] {
+            press(&mut app, KeyCode::Char('i'));
+            assert!(app.inbox_input().is_some());
+            type_text(&mut app, thought);
+            press(&mut app, KeyCode::Enter);
+            clock.advance(Duration::from_secs(60));
+            app.update();
+        }
+        // Parking a thought never stops the clock
+        assert_eq!(app.timer().state(), TimerState::Running);
+        assert_eq!(app.timer().elapsed(), Duration::from_secs(3 * 60));
+        assert_eq!(app.inbox_due(), 3);
+
+        app.skip_to_break();
+        assert!(app.status_message().unwrap().contains("Inbox: 3"));
+        press(&mut app, KeyCode::Char('I'));
+        assert_eq!(app.screen(), Screen::Inbox);
+
+        press(&mut app, KeyCode::Char('t'));
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.inbox_due(), 1);
+        assert_eq!(app.inbox().items[0].text, "Look up that song");
+        press(&mut app, KeyCode::Char('d'));
+        assert_eq!(app.inbox_due(), 0);
+        let texts: Vec<&str> = app
+            .inbox()
+            .items
+            .iter()
+            .map(|item| item.text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["Water the plants"]);
+
+        let tasks = app.storage().unwrap().tasks().unwrap();
+        assert_eq!(tasks[0].title, "Book the dentist");
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.screen(), Screen::Timer);
+    }
+
+    #[test]
+    fn test_void_with_a_reason() {
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(Config::default(), clock.clone());
+        press(&mut app, KeyCode::Char('V'));
+        assert!(!app.is_void_prompt_open());
+
+        app.toggle_timer();
+        clock.advance(Duration::from_secs(10 * 60));
+        press(&mut app, KeyCode::Char('V'));
+        assert!(app.is_void_prompt_open());
+        press(&mut app, KeyCode::Char('9'));
+        assert!(app.is_void_prompt_open());
+        press(&mut app, KeyCode::Char('2'));
+
+        assert!(!app.is_void_prompt_open());
+        assert_eq!(app.mode(), AppMode::Pomodoro);
+        assert_eq!(app.timer().state(), TimerState::Idle);
+        assert_eq!(app.session_count(), 0);
+        let sessions = recorded_sessions(&app);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].outcome, SessionOutcome::Voided);
+        assert_eq!(sessions[0].void_reason, Some(VoidReason::Distracted));
+
+        // Changed minds get the pomodoro back
+        press(&mut app, KeyCode::Char('u'));
+        assert_eq!(app.timer().state(), TimerState::Running);
+        assert!(recorded_sessions(&app).is_empty());
+    }
+
+    #[test]
+    fn test_long_pause_voids_the_pomodoro() {
+        let config = Config {
+            max_pause_minutes: 5,
+            on_max_pause: PauseLimitAction::Void,
+            ..Config::default()
+        };
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(config, clock.clone());
+        app.toggle_timer();
+        clock.advance(Duration::from_secs(8 * 60));
+        app.toggle_timer();
+        clock.advance(Duration::from_secs(4 * 60));
+        app.update();
+        assert_eq!(app.timer().state(), TimerState::Paused);
+        assert_eq!(app.timer().current_pause(), Duration::from_secs(4 * 60));
+
+        clock.advance(Duration::from_secs(60));
+        app.update();
+        assert_eq!(app.timer().state(), TimerState::Idle);
+        assert_eq!(app.mode(), AppMode::Pomodoro);
+        assert!(app.status_message().unwrap().contains("voided"));
+
+        let sessions = recorded_sessions(&app);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].outcome, SessionOutcome::Voided);
+        assert_eq!(sessions[0].pause_count, 1);
+        assert_eq!(sessions[0].actual, Duration::from_secs(8 * 60));
+        assert_eq!(app.snapshot().timer_state, TimerState::Idle);
+
+        // Breaks aren't voided, so a long pause in one only gets a reminder
+        app.skip_to_break();
+        press(&mut app, KeyCode::Char(' '));
+        app.toggle_timer();
+        assert_eq!(app.timer().state(), TimerState::Paused);
+        clock.advance(Duration::from_secs(5 * 60));
+        app.update();
+        assert_eq!(app.mode(), AppMode::Break);
+        assert_eq!(app.timer().state(), TimerState::Paused);
+        assert_eq!(
+            app.status_message(),
+            Some("⏸️ Paused for 5 minutes - press Space to carry on")
+        );
+    }
+
+    #[test]
+    fn test_hyperfocus_guard_escalates() {
+        let config = Config {
+            hyperfocus: HyperfocusGuard {
+                enabled: true,
+                note_minutes: 30,
+                block_minutes: 40,
+                ..HyperfocusGuard::default()
+            },
+            ..Config::default()
+        };
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(config, clock.clone());
+        app.toggle_timer();
+        clock.advance(Duration::from_secs(25 * 60));
+        app.update();
+        app.toggle_timer();
+        press(&mut app, KeyCode::Char('b'));
+
+        press(&mut app, KeyCode::Char(' '));
+        clock.advance(Duration::from_secs(5 * 60));
+        app.update();
+        assert!(app.status_message().unwrap().starts_with("🧠 30m of focus"));
+        assert!(app.guard_screen().is_none());
+
+        clock.advance(Duration::from_secs(10 * 60));
+        app.update();
+        let guard = *app.guard_screen().unwrap();
+        assert_eq!(guard.focus, Duration::from_secs(40 * 60));
+        assert_eq!(guard.skipped_breaks, 1);
+        assert_eq!(app.timer().state(), TimerState::Paused);
+
+        // The screen only lets go after the delay
+        press(&mut app, KeyCode::Char('k'));
+        assert!(app.guard_screen().is_some());
+        clock.advance(Duration::from_secs(60));
+        press(&mut app, KeyCode::Char('k'));
+        assert!(app.guard_screen().is_none());
+        assert_eq!(app.timer().state(), TimerState::Running);
+
+        // It stays quiet for the rest of the streak, and each step is logged
+        clock.advance(Duration::from_secs(5 * 60));
+        app.update();
+        assert!(app.guard_screen().is_none());
+        let now = clock.wall_now();
+        let log = app
+            .storage()
+            .unwrap()
+            .guard_log_between(now - chrono::Duration::days(1), now)
+            .unwrap();
+        let levels: Vec<_> = log.iter().map(|record| record.level).collect();
+        assert_eq!(levels, vec![GuardLevel::Note, GuardLevel::Block]);
+        assert_eq!(log[1].focus, Duration::from_secs(40 * 60));
+    }
+
+    #[test]
+    fn test_adjust_time_with_brackets() {
+        let mut app = App::for_test();
+        press(&mut app, KeyCode::Char('}'));
+        assert!(app.status_message().unwrap().contains("Start the timer"));
+
+        app.toggle_timer();
+        press(&mut app, KeyCode::Char('}'));
+        press(&mut app, KeyCode::Char(']'));
+        press(&mut app, KeyCode::Char('['));
+        assert_eq!(app.timer().duration().as_secs(), 30 * 60);
+        assert!(app.status_message().unwrap().starts_with("⏱️ −1:00"));
+
+        app.skip_to_break();
+        let sessions = recorded_sessions(&app);
+        assert_eq!(sessions[0].adjustments.len(), 3);
+        assert_eq!(app.storage().unwrap().stats(7).unwrap().snoozes, 2);
+    }
+
+    #[test]
+    fn test_warm_up_rolls_into_a_pomodoro() {
+        let clock = Arc::new(ManualClock::new());
+        let config = Config {
+            warm_up: true,
+            ..Config::default()
+        };
+        let mut app = App::for_test_with(config, clock.clone());
+        assert_eq!(app.mode(), AppMode::WarmUp);
+
+        app.toggle_timer();
+        clock.advance(app.timer().duration());
+        app.update();
+        assert!(app.warm_up_done());
+
+        // Other keys wait for an answer
+        press(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.mode(), AppMode::WarmUp);
+        press(&mut app, KeyCode::Char('y'));
+        assert!(!app.warm_up_done());
+        assert_eq!(app.mode(), AppMode::Pomodoro);
+        assert_eq!(app.timer().state(), TimerState::Running);
+        assert_eq!(app.session_count(), 0);
+
+        let sessions = recorded_sessions(&app);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].kind, PhaseKind::WarmUp);
+        assert_eq!(sessions[0].outcome, SessionOutcome::Completed);
+    }
+
+    #[test]
+    fn test_switch_plan_from_picker() {
+        let mut app = App::for_test();
+        press(&mut app, KeyCode::Char('p'));
+        assert!(app.plan_picker().is_some());
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+        assert!(app.plan_picker().is_none());
+        assert_eq!(app.session().plan().unwrap().name, "deep work");
+        assert_eq!(app.timer().duration().as_secs(), 90 * 60);
+
+        // The plan and its progress survive a restart
+        app.skip_to_break();
+        let mut resumed = App::for_test();
+        resumed.resume_prompt = Some(snapshot_from_past(&app, 1));
+        press(&mut resumed, KeyCode::Char('p'));
+        assert_eq!(resumed.session().plan().unwrap().name, "deep work");
+        assert_eq!(resumed.session().plan_step(), 1);
+
+        // Plans only change between phases
+        resumed.toggle_timer();
+        press(&mut resumed, KeyCode::Char('p'));
+        assert!(resumed.plan_picker().is_none());
+    }
+
+    #[test]
+    fn test_work_until_survives_resume() {
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(Config::default(), clock.clone());
+        app.toggle_timer();
+        clock.advance(Duration::from_secs(5 * 60));
+        let snapshot = snapshot_from_past(&app, 2);
+
+        // --until is applied while the resume prompt is still up
+        let mut resumed = App::for_test_with(Config::default(), clock.clone());
+        resumed.set_resume_prompt(snapshot);
+        let until = clock.wall_now() + chrono::Duration::hours(3);
+        assert!(resumed.work_until(until.time()));
+        let deadline = resumed.session().deadline().unwrap();
+
+        press(&mut resumed, KeyCode::Char('p'));
+        assert!(resumed.resume_prompt().is_none());
+        assert_eq!(resumed.timer().state(), TimerState::Paused);
+        assert_eq!(resumed.session().deadline(), Some(deadline));
+        assert_eq!(resumed.session().schedule().last().unwrap().end, deadline);
+        assert!(resumed
+            .status_message()
+            .unwrap()
+            .contains(&format!("working until {}", deadline.format("%H:%M"))));
+    }
+
+    #[test]
+    fn test_work_until_from_prompt() {
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(Config::default(), clock.clone());
+        let until = clock.wall_now() + chrono::Duration::minutes(70);
+
+        press(&mut app, KeyCode::Char('w'));
+        assert!(app.deadline_input().is_some());
+        for c in until.format("%H:%M").to_string().chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert!(app.deadline_input().is_none());
+
+        let deadline = app.session().deadline().unwrap();
+        assert_eq!(
+            deadline.format("%H:%M").to_string(),
+            until.format("%H:%M").to_string()
+        );
+        let schedule = app.session().schedule();
+        assert_eq!(schedule.len(), 5);
+        assert_eq!(schedule.last().unwrap().end, deadline);
+        assert!(schedule.last().unwrap().shortened);
+
+        // The prompt opens with the current deadline, and an empty time clears it
+        press(&mut app, KeyCode::Char('w'));
+        assert_eq!(
+            app.deadline_input().unwrap().value(),
+            until.format("%H:%M").to_string()
+        );
+        for _ in 0..5 {
+            press(&mut app, KeyCode::Backspace);
+        }
+        press(&mut app, KeyCode::Enter);
+        assert!(app.session().deadline().is_none());
+        assert!(app.session().schedule().is_empty());
+    }
+
+    #[test]
+    fn test_resume_counting_time_away_as_paused() {
+        let mut app = App::for_test();
+        app.skip_to_break();
+        app.skip_break();
+        app.toggle_timer();
+        let snapshot = snapshot_from_past(&app, 10);
+
+        let mut resumed = App::for_test();
+        resumed.resume_prompt = Some(snapshot);
+        // Other keys are ignored until the user decides
+        press(&mut resumed, KeyCode::Char('s'));
+        assert_eq!(resumed.mode(), AppMode::Pomodoro);
+
+        press(&mut resumed, KeyCode::Char('p'));
+        assert!(resumed.resume_prompt().is_none());
+        assert_eq!(resumed.session_count(), 1);
+        assert_eq!(resumed.mode(), AppMode::Pomodoro);
+        assert_eq!(resumed.timer().state(), TimerState::Paused);
+        assert!(resumed.timer().elapsed() < Duration::from_secs(1));
+        assert!(resumed.timer().total_pause_duration() >= Duration::from_secs(10 * 60));
+
+        // The resumed phase is still recorded once, with its original start
+        resumed.skip_to_break();
+        let sessions = recorded_sessions(&resumed);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(
+            sessions[0].started_at.timestamp(),
+            app.session().started_at().unwrap().timestamp()
+        );
+    }
+
+    #[test]
+    fn test_resume_counting_time_away_as_elapsed() {
+        let mut app = App::for_test();
+        start_long_break(&mut app);
+        app.select_break_option(4);
+        let snapshot = snapshot_from_past(&app, 3);
+
+        let mut resumed = App::for_test();
+        resumed.resume_prompt = Some(snapshot.clone());
+        press(&mut resumed, KeyCode::Char('e'));
+        assert!(resumed.is_long_break());
+        assert_eq!(resumed.timer().state(), TimerState::Running);
+        assert!(resumed.timer().elapsed() >= Duration::from_secs(3 * 60));
+        assert_eq!(resumed.break_activity(), BreakActivity::Stretch);
+        assert!(resumed.break_animation().is_some());
+
+        // A phase that ran out while closed completes on the next update
+        let mut expired = App::for_test();
+        let mut snapshot = snapshot;
+        snapshot.saved_at -= chrono::Duration::hours(1);
+        expired.resume_prompt = Some(snapshot);
+        press(&mut expired, KeyCode::Char('e'));
+        expired.update();
+        assert_eq!(expired.timer().state(), TimerState::Completed);
+        assert_eq!(
+            recorded_sessions(&expired)[0].outcome,
+            SessionOutcome::Completed
+        );
+    }
+
+    #[test]
+    fn test_start_fresh_discards_snapshot() {
+        let mut app = App::for_test();
+        app.toggle_timer();
+        app.save_snapshot();
+        assert!(app.storage().unwrap().load_snapshot().unwrap().is_some());
+
+        app.resume_prompt = Some(app.snapshot());
+        press(&mut app, KeyCode::Char('n'));
+        assert!(app.resume_prompt().is_none());
+        assert!(app.storage().unwrap().load_snapshot().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_full_cycle_on_manual_clock() {
+        let clock = Arc::new(ManualClock::new());
+        let mut app = App::for_test_with(Config::default(), clock.clone());
+
+        app.toggle_timer();
+        clock.advance(Duration::from_secs(25 * 60) - Duration::from_millis(1));
+        app.update();
+        assert_eq!(app.timer().state(), TimerState::Running);
+        clock.advance(Duration::from_millis(1));
+        app.update();
+        assert_eq!(app.timer().state(), TimerState::Completed);
+        assert_eq!(app.session_count(), 1);
+
+        app.toggle_timer();
+        app.select_break_option(2);
+        app.update();
+
+        // A stalled loop still moves breathing on by the real time that passed
+        clock.advance(Duration::from_secs(30));
+        app.update();
+        let exercise = app.breathing_exercise().unwrap();
+        assert_eq!(exercise.get_total_elapsed(), Duration::from_secs(30));
+        assert_eq!(exercise.get_cycle_count(), 2);
+
+        // Paused time is neither counted by the timer nor replayed into breathing
+        app.toggle_timer();
+        clock.advance(Duration::from_secs(10 * 60));
+        app.update();
+        app.pause_menu_active = false;
+        app.toggle_timer();
+        app.update();
+        let exercise = app.breathing_exercise().unwrap();
+        assert_eq!(exercise.get_total_elapsed(), Duration::from_secs(30));
+
+        for _ in 0..270 {
+            clock.advance(Duration::from_secs(1));
+            app.update();
+        }
+        assert!(app.breathing_complete());
+        assert_eq!(app.timer().state(), TimerState::Completed);
+
+        let sessions = recorded_sessions(&app);
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].actual, Duration::from_secs(25 * 60));
+        assert_eq!(
+            (sessions[0].ended_at - sessions[0].started_at).num_minutes(),
+            25
+        );
+        assert_eq!(sessions[1].kind, PhaseKind::ShortBreak);
+        assert_eq!(sessions[1].outcome, SessionOutcome::Completed);
+        assert_eq!(sessions[1].paused, Duration::from_secs(10 * 60));
+    }
+
+    #[test]
+    fn test_space_ends_overtime() {
+        let clock = Arc::new(ManualClock::new());
+        let config = Config {
+            overtime: true,
+            ..Config::default()
+        };
+        let mut app = App::for_test_with(config, clock.clone());
+
+        app.toggle_timer();
+        clock.advance(Duration::from_secs(28 * 60));
+        app.update();
+        assert!(app.session().in_overtime());
+        assert_eq!(app.timer().state(), TimerState::Running);
+        assert_eq!(app.mode(), AppMode::Pomodoro);
+
+        app.toggle_timer();
+        assert_eq!(app.mode(), AppMode::Break);
+        assert_eq!(app.session_count(), 1);
+        let sessions = recorded_sessions(&app);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].outcome, SessionOutcome::Completed);
+        assert_eq!(sessions[0].actual, Duration::from_secs(25 * 60));
+        assert_eq!(sessions[0].overtime, Duration::from_secs(3 * 60));
+    }
+
+    #[test]
+    fn test_shift_p_pauses_flowtime_work() {
+        let clock = Arc::new(ManualClock::new());
+        let config = Config {
+            mode: crate::core::SessionMode::Flowtime,
+            ..Config::default()
+        };
+        let mut app = App::for_test_with(config, clock.clone());
+
+        app.toggle_timer();
+        clock.advance(Duration::from_secs(30 * 60));
+        press(&mut app, KeyCode::Char('P'));
+        assert_eq!(app.mode(), AppMode::Pomodoro);
+        assert_eq!(app.timer().state(), TimerState::Paused);
+
+        clock.advance(Duration::from_secs(5 * 60));
+        press(&mut app, KeyCode::Char('P'));
+        assert_eq!(app.timer().state(), TimerState::Running);
+        assert_eq!(app.timer().elapsed(), Duration::from_secs(30 * 60));
+
+        // Space still stops the work for a break
+        press(&mut app, KeyCode::Char(' '));
+        assert_eq!(app.mode(), AppMode::Break);
+    }
+
+    #[test]
+    fn test_random_roll_range() {
+        for _ in 0..100 {
+            let roll = random_roll();
+            assert!((0.0..1.0).contains(&roll));
+        }
+    }
+
     #[test]
     fn test_dnd_initialization() {
-        let app = App::new().unwrap();
+        let app = App::for_test();
 
         // DND should be initialized properly
         if app.is_dnd_supported() {
@@ -1281,7 +4530,7 @@ mod tests {
 
     #[test]
     fn test_dnd_auto_enable_toggle() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         let initial_state = app.dnd_auto_enabled();
         app.toggle_dnd_auto_enable();
@@ -1293,7 +4542,7 @@ mod tests {
 
     #[test]
     fn test_dnd_manual_toggle() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         if app.is_dnd_supported() {
             // Test manual DND toggle
@@ -1322,7 +4571,7 @@ mod tests {
 
     #[test]
     fn test_dnd_keyboard_controls() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         let initial_auto_state = app.dnd_auto_enabled();
 
@@ -1339,7 +4588,7 @@ mod tests {
 
     #[test]
     fn test_dnd_quit_behavior() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
 
         // Test that quitting restores DND state
         let key_event = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
@@ -1353,7 +4602,7 @@ mod tests {
     #[cfg(target_os = "macos")]
     #[test]
     fn test_dnd_macos_integration() {
-        let app = App::new().unwrap();
+        let app = App::for_test();
 
         // On macOS, DND should be supported
         assert!(app.is_dnd_supported());
@@ -1368,7 +4617,7 @@ mod tests {
     #[cfg(not(target_os = "macos"))]
     #[test]
     fn test_dnd_non_macos_behavior() {
-        let app = App::new().unwrap();
+        let app = App::for_test();
 
         // On non-macOS, DND should not be supported
         assert!(!app.is_dnd_supported());
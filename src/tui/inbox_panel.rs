@@ -0,0 +1,36 @@
+use crate::core::InboxItem;
+use chrono::NaiveDate;
+
+/// State of the distraction inbox screen
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InboxPanel {
+    pub items: Vec<InboxItem>,
+    pub selected: usize,
+}
+
+impl InboxPanel {
+    /// Replace the items, keeping the selection in range
+    pub fn set_items(&mut self, items: Vec<InboxItem>) {
+        self.items = items;
+        self.selected = self.selected.min(self.items.len().saturating_sub(1));
+    }
+
+    pub fn selected_item(&self) -> Option<&InboxItem> {
+        self.items.get(self.selected)
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.items.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Items waiting to be sorted out as of `today`
+    pub fn due(&self, today: NaiveDate) -> usize {
+        self.items.iter().filter(|item| item.is_due(today)).count()
+    }
+}
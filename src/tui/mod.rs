@@ -1,4 +1,9 @@
 mod app;
+mod inbox_panel;
+mod input;
+mod plan_picker;
+mod task_panel;
+mod task_picker;
 mod ui;
 
 pub use app::App;
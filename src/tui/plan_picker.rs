@@ -0,0 +1,78 @@
+use crate::core::SessionPlan;
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// What the plan picker wants the app to do after a key press
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanAction {
+    None,
+    /// Switch to the highlighted entry
+    Choose,
+    Cancel,
+}
+
+/// List of session plans to switch to, headed by the standard cycle
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanPicker {
+    plans: Vec<SessionPlan>,
+    /// 0 is the standard cycle, then one entry per plan
+    pub selected: usize,
+}
+
+impl PlanPicker {
+    /// Create a picker with the `current` plan highlighted
+    pub fn new(plans: Vec<SessionPlan>, current: Option<&str>) -> Self {
+        let selected = current
+            .and_then(|name| plans.iter().position(|plan| plan.name == name))
+            .map_or(0, |index| index + 1);
+        Self { plans, selected }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> PlanAction {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return PlanAction::Cancel,
+            KeyCode::Enter | KeyCode::Char(' ') => return PlanAction::Choose,
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') if self.selected < self.plans.len() => {
+                self.selected += 1
+            }
+            _ => {}
+        }
+        PlanAction::None
+    }
+
+    pub fn plans(&self) -> &[SessionPlan] {
+        &self.plans
+    }
+
+    /// The highlighted plan, `None` for the standard cycle
+    pub fn selected_plan(&self) -> Option<&SessionPlan> {
+        self.selected
+            .checked_sub(1)
+            .and_then(|index| self.plans.get(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn press(picker: &mut PlanPicker, code: KeyCode) -> PlanAction {
+        picker.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_navigate_and_choose() {
+        let mut picker = PlanPicker::new(SessionPlan::presets(), Some("study"));
+        assert_eq!(picker.selected_plan().unwrap().name, "study");
+
+        press(&mut picker, KeyCode::Down);
+        assert_eq!(picker.selected, 2);
+        press(&mut picker, KeyCode::Up);
+        press(&mut picker, KeyCode::Up);
+        press(&mut picker, KeyCode::Up);
+        assert!(picker.selected_plan().is_none());
+        assert_eq!(press(&mut picker, KeyCode::Enter), PlanAction::Choose);
+        assert_eq!(press(&mut picker, KeyCode::Esc), PlanAction::Cancel);
+    }
+}
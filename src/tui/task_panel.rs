@@ -0,0 +1,282 @@
+use super::input::TextInput;
+use crate::core::{Priority, Task};
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Largest pomodoro estimate the form accepts
+const MAX_ESTIMATE: u32 = 20;
+
+/// Field focused in the task form
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskField {
+    Title,
+    Notes,
+    Priority,
+    Estimate,
+    Tags,
+}
+
+impl TaskField {
+    pub const ALL: [TaskField; 5] = [
+        TaskField::Title,
+        TaskField::Notes,
+        TaskField::Priority,
+        TaskField::Estimate,
+        TaskField::Tags,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TaskField::Title => "Title",
+            TaskField::Notes => "Notes",
+            TaskField::Priority => "Priority",
+            TaskField::Estimate => "Estimate",
+            TaskField::Tags => "Tags",
+        }
+    }
+
+    fn index(&self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|field| field == self)
+            .unwrap_or(0)
+    }
+}
+
+/// What the form wants the app to do after a key press
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormAction {
+    None,
+    Save,
+    Cancel,
+}
+
+/// Add/edit form for a single task
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskForm {
+    /// Task being edited, `None` when adding a new one
+    pub editing: Option<i64>,
+    pub title: TextInput,
+    pub notes: TextInput,
+    pub priority: Priority,
+    pub estimate: u32,
+    pub tags: TextInput,
+    pub field: TaskField,
+}
+
+impl TaskForm {
+    pub fn new() -> Self {
+        Self {
+            editing: None,
+            title: TextInput::new(),
+            notes: TextInput::new(),
+            priority: Priority::Medium,
+            estimate: 1,
+            tags: TextInput::new(),
+            field: TaskField::Title,
+        }
+    }
+
+    pub fn from_task(task: &Task) -> Self {
+        Self {
+            editing: task.id,
+            title: TextInput::with_value(task.title.clone()),
+            notes: TextInput::with_value(task.notes.clone()),
+            priority: task.priority,
+            estimate: task.estimated_pomodoros,
+            tags: TextInput::with_value(task.tags.join(", ")),
+            field: TaskField::Title,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> FormAction {
+        match key.code {
+            KeyCode::Esc => return FormAction::Cancel,
+            KeyCode::Enter if !self.title.value().trim().is_empty() => return FormAction::Save,
+            KeyCode::Up | KeyCode::BackTab => self.move_field(-1),
+            KeyCode::Down | KeyCode::Tab => self.move_field(1),
+            _ => match self.field {
+                TaskField::Title => {
+                    self.title.handle_key(key);
+                }
+                TaskField::Notes => {
+                    self.notes.handle_key(key);
+                }
+                TaskField::Tags => {
+                    self.tags.handle_key(key);
+                }
+                TaskField::Priority => {
+                    if matches!(
+                        key.code,
+                        KeyCode::Left | KeyCode::Right | KeyCode::Char(' ')
+                    ) {
+                        self.priority = self.priority.cycle();
+                    }
+                }
+                TaskField::Estimate => match key.code {
+                    KeyCode::Left | KeyCode::Char('-') => {
+                        self.estimate = self.estimate.saturating_sub(1).max(1)
+                    }
+                    KeyCode::Right | KeyCode::Char('+') => {
+                        self.estimate = (self.estimate + 1).min(MAX_ESTIMATE)
+                    }
+                    _ => {}
+                },
+            },
+        }
+        FormAction::None
+    }
+
+    /// Copy the form values onto a task
+    pub fn apply_to(&self, task: &mut Task) {
+        task.title = self.title.value().trim().to_string();
+        task.notes = self.notes.value().trim().to_string();
+        task.priority = self.priority;
+        task.estimated_pomodoros = self.estimate;
+        task.tags = Task::parse_tags(self.tags.value());
+    }
+
+    fn move_field(&mut self, step: isize) {
+        let count = TaskField::ALL.len() as isize;
+        let index = (self.field.index() as isize + step).rem_euclid(count);
+        self.field = TaskField::ALL[index as usize];
+    }
+}
+
+impl Default for TaskForm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// State of the task list screen
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskPanel {
+    pub tasks: Vec<Task>,
+    pub selected: usize,
+    pub form: Option<TaskForm>,
+}
+
+impl TaskPanel {
+    /// Replace the list, keeping the selection on the same task where possible
+    pub fn set_tasks(&mut self, tasks: Vec<Task>, select: Option<i64>) {
+        let select = select.or_else(|| self.selected_task().and_then(|task| task.id));
+        self.tasks = tasks;
+        if let Some(index) = select.and_then(|id| self.tasks.iter().position(|t| t.id == Some(id)))
+        {
+            self.selected = index;
+        }
+        self.selected = self.selected.min(self.tasks.len().saturating_sub(1));
+    }
+
+    pub fn selected_task(&self) -> Option<&Task> {
+        self.tasks.get(self.selected)
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.tasks.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Open task next to the selection that it can swap places with
+    pub fn reorder_neighbor(&self, up: bool) -> Option<i64> {
+        let task = self.selected_task()?;
+        if task.is_done() {
+            return None;
+        }
+        let neighbor = if up {
+            self.tasks.get(self.selected.checked_sub(1)?)
+        } else {
+            self.tasks.get(self.selected + 1)
+        }?;
+        if neighbor.is_done() {
+            None
+        } else {
+            neighbor.id
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::TaskStatus;
+    use crossterm::event::KeyModifiers;
+
+    fn press(form: &mut TaskForm, code: KeyCode) -> FormAction {
+        form.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn task(id: i64, title: &str, status: TaskStatus) -> Task {
+        let mut task = Task::new(title);
+        task.id = Some(id);
+        task.status = status;
+        task
+    }
+
+    #[test]
+    fn test_form_fields_and_save() {
+        let mut form = TaskForm::new();
+
+        // An empty title can't be saved
+        assert_eq!(press(&mut form, KeyCode::Enter), FormAction::None);
+
+        for c in "Review PR".chars() {
+            press(&mut form, KeyCode::Char(c));
+        }
+        press(&mut form, KeyCode::Down);
+        press(&mut form, KeyCode::Down);
+        assert_eq!(form.field, TaskField::Priority);
+        press(&mut form, KeyCode::Right);
+        press(&mut form, KeyCode::Down);
+        press(&mut form, KeyCode::Right);
+        press(&mut form, KeyCode::Right);
+        press(&mut form, KeyCode::Down);
+        for c in "work, Code".chars() {
+            press(&mut form, KeyCode::Char(c));
+        }
+        assert_eq!(press(&mut form, KeyCode::Enter), FormAction::Save);
+
+        let mut task = Task::new("");
+        form.apply_to(&mut task);
+        assert_eq!(task.title, "Review PR");
+        assert_eq!(task.priority, Priority::High);
+        assert_eq!(task.estimated_pomodoros, 3);
+        assert_eq!(task.tags, vec!["work", "code"]);
+
+        // Field navigation wraps around
+        press(&mut form, KeyCode::Down);
+        assert_eq!(form.field, TaskField::Title);
+        assert_eq!(press(&mut form, KeyCode::Esc), FormAction::Cancel);
+    }
+
+    #[test]
+    fn test_selection_and_reorder_neighbors() {
+        let mut panel = TaskPanel::default();
+        panel.set_tasks(
+            vec![
+                task(1, "A", TaskStatus::Open),
+                task(2, "B", TaskStatus::Open),
+                task(3, "C", TaskStatus::Done),
+            ],
+            Some(2),
+        );
+        assert_eq!(panel.selected, 1);
+        assert_eq!(panel.reorder_neighbor(true), Some(1));
+        // Open tasks never swap with done ones
+        assert_eq!(panel.reorder_neighbor(false), None);
+
+        panel.select_next();
+        panel.select_next();
+        assert_eq!(panel.selected, 2);
+        assert_eq!(panel.reorder_neighbor(true), None);
+
+        // Selection stays in range when the list shrinks
+        panel.set_tasks(vec![task(1, "A", TaskStatus::Open)], None);
+        assert_eq!(panel.selected, 0);
+    }
+}
@@ -0,0 +1,208 @@
+use super::input::TextInput;
+use crate::core::Task;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// What the picker wants the app to do after a key press
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickerAction {
+    None,
+    /// Start with the highlighted task
+    Choose,
+    /// Let the app pick one of the matching tasks
+    DecideForMe,
+    /// Start without a task
+    NoTask,
+    Cancel,
+}
+
+/// Fuzzy-filtered list of open tasks shown before a pomodoro starts
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskPicker {
+    pub query: TextInput,
+    tasks: Vec<Task>,
+    /// Indices into `tasks` that match the query, best first
+    matches: Vec<usize>,
+    pub selected: usize,
+}
+
+impl TaskPicker {
+    /// Create a picker with `active` highlighted if it is in the list
+    pub fn new(tasks: Vec<Task>, active: Option<i64>) -> Self {
+        let selected = active
+            .and_then(|id| tasks.iter().position(|task| task.id == Some(id)))
+            .unwrap_or(0);
+        Self {
+            query: TextInput::new(),
+            matches: (0..tasks.len()).collect(),
+            tasks,
+            selected,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> PickerAction {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return PickerAction::Cancel,
+            KeyCode::Enter if self.selected_task().is_some() => return PickerAction::Choose,
+            KeyCode::Char('d') if ctrl && !self.matches.is_empty() => {
+                return PickerAction::DecideForMe
+            }
+            KeyCode::Char('n') if ctrl => return PickerAction::NoTask,
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => {
+                if self.selected + 1 < self.matches.len() {
+                    self.selected += 1;
+                }
+            }
+            _ => {
+                if self.query.handle_key(key) {
+                    self.refilter();
+                }
+            }
+        }
+        PickerAction::None
+    }
+
+    /// Tasks matching the query, best match first
+    pub fn matches(&self) -> impl Iterator<Item = &Task> {
+        self.matches.iter().map(|&index| &self.tasks[index])
+    }
+
+    pub fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    pub fn selected_task(&self) -> Option<&Task> {
+        self.matches
+            .get(self.selected)
+            .map(|&index| &self.tasks[index])
+    }
+
+    /// Matching tasks as an owned list, for picking one at random
+    pub fn matching_tasks(&self) -> Vec<Task> {
+        self.matches().cloned().collect()
+    }
+
+    fn refilter(&mut self) {
+        let query = self.query.value();
+        let mut scored: Vec<(usize, u32)> = self
+            .tasks
+            .iter()
+            .enumerate()
+            .filter_map(|(index, task)| fuzzy_score(query, &task.title).map(|score| (index, score)))
+            .collect();
+        // Stable sort keeps the manual task order for equal scores
+        scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        self.matches = scored.into_iter().map(|(index, _)| index).collect();
+        self.selected = 0;
+    }
+}
+
+/// Score `text` against a fuzzy `query`, or `None` if it doesn't match
+///
+/// Every query character must appear in order. Consecutive characters and
+/// characters at the start of a word score higher.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let query: Vec<char> = query
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let Some(&first) = query.first() else {
+        return Some(0);
+    };
+
+    // Try every place the first character occurs and keep the best run
+    text.iter()
+        .enumerate()
+        .filter(|(_, &c)| c == first)
+        .filter_map(|(start, _)| score_from(&query, &text, start))
+        .max()
+}
+
+/// Greedy match of `query` against `text` with the first character at `start`
+fn score_from(query: &[char], text: &[char], start: usize) -> Option<u32> {
+    let mut score = 0;
+    let mut position = start;
+    let mut previous: Option<usize> = None;
+
+    for &wanted in query {
+        let offset = text[position..].iter().position(|&c| c == wanted)?;
+        let index = position + offset;
+
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == index) {
+            score += 5;
+        }
+        if index == 0 || !text[index - 1].is_alphanumeric() {
+            score += 8;
+        }
+
+        previous = Some(index);
+        position = index + 1;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: i64, title: &str) -> Task {
+        let mut task = Task::new(title);
+        task.id = Some(id);
+        task
+    }
+
+    fn press(picker: &mut TaskPicker, code: KeyCode) -> PickerAction {
+        picker.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("", "Anything"), Some(0));
+        assert!(fuzzy_score("wrp", "Write report").is_some());
+        assert!(fuzzy_score("xyz", "Write report").is_none());
+        assert!(fuzzy_score("rw", "Write").is_none());
+        // Word starts and runs beat scattered letters
+        assert!(fuzzy_score("rep", "Write report") > fuzzy_score("rep", "Prepare slides"));
+    }
+
+    #[test]
+    fn test_filtering_and_selection() {
+        let mut picker = TaskPicker::new(
+            vec![
+                task(1, "Prepare slides"),
+                task(2, "Write report"),
+                task(3, "Email Sam"),
+            ],
+            Some(2),
+        );
+        assert_eq!(picker.selected_task().unwrap().id, Some(2));
+
+        for c in "rep".chars() {
+            press(&mut picker, KeyCode::Char(c));
+        }
+        let titles: Vec<&str> = picker.matches().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Write report", "Prepare slides"]);
+        assert_eq!(picker.selected_task().unwrap().id, Some(2));
+
+        press(&mut picker, KeyCode::Down);
+        assert_eq!(picker.selected_task().unwrap().id, Some(1));
+        assert_eq!(press(&mut picker, KeyCode::Enter), PickerAction::Choose);
+
+        press(&mut picker, KeyCode::Char('z'));
+        assert_eq!(picker.match_count(), 0);
+        // Nothing to choose or decide between
+        assert_eq!(press(&mut picker, KeyCode::Enter), PickerAction::None);
+        assert_eq!(
+            picker.handle_key(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+            PickerAction::None
+        );
+        assert_eq!(
+            picker.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL)),
+            PickerAction::NoTask
+        );
+    }
+}
@@ -1,14 +1,24 @@
 use crate::core::breathing::BreathPhase;
+use crate::core::{ClockGap, InboxItem, Priority, SessionMode, Task, VoidReason};
 use crate::integrations::DndState;
-use crate::tui::app::{App, AppMode};
+use crate::storage::{Completion, SessionSnapshot, Stats};
+use crate::tui::app::{
+    App, AppMode, CheckInPrompt, GuardScreen, IntentionCheck, InterruptionInput, Screen,
+};
+use crate::tui::input::TextInput;
+use crate::tui::plan_picker::PlanPicker;
+use crate::tui::task_panel::{TaskField, TaskForm};
+use crate::tui::task_picker::TaskPicker;
 use ratatui::{
     prelude::*,
     symbols,
     widgets::{
         canvas::{Canvas, Circle, Context},
-        Block, Borders, Gauge, Paragraph,
+        Bar, BarChart, BarGroup, Block, Borders, Gauge, List, ListItem, ListState, Paragraph,
+        Sparkline,
     },
 };
+use std::time::Duration;
 
 pub fn draw(frame: &mut Frame, app: &App) {
     // Check if we have a status message to display
@@ -30,7 +40,7 @@ pub fn draw(frame: &mut Frame, app: &App) {
             } else {
                 // Calculate height needed for normal messages based on content length
                 let estimated_lines = (msg.len() as u16 / available_width) + 2; // +2 for dismiss text and padding
-                estimated_lines.min(8).max(4) // Between 4-8 lines
+                estimated_lines.clamp(4, 8) // Between 4-8 lines
             }
         } else {
             4
@@ -81,28 +91,114 @@ pub fn draw(frame: &mut Frame, app: &App) {
         ""
     };
 
+    // Where we are in the session plan, if one is being followed
+    let plan_progress = app
+        .session()
+        .plan()
+        .map(|plan| {
+            let step = app.session().plan_step();
+            let label = plan
+                .phase(step)
+                .and_then(|phase| phase.label.as_ref())
+                .map(|label| format!(" ({})", label))
+                .unwrap_or_default();
+            format!(
+                " · {}, phase {} of {}{}",
+                plan.name,
+                step + 1,
+                plan.len(),
+                label
+            )
+        })
+        .unwrap_or_default();
+    let plan_progress = match app.session().deadline() {
+        Some(deadline) => format!("{} · until {}", plan_progress, deadline.format("%H:%M")),
+        None => plan_progress,
+    };
+    // Interruptions so far in this pomodoro, e.g. "2' 1-"
+    let interruptions = app.interruptions();
+    let interruptions = if interruptions.total() > 0 {
+        format!(" · {}", interruptions)
+    } else {
+        String::new()
+    };
+    let inbox = match app.inbox_due() {
+        0 => String::new(),
+        due => format!(" · 📥 {}", due),
+    };
+
     let title = match app.mode() {
-        AppMode::Pomodoro => format!(
-            "🍅 Pomodoro Timer - Session #{}{}{}",
-            app.session_count() + 1,
+        _ if app.screen() == Screen::Stats => format!(
+            "📊 {} - Last {} Days{}{}",
+            if app.stats_patterns() {
+                "Patterns"
+            } else {
+                "Statistics"
+            },
+            app.stats_days(),
             focus_indicator,
             audio_indicator
         ),
+        _ if app.screen() == Screen::Tasks => {
+            format!("📋 Tasks{}{}", focus_indicator, audio_indicator)
+        }
+        _ if app.screen() == Screen::Inbox => {
+            format!("📥 Inbox{}{}", focus_indicator, audio_indicator)
+        }
+        AppMode::WarmUp => {
+            let task = app
+                .active_task()
+                .map(|task| format!(" · {}", task.title))
+                .unwrap_or_default();
+            format!(
+                "🌱 Warm-up - just open the file{}{}{}{}",
+                task, interruptions, focus_indicator, audio_indicator
+            )
+        }
+        AppMode::Pomodoro => {
+            let task = app
+                .active_task()
+                .map(|task| format!(" · {}", task.title))
+                .unwrap_or_default();
+            let name = match app.config().mode {
+                SessionMode::Pomodoro => "🍅 Pomodoro Timer",
+                SessionMode::Flowtime => "🌊 Flowtime",
+            };
+            format!(
+                "{} - Session #{}{}{}{}{}{}{}",
+                name,
+                app.session_count() + 1,
+                plan_progress,
+                task,
+                interruptions,
+                inbox,
+                focus_indicator,
+                audio_indicator
+            )
+        }
         AppMode::Break => {
-            let break_type = if (app.session_count() % 4) == 0 {
+            let break_type = if app.is_long_break() {
                 "Long Break"
             } else {
                 "Short Break"
             };
             format!(
-                "☕ {} - After Session #{}{}{}",
+                "☕ {} - After Session #{}{}{}{}{}",
                 break_type,
                 app.session_count(),
+                plan_progress,
+                inbox,
                 focus_indicator,
                 audio_indicator
             )
         }
     };
+    // Time-warped demo runs say so, since the countdown moves impossibly fast
+    let title = if app.time_warp() != 1.0 {
+        format!("{} ⏩ {}x", title, app.time_warp())
+    } else {
+        title
+    };
 
     let title_widget = Paragraph::new(title)
         .style(
@@ -117,26 +213,67 @@ pub fn draw(frame: &mut Frame, app: &App) {
     frame.render_widget(title_widget, chunks[0]);
 
     // Main content
-    let main_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Min(3),
-        ])
-        .split(chunks[1]);
-
-    // Timer display
-    render_timer(frame, app, main_chunks[0]);
-
-    // Progress bar
-    render_progress(frame, app, main_chunks[1]);
-
-    // Breathing or status
-    if app.mode() == AppMode::Break {
-        render_breathing(frame, app, main_chunks[2]);
+    if let Some(guard) = app.guard_screen() {
+        render_guard_screen(frame, app, guard, chunks[1]);
+    } else if app.screen() == Screen::Stats {
+        render_stats(frame, app, chunks[1]);
+    } else if app.screen() == Screen::Tasks {
+        render_tasks(frame, app, chunks[1]);
+    } else if app.screen() == Screen::Inbox {
+        render_inbox(frame, app, chunks[1]);
     } else {
-        render_status(frame, app, main_chunks[2]);
+        // The intention and breadcrumb sit under the countdown while the pomodoro runs
+        let timer_height = match app.mode() {
+            AppMode::Pomodoro => {
+                3 + app.intention().is_some() as u16 + app.breadcrumb().is_some() as u16
+            }
+            _ => 3,
+        };
+        let main_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(timer_height),
+                Constraint::Length(3),
+                Constraint::Min(3),
+            ])
+            .split(chunks[1]);
+
+        // Timer display
+        render_timer(frame, app, main_chunks[0]);
+
+        // Progress bar
+        render_progress(frame, app, main_chunks[1]);
+
+        // Breathing or status
+        if let Some(snapshot) = app.resume_prompt() {
+            render_resume_prompt(frame, snapshot, main_chunks[2]);
+        } else if let Some(gap) = app.gap_prompt() {
+            render_gap_prompt(frame, app, gap, main_chunks[2]);
+        } else if let Some(input) = app.interruption_input() {
+            render_interruption_input(frame, input, main_chunks[2]);
+        } else if let Some(input) = app.inbox_input() {
+            render_inbox_input(frame, input, main_chunks[2]);
+        } else if let Some(picker) = app.plan_picker() {
+            render_plan_picker(frame, picker, main_chunks[2]);
+        } else if let Some(input) = app.deadline_input() {
+            render_deadline_input(frame, input, main_chunks[2]);
+        } else if let Some(check) = app.intention_check() {
+            render_intention_check(frame, check, main_chunks[2]);
+        } else if let Some(prompt) = app.check_in_prompt() {
+            render_check_in_prompt(frame, prompt, main_chunks[2]);
+        } else if let Some(input) = app.breadcrumb_input() {
+            render_breadcrumb_input(frame, input, main_chunks[2]);
+        } else if let Some(input) = app.intention_input() {
+            render_intention_input(frame, input, main_chunks[2]);
+        } else if app.is_void_prompt_open() {
+            render_void_prompt(frame, main_chunks[2]);
+        } else if app.mode() == AppMode::Break {
+            render_breathing(frame, app, main_chunks[2]);
+        } else if let Some(picker) = app.task_picker() {
+            render_task_picker(frame, picker, main_chunks[2]);
+        } else {
+            render_status(frame, app, main_chunks[2]);
+        }
     }
 
     // Controls
@@ -204,26 +341,58 @@ pub fn draw(frame: &mut Frame, app: &App) {
 
 fn render_timer(frame: &mut Frame, app: &App, area: Rect) {
     let timer = app.timer();
-    let remaining = timer.remaining();
-    let minutes = remaining.as_secs() / 60;
-    let seconds = remaining.as_secs() % 60;
 
-    let time_str = format!("{:02}:{:02}", minutes, seconds);
-    let color = match timer.state() {
-        crate::core::timer::TimerState::Running => Color::Green,
-        crate::core::timer::TimerState::Paused => Color::Yellow,
-        crate::core::timer::TimerState::Completed => Color::Red,
-        crate::core::timer::TimerState::Idle => Color::Gray,
+    let (time_str, color, title) = if timer.is_open_ended() {
+        // Flowtime work counts up with no end
+        (
+            format_minutes_seconds(timer.elapsed()),
+            Color::Cyan,
+            "Flowtime",
+        )
+    } else if app.session().in_overtime() {
+        // Count up past the end of the pomodoro
+        let overtime = timer.overtime();
+        (
+            format!("+{}", format_minutes_seconds(overtime)),
+            Color::Magenta,
+            "Overtime",
+        )
+    } else {
+        let color = match timer.state() {
+            crate::core::timer::TimerState::Running => Color::Green,
+            crate::core::timer::TimerState::Paused => Color::Yellow,
+            crate::core::timer::TimerState::Completed => Color::Red,
+            crate::core::timer::TimerState::Idle => Color::Gray,
+        };
+        (
+            format_minutes_seconds(timer.remaining()),
+            color,
+            "Time Remaining",
+        )
     };
 
-    let timer_widget = Paragraph::new(time_str)
-        .style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+    let mut lines = vec![Line::from(Span::styled(
+        time_str,
+        Style::default().fg(color).add_modifier(Modifier::BOLD),
+    ))];
+    if let (AppMode::Pomodoro, Some(intention)) = (app.mode(), app.intention()) {
+        lines.push(Line::from(Span::styled(
+            format!("🎯 {}", intention),
+            Style::default().add_modifier(Modifier::ITALIC),
+        )));
+    }
+    if let (AppMode::Pomodoro, Some(breadcrumb)) = (app.mode(), app.breadcrumb()) {
+        lines.push(Line::from(Span::styled(
+            format!("📌 Left off: {}", breadcrumb.note),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+
+    let timer_widget = Paragraph::new(lines)
         .alignment(Alignment::Center)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Time Remaining"),
-        )
+        .block(Block::default().borders(Borders::ALL).title(title))
         .wrap(ratatui::widgets::Wrap { trim: true });
 
     frame.render_widget(timer_widget, area);
@@ -286,6 +455,27 @@ fn render_breathing(frame: &mut Frame, app: &App, area: Rect) {
 
 fn render_status(frame: &mut Frame, app: &App, area: Rect) {
     let state = match (app.mode(), app.timer().state()) {
+        (_, crate::core::timer::TimerState::Paused) if app.timer().is_counting_up() => {
+            "Paused - Press Space or P to resume"
+        }
+        _ if app.session().in_overtime() => {
+            "Overtime - Press Space when you're ready for a break, P to pause"
+        }
+        _ if app.timer().is_counting_up() => {
+            "In the flow - Press Space to stop and take a break, P to pause"
+        }
+        (crate::tui::app::AppMode::WarmUp, crate::core::timer::TimerState::Idle) => {
+            "Starting is the hard part - a few minutes, no pressure. Press Space"
+        }
+        (crate::tui::app::AppMode::WarmUp, crate::core::timer::TimerState::Running) => {
+            "Warming up - just open the file and begin"
+        }
+        (crate::tui::app::AppMode::WarmUp, crate::core::timer::TimerState::Paused) => {
+            "Warm-up paused - Press Space to resume"
+        }
+        (crate::tui::app::AppMode::WarmUp, crate::core::timer::TimerState::Completed) => {
+            "Warm-up done! Y: roll into a full pomodoro | N: not yet"
+        }
         (crate::tui::app::AppMode::Pomodoro, crate::core::timer::TimerState::Idle) => {
             "Ready to start Pomodoro - Press Space"
         }
@@ -311,10 +501,44 @@ fn render_status(frame: &mut Frame, app: &App, area: Rect) {
             "Break complete! Press Space to start new Pomodoro"
         }
     };
-
+    let countdown = app.auto_start_remaining().map(|remaining| {
+        // A completed phase hasn't moved on yet, so what starts is the one after it
+        let kind = if app.timer().state() == crate::core::timer::TimerState::Completed {
+            app.session().upcoming_kind()
+        } else {
+            app.session().kind()
+        };
+        let next = if kind.is_break() { "Break" } else { "Pomodoro" };
+        format!(
+            "{} starts in {}s - press any key to stay put",
+            next,
+            remaining.as_secs_f64().ceil() as u64
+        )
+    });
+    let pause_clock = (app.timer().state() == crate::core::timer::TimerState::Paused).then(|| {
+        let pauses = app.session().state().pauses;
+        format!(
+            "{} | ⏸ {} this pause, {} over {} {}",
+            state,
+            format_minutes_seconds(app.timer().current_pause()),
+            format_minutes_seconds(app.timer().total_pause_duration()),
+            pauses,
+            if pauses == 1 { "pause" } else { "pauses" }
+        )
+    });
+    let state = countdown
+        .as_deref()
+        .or(pause_clock.as_deref())
+        .unwrap_or(state);
+
+    let pause_too_long = app
+        .config()
+        .max_pause()
+        .is_some_and(|limit| app.timer().current_pause() >= limit);
     let color = match app.timer().state() {
         crate::core::timer::TimerState::Idle => Color::Yellow,
         crate::core::timer::TimerState::Running => Color::Green,
+        crate::core::timer::TimerState::Paused if pause_too_long => Color::Red,
         crate::core::timer::TimerState::Paused => Color::Yellow,
         crate::core::timer::TimerState::Completed => Color::Cyan,
     };
@@ -325,7 +549,27 @@ fn render_status(frame: &mut Frame, app: &App, area: Rect) {
         .block(Block::default().borders(Borders::ALL).title("Status"))
         .wrap(ratatui::widgets::Wrap { trim: true });
 
-    frame.render_widget(status_widget, area);
+    let schedule = schedule_lines(app);
+    if schedule.is_empty() {
+        frame.render_widget(status_widget, area);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(3)])
+        .split(area);
+    frame.render_widget(status_widget, chunks[0]);
+
+    let title = app
+        .session()
+        .deadline()
+        .map(|deadline| format!("Schedule until {}", deadline.format("%H:%M")))
+        .unwrap_or_default();
+    let schedule_widget = Paragraph::new(schedule)
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).title(title));
+    frame.render_widget(schedule_widget, chunks[1]);
 }
 
 fn render_controls(frame: &mut Frame, app: &App, area: Rect) {
@@ -341,6 +585,27 @@ fn render_controls(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 fn get_responsive_controls(app: &App, width: u16) -> Vec<Line<'static>> {
+    match app.screen() {
+        Screen::Stats => return get_stats_controls(app, width),
+        Screen::Tasks => return get_tasks_controls(app, width),
+        Screen::Inbox => return get_inbox_controls(width),
+        _ if app.resume_prompt().is_some() => return get_resume_controls(width),
+        _ if app.gap_prompt().is_some() => return get_gap_controls(),
+        _ if app.guard_screen().is_some() => return get_guard_controls(app),
+        Screen::Timer if app.interruption_input().is_some() => return get_interruption_controls(),
+        Screen::Timer if app.inbox_input().is_some() => return get_inbox_input_controls(),
+        Screen::Timer if app.task_picker().is_some() => return get_picker_controls(width),
+        Screen::Timer if app.plan_picker().is_some() => return get_plan_picker_controls(),
+        Screen::Timer if app.deadline_input().is_some() => return get_deadline_controls(),
+        Screen::Timer if app.intention_check().is_some() => return get_intention_check_controls(),
+        Screen::Timer if app.check_in_prompt().is_some() => return get_check_in_controls(),
+        Screen::Timer if app.breadcrumb_input().is_some() => return get_breadcrumb_controls(),
+        Screen::Timer if app.intention_input().is_some() => return get_intention_controls(),
+        Screen::Timer if app.is_void_prompt_open() => return get_void_controls(),
+        Screen::Timer if app.warm_up_done() => return get_warm_up_controls(),
+        Screen::Timer => {}
+    }
+
     match width {
         0..=39 => get_narrow_controls(app),
         40..=79 => get_medium_controls(app),
@@ -348,10 +613,350 @@ fn get_responsive_controls(app: &App, width: u16) -> Vec<Line<'static>> {
     }
 }
 
+fn get_stats_controls(app: &App, width: u16) -> Vec<Line<'static>> {
+    let (back, range) = if width < 40 {
+        ("Back", "7/30")
+    } else {
+        ("Back to Timer", "7/30 Days")
+    };
+    let view = if app.stats_patterns() {
+        "Overview"
+    } else {
+        "Patterns"
+    };
+
+    vec![Line::from(vec![
+        Span::raw("Tab: "),
+        Span::styled("Tasks", Style::default().fg(Color::Cyan)),
+        Span::raw(" | "),
+        Span::raw("Esc: "),
+        Span::styled(back, Style::default().fg(Color::Cyan)),
+        Span::raw(" | "),
+        Span::raw("Space: "),
+        Span::styled(range, Style::default().fg(Color::Green)),
+        Span::raw(" | "),
+        Span::raw("P: "),
+        Span::styled(view, Style::default().fg(Color::Cyan)),
+        Span::raw(" | "),
+        Span::raw("Q: "),
+        Span::styled("Quit", Style::default().fg(Color::Red)),
+    ])]
+}
+
+fn get_inbox_controls(width: u16) -> Vec<Line<'static>> {
+    let (task, later, back) = if width < 60 {
+        ("Task", "Later", "Back")
+    } else {
+        ("Make Task", "Tomorrow (+1 day)", "Back to Timer")
+    };
+
+    vec![Line::from(vec![
+        Span::raw("T: "),
+        Span::styled(task, Style::default().fg(Color::Green)),
+        Span::raw(" | "),
+        Span::raw("S: "),
+        Span::styled(later, Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::raw("D: "),
+        Span::styled("Delete", Style::default().fg(Color::Red)),
+        Span::raw(" | "),
+        Span::raw("Esc: "),
+        Span::styled(back, Style::default().fg(Color::Cyan)),
+        Span::raw(" | "),
+        Span::raw("Q: "),
+        Span::styled("Quit", Style::default().fg(Color::Red)),
+    ])]
+}
+
+fn get_inbox_input_controls() -> Vec<Line<'static>> {
+    vec![Line::from(vec![
+        Span::raw("Enter: "),
+        Span::styled("Park It", Style::default().fg(Color::Green)),
+        Span::raw(" | "),
+        Span::raw("Esc: "),
+        Span::styled("Cancel", Style::default().fg(Color::Red)),
+    ])]
+}
+
+fn get_tasks_controls(app: &App, width: u16) -> Vec<Line<'static>> {
+    let key = |text: &'static str| Span::raw(text);
+    let action = |text: &'static str, color: Color| Span::styled(text, Style::default().fg(color));
+    let sep = || Span::raw(" | ");
+
+    if app.task_panel().form.is_some() {
+        return vec![Line::from(vec![
+            key("↑↓: "),
+            action("Field", Color::Cyan),
+            sep(),
+            key("←→: "),
+            action("Change", Color::Cyan),
+            sep(),
+            key("Enter: "),
+            action("Save", Color::Green),
+            sep(),
+            key("Esc: "),
+            action("Cancel", Color::Red),
+        ])];
+    }
+
+    if width < 80 {
+        return vec![Line::from(vec![
+            key("A: "),
+            action("Add", Color::Green),
+            sep(),
+            key("E: "),
+            action("Edit", Color::Cyan),
+            sep(),
+            key("C: "),
+            action("Done", Color::Green),
+            sep(),
+            key("⏎: "),
+            action("Active", Color::Yellow),
+            sep(),
+            key("Esc: "),
+            action("Back", Color::Cyan),
+            sep(),
+            key("Q: "),
+            action("Quit", Color::Red),
+        ])];
+    }
+
+    vec![
+        Line::from(vec![
+            key("A: "),
+            action("Add", Color::Green),
+            sep(),
+            key("E: "),
+            action("Edit", Color::Cyan),
+            sep(),
+            key("C: "),
+            action("Complete", Color::Green),
+            sep(),
+            key("Enter: "),
+            action("Set Active", Color::Yellow),
+            sep(),
+            key("Shift+↑↓ / K J: "),
+            action("Reorder", Color::Magenta),
+        ]),
+        Line::from(vec![
+            key("Tab: "),
+            action("Timer", Color::Cyan),
+            sep(),
+            key("Esc: "),
+            action("Back to Timer", Color::Cyan),
+            sep(),
+            key("Q: "),
+            action("Quit", Color::Red),
+        ]),
+    ]
+}
+
+fn get_resume_controls(width: u16) -> Vec<Line<'static>> {
+    let (paused, elapsed, fresh) = if width < 60 {
+        ("As Paused", "As Elapsed", "Fresh")
+    } else {
+        (
+            "Count Time Away as Paused",
+            "Count as Elapsed",
+            "Start Fresh",
+        )
+    };
+
+    vec![Line::from(vec![
+        Span::raw("P: "),
+        Span::styled(paused, Style::default().fg(Color::Green)),
+        Span::raw(" | "),
+        Span::raw("E: "),
+        Span::styled(elapsed, Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::raw("N: "),
+        Span::styled(fresh, Style::default().fg(Color::Cyan)),
+        Span::raw(" | "),
+        Span::raw("Q: "),
+        Span::styled("Quit", Style::default().fg(Color::Red)),
+    ])]
+}
+
+fn get_picker_controls(width: u16) -> Vec<Line<'static>> {
+    let (decide, no_task) = if width < 60 {
+        ("Decide", "No Task")
+    } else {
+        ("Decide for Me", "Start Without Task")
+    };
+
+    vec![Line::from(vec![
+        Span::raw("Type: "),
+        Span::styled("Filter", Style::default().fg(Color::Cyan)),
+        Span::raw(" | "),
+        Span::raw("Enter: "),
+        Span::styled("Start", Style::default().fg(Color::Green)),
+        Span::raw(" | "),
+        Span::raw("Ctrl+D: "),
+        Span::styled(decide, Style::default().fg(Color::Magenta)),
+        Span::raw(" | "),
+        Span::raw("Ctrl+N: "),
+        Span::styled(no_task, Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::raw("Esc: "),
+        Span::styled("Cancel", Style::default().fg(Color::Red)),
+    ])]
+}
+
+fn get_plan_picker_controls() -> Vec<Line<'static>> {
+    vec![Line::from(vec![
+        Span::raw("↑/↓: "),
+        Span::styled("Select", Style::default().fg(Color::Cyan)),
+        Span::raw(" | "),
+        Span::raw("Enter: "),
+        Span::styled("Switch", Style::default().fg(Color::Green)),
+        Span::raw(" | "),
+        Span::raw("Esc: "),
+        Span::styled("Cancel", Style::default().fg(Color::Red)),
+    ])]
+}
+
+fn get_deadline_controls() -> Vec<Line<'static>> {
+    vec![Line::from(vec![
+        Span::raw("Enter: "),
+        Span::styled("Set (empty clears)", Style::default().fg(Color::Green)),
+        Span::raw(" | "),
+        Span::raw("Esc: "),
+        Span::styled("Cancel", Style::default().fg(Color::Red)),
+    ])]
+}
+
+fn get_interruption_controls() -> Vec<Line<'static>> {
+    vec![Line::from(vec![
+        Span::raw("Enter: "),
+        Span::styled("Log and Keep Going", Style::default().fg(Color::Green)),
+        Span::raw(" | "),
+        Span::raw("Tab: "),
+        Span::styled("Log and Pause", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::raw("Esc: "),
+        Span::styled("Cancel", Style::default().fg(Color::Red)),
+    ])]
+}
+
+fn get_intention_controls() -> Vec<Line<'static>> {
+    vec![Line::from(vec![
+        Span::raw("Enter: "),
+        Span::styled("Start (empty skips)", Style::default().fg(Color::Green)),
+        Span::raw(" | "),
+        Span::raw("Esc: "),
+        Span::styled("Cancel", Style::default().fg(Color::Red)),
+    ])]
+}
+
+fn get_check_in_controls() -> Vec<Line<'static>> {
+    vec![Line::from(vec![
+        Span::raw("1-5: "),
+        Span::styled("Rate", Style::default().fg(Color::Green)),
+        Span::raw(" | "),
+        Span::raw("Esc: "),
+        Span::styled("Skip", Style::default().fg(Color::Gray)),
+    ])]
+}
+
+fn get_breadcrumb_controls() -> Vec<Line<'static>> {
+    vec![Line::from(vec![
+        Span::raw("Enter: "),
+        Span::styled("Save (empty skips)", Style::default().fg(Color::Green)),
+        Span::raw(" | "),
+        Span::raw("Esc: "),
+        Span::styled("Skip", Style::default().fg(Color::Red)),
+    ])]
+}
+
+fn get_intention_check_controls() -> Vec<Line<'static>> {
+    vec![Line::from(vec![
+        Span::raw("Y: "),
+        Span::styled("Yes", Style::default().fg(Color::Green)),
+        Span::raw(" | "),
+        Span::raw("P: "),
+        Span::styled("Partly", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::raw("N: "),
+        Span::styled("No", Style::default().fg(Color::Red)),
+        Span::raw(" | "),
+        Span::raw("Esc: "),
+        Span::styled("Don't Say", Style::default().fg(Color::Gray)),
+    ])]
+}
+
+fn get_void_controls() -> Vec<Line<'static>> {
+    vec![Line::from(vec![
+        Span::raw("1-4: "),
+        Span::styled("Void with Reason", Style::default().fg(Color::Red)),
+        Span::raw(" | "),
+        Span::raw("Esc: "),
+        Span::styled("Keep Going", Style::default().fg(Color::Green)),
+    ])]
+}
+
+/// Hints for changing the running phase's length and undoing mistakes
+fn phase_edit_controls() -> Vec<Span<'static>> {
+    vec![
+        Span::raw(" | "),
+        Span::raw("]/[: "),
+        Span::styled("±1m", Style::default().fg(Color::Blue)),
+        Span::raw(" | "),
+        Span::raw("}/{: "),
+        Span::styled("±5m", Style::default().fg(Color::Blue)),
+        Span::raw(" | "),
+        Span::raw("U: "),
+        Span::styled("Undo", Style::default().fg(Color::Yellow)),
+    ]
+}
+
+fn get_guard_controls(app: &App) -> Vec<Line<'static>> {
+    if app.guard_dismiss_in().is_some() {
+        return vec![Line::from(vec![
+            Span::raw("Q: "),
+            Span::styled("Quit", Style::default().fg(Color::Red)),
+        ])];
+    }
+    vec![Line::from(vec![
+        Span::raw("B/Enter: "),
+        Span::styled("Take a Break", Style::default().fg(Color::Green)),
+        Span::raw(" | "),
+        Span::raw("K/Esc: "),
+        Span::styled("Keep Going", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::raw("Q: "),
+        Span::styled("Quit", Style::default().fg(Color::Red)),
+    ])]
+}
+
+fn get_gap_controls() -> Vec<Line<'static>> {
+    vec![Line::from(vec![
+        Span::raw("W: "),
+        Span::styled("Work", Style::default().fg(Color::Green)),
+        Span::raw(" | "),
+        Span::raw("B: "),
+        Span::styled("Break", Style::default().fg(Color::Cyan)),
+        Span::raw(" | "),
+        Span::raw("N/Esc: "),
+        Span::styled("Neither", Style::default().fg(Color::Yellow)),
+    ])]
+}
+
+fn get_warm_up_controls() -> Vec<Line<'static>> {
+    vec![Line::from(vec![
+        Span::raw("Y/Enter: "),
+        Span::styled("Full Pomodoro", Style::default().fg(Color::Green)),
+        Span::raw(" | "),
+        Span::raw("N/Esc: "),
+        Span::styled("Not Yet", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::raw("Q: "),
+        Span::styled("Quit", Style::default().fg(Color::Red)),
+    ])]
+}
+
 fn get_wide_controls(app: &App) -> Vec<Line<'static>> {
     if app.mode() == AppMode::Break {
-        let is_long_break =
-            (app.session_count() % 4) == 0 && app.timer().duration().as_secs() > 5 * 60;
+        let is_long_break = app.is_long_break() && !app.break_was_shortened();
         let is_shortened_break = app.break_was_shortened();
 
         let mut first_line = vec![
@@ -381,6 +986,7 @@ fn get_wide_controls(app: &App) -> Vec<Line<'static>> {
             ]);
         }
 
+        first_line.extend(phase_edit_controls());
         first_line.extend(vec![
             Span::raw(" | "),
             Span::raw("Q/Esc: "),
@@ -414,7 +1020,7 @@ fn get_wide_controls(app: &App) -> Vec<Line<'static>> {
                 Span::raw("M: "),
                 Span::styled("Mute", Style::default().fg(Color::Magenta)),
                 Span::raw(" | "),
-                Span::raw("+/-: "),
+                Span::raw("+/_: "),
                 Span::styled("Volume", Style::default().fg(Color::Green)),
                 Span::raw(" | "),
                 Span::raw("V: "),
@@ -465,7 +1071,23 @@ fn get_wide_controls(app: &App) -> Vec<Line<'static>> {
             Span::raw(" | "),
             Span::raw("S: "),
             Span::styled("Skip to Break", Style::default().fg(Color::Cyan)),
+            Span::raw(" | "),
+            Span::raw("P: "),
+            Span::styled("Plan", Style::default().fg(Color::Magenta)),
+            Span::raw(" | "),
+            Span::raw("W: "),
+            Span::styled("Work Until", Style::default().fg(Color::Magenta)),
+            Span::raw(" | "),
+            Span::raw("'/-: "),
+            Span::styled("Interrupted", Style::default().fg(Color::Blue)),
+            Span::raw(" | "),
+            Span::raw("Shift+V: "),
+            Span::styled("Void", Style::default().fg(Color::Red)),
+            Span::raw(" | "),
+            Span::raw("I/Shift+I: "),
+            Span::styled("Park Thought/Inbox", Style::default().fg(Color::Blue)),
         ];
+        first_line.extend(phase_edit_controls());
 
         // Add Focus controls if supported
         if app.is_dnd_supported() {
@@ -501,7 +1123,7 @@ fn get_wide_controls(app: &App) -> Vec<Line<'static>> {
                 Span::raw("M: "),
                 Span::styled("Mute", Style::default().fg(Color::Magenta)),
                 Span::raw(" | "),
-                Span::raw("+/-: "),
+                Span::raw("+/_: "),
                 Span::styled("Volume", Style::default().fg(Color::Green)),
                 Span::raw(" | "),
                 Span::raw("T: "),
@@ -523,8 +1145,7 @@ fn get_wide_controls(app: &App) -> Vec<Line<'static>> {
 
 fn get_medium_controls(app: &App) -> Vec<Line<'static>> {
     if app.mode() == AppMode::Break {
-        let is_long_break =
-            (app.session_count() % 4) == 0 && app.timer().duration().as_secs() > 5 * 60;
+        let is_long_break = app.is_long_break() && !app.break_was_shortened();
         let is_shortened_break = app.break_was_shortened();
 
         let mut first_line = vec![
@@ -610,7 +1231,7 @@ fn get_medium_controls(app: &App) -> Vec<Line<'static>> {
                 Span::raw("M: "),
                 Span::styled("Mute", Style::default().fg(Color::Magenta)),
                 Span::raw(" | "),
-                Span::raw("+/-: "),
+                Span::raw("+/_: "),
                 Span::styled("Vol", Style::default().fg(Color::Green)),
                 Span::raw(" | "),
             ]);
@@ -636,8 +1257,7 @@ fn get_medium_controls(app: &App) -> Vec<Line<'static>> {
 
 fn get_narrow_controls(app: &App) -> Vec<Line<'static>> {
     if app.mode() == AppMode::Break {
-        let is_long_break =
-            (app.session_count() % 4) == 0 && app.timer().duration().as_secs() > 5 * 60;
+        let is_long_break = app.is_long_break() && !app.break_was_shortened();
         let is_shortened_break = app.break_was_shortened();
 
         let mut second_line = vec![
@@ -716,6 +1336,926 @@ fn get_narrow_controls(app: &App) -> Vec<Line<'static>> {
     }
 }
 
+fn render_stats(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(stats) = app.stats() else {
+        let unavailable = Paragraph::new(vec![
+            Line::from(""),
+            Line::from("Statistics are unavailable - the session history could not be opened."),
+        ])
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).title("Statistics"))
+        .wrap(ratatui::widgets::Wrap { trim: true });
+        frame.render_widget(unavailable, area);
+        return;
+    };
+    if app.stats_patterns() {
+        render_patterns(frame, stats, area);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(8),    // Focus minutes bar chart
+            Constraint::Length(3), // Completed pomodoros sparkline
+            Constraint::Length(9), // Summary
+            Constraint::Length(5), // Interruptions
+        ])
+        .split(area);
+
+    render_focus_chart(frame, stats, chunks[0]);
+
+    let completed: Vec<u64> = stats
+        .days
+        .iter()
+        .map(|day| day.completed_pomodoros as u64)
+        .collect();
+    let sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Completed Pomodoros"),
+        )
+        .data(&completed)
+        .style(Style::default().fg(Color::Green));
+    frame.render_widget(sparkline, chunks[1]);
+
+    let summary_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[2]);
+
+    let pomodoro_summary = Paragraph::new(vec![
+        Line::from(vec![
+            Span::raw("Completed: "),
+            Span::styled(
+                stats.completed_pomodoros.to_string(),
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("Abandoned: "),
+            Span::styled(
+                stats.abandoned_pomodoros.to_string(),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(if stats.void_reasons.is_empty() {
+            format!("Voided: {}", stats.voided_pomodoros)
+        } else {
+            let reasons: Vec<String> = stats
+                .void_reasons
+                .iter()
+                .map(|(reason, count)| format!("{} {}", reason.to_lowercase(), count))
+                .collect();
+            format!(
+                "Voided: {} ({})",
+                stats.voided_pomodoros,
+                reasons.join(", ")
+            )
+        }),
+        Line::from(format!(
+            "Total focus: {}",
+            format_hours_minutes(stats.total_focus())
+        )),
+        Line::from(format!(
+            "Average pause: {} ({} pauses)",
+            format_minutes_seconds(stats.average_pause),
+            stats.pauses
+        )),
+        Line::from(format!("Warm-ups: {}", stats.warm_ups)),
+        Line::from(format!(
+            "Snoozed: {}× (+{}m) | Cut short: {}×",
+            stats.snoozes,
+            stats.snoozed.as_secs() / 60,
+            stats.cuts
+        )),
+    ])
+    .block(Block::default().borders(Borders::ALL).title("Pomodoros"))
+    .wrap(ratatui::widgets::Wrap { trim: true });
+    frame.render_widget(pomodoro_summary, summary_chunks[0]);
+
+    let mut break_lines = vec![Line::from(format!(
+        "Taken: {} | Skipped: {} | Shortened: {} | Extended: {}",
+        stats.breaks_taken, stats.breaks_skipped, stats.breaks_shortened, stats.breaks_extended
+    ))];
+    if stats.activities.is_empty() {
+        break_lines.push(Line::from("No break activities yet"));
+    }
+    for (activity, count) in &stats.activities {
+        break_lines.push(Line::from(format!("• {}: {}", activity, count)));
+    }
+
+    let break_summary = Paragraph::new(break_lines)
+        .block(Block::default().borders(Borders::ALL).title("Breaks"))
+        .wrap(ratatui::widgets::Wrap { trim: true });
+    frame.render_widget(break_summary, summary_chunks[1]);
+
+    let top = |counts: Vec<String>| {
+        if counts.is_empty() {
+            "none yet".to_string()
+        } else {
+            counts.join(", ")
+        }
+    };
+    let hours = stats
+        .interrupted_hours
+        .iter()
+        .take(3)
+        .map(|(hour, count)| format!("{:02}:00 ({})", hour, count))
+        .collect();
+    let tasks = stats
+        .interrupted_tasks
+        .iter()
+        .take(3)
+        .map(|(task, count)| format!("{} ({})", task, count))
+        .collect();
+    let interruption_summary = Paragraph::new(vec![
+        Line::from(format!(
+            "Internal ': {} | External -: {}",
+            stats.interruptions.internal, stats.interruptions.external
+        )),
+        Line::from(format!("Most interrupted hours: {}", top(hours))),
+        Line::from(format!("Most interrupted tasks: {}", top(tasks))),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Interruptions"),
+    )
+    .wrap(ratatui::widgets::Wrap { trim: true });
+    frame.render_widget(interruption_summary, chunks[3]);
+}
+
+/// How finishing pomodoros goes with energy, mood, time of day and breaks
+fn render_patterns(frame: &mut Frame, stats: &Stats, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(5),    // Patterns worth pointing out
+            Constraint::Length(5), // By check-in and time of day
+            Constraint::Min(4),    // After each break activity
+        ])
+        .split(area);
+
+    let patterns = stats.patterns();
+    let mut pattern_lines = vec![Line::from(format!(
+        "Overall: {} completed",
+        format_completion(&stats.completion())
+    ))];
+    if patterns.is_empty() {
+        pattern_lines.push(Line::from(Span::styled(
+            "Nothing stands out yet - patterns show up after a few pomodoros in each group",
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    for pattern in patterns {
+        pattern_lines.push(Line::from(format!("• {}", pattern)));
+    }
+    let pattern_summary = Paragraph::new(pattern_lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("What Stands Out"),
+        )
+        .wrap(ratatui::widgets::Wrap { trim: true });
+    frame.render_widget(pattern_summary, chunks[0]);
+
+    let row = |label: &str, groups: Vec<String>| {
+        Line::from(if groups.is_empty() {
+            format!("{}: no check-ins yet", label)
+        } else {
+            format!("{}: {}", label, groups.join(" | "))
+        })
+    };
+    let levels = |groups: &[(u8, Completion)]| {
+        groups
+            .iter()
+            .map(|(level, completion)| format!("{} → {}", level, format_completion(completion)))
+            .collect()
+    };
+    let parts = stats
+        .completion_by_part_of_day
+        .iter()
+        .map(|(part, completion)| format!("{} {}", part, format_completion(completion)))
+        .collect::<Vec<_>>();
+    let check_in_summary = Paragraph::new(vec![
+        row("Energy", levels(&stats.completion_by_energy)),
+        row("Mood", levels(&stats.completion_by_mood)),
+        Line::from(format!("Time of day: {}", parts.join(" | "))),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Completed by Check-in and Time of Day"),
+    )
+    .wrap(ratatui::widgets::Wrap { trim: true });
+    frame.render_widget(check_in_summary, chunks[1]);
+
+    let mut break_lines = Vec::new();
+    if stats.completion_after_activity.is_empty() {
+        break_lines.push(Line::from("No pomodoros after a break activity yet"));
+    }
+    for (activity, completion) in &stats.completion_after_activity {
+        break_lines.push(Line::from(format!(
+            "• {}: {}",
+            activity,
+            format_completion(completion)
+        )));
+    }
+    let break_summary = Paragraph::new(break_lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Completed After Each Break"),
+        )
+        .wrap(ratatui::widgets::Wrap { trim: true });
+    frame.render_widget(break_summary, chunks[2]);
+}
+
+/// Completion as "80% of 10"
+fn format_completion(completion: &Completion) -> String {
+    format!(
+        "{:.0}% of {}",
+        completion.rate() * 100.0,
+        completion.started
+    )
+}
+
+fn render_tasks(frame: &mut Frame, app: &App, area: Rect) {
+    if app.storage().is_none() {
+        let unavailable = Paragraph::new(vec![
+            Line::from(""),
+            Line::from("Tasks are unavailable - the session history could not be opened."),
+        ])
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).title("Tasks"))
+        .wrap(ratatui::widgets::Wrap { trim: true });
+        frame.render_widget(unavailable, area);
+        return;
+    }
+
+    let panel = app.task_panel();
+    let (list_area, form_area) = match panel.form {
+        Some(_) => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(3), Constraint::Length(7)])
+                .split(area);
+            (chunks[0], Some(chunks[1]))
+        }
+        None => (area, None),
+    };
+
+    let active_id = app.active_task().and_then(|task| task.id);
+    let items: Vec<ListItem> = if panel.tasks.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            "No tasks yet - press 'a' to add one",
+            Style::default().fg(Color::DarkGray),
+        )))]
+    } else {
+        panel
+            .tasks
+            .iter()
+            .map(|task| ListItem::new(task_line(task, task.id.is_some() && task.id == active_id)))
+            .collect()
+    };
+
+    let mut state = ListState::default();
+    if !panel.tasks.is_empty() {
+        state.select(Some(panel.selected));
+    }
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("Tasks"))
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("› ");
+    frame.render_stateful_widget(list, list_area, &mut state);
+
+    if let (Some(form), Some(form_area)) = (panel.form.as_ref(), form_area) {
+        render_task_form(frame, form, form_area);
+    }
+}
+
+fn render_inbox(frame: &mut Frame, app: &App, area: Rect) {
+    if app.storage().is_none() {
+        let unavailable = Paragraph::new(vec![
+            Line::from(""),
+            Line::from("The inbox is unavailable - the session history could not be opened."),
+        ])
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).title("Inbox"))
+        .wrap(ratatui::widgets::Wrap { trim: true });
+        frame.render_widget(unavailable, area);
+        return;
+    }
+
+    let panel = app.inbox();
+    let today = chrono::Local::now().date_naive();
+    let items: Vec<ListItem> = if panel.items.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            "Nothing parked - press 'i' on the timer to catch a stray thought",
+            Style::default().fg(Color::DarkGray),
+        )))]
+    } else {
+        panel
+            .items
+            .iter()
+            .map(|item| ListItem::new(inbox_line(item, today)))
+            .collect()
+    };
+
+    let mut state = ListState::default();
+    if !panel.items.is_empty() {
+        state.select(Some(panel.selected));
+    }
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Inbox - {} to sort out", panel.due(today))),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("› ");
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// One inbox item, greyed out with its day while it's put off
+fn inbox_line(item: &InboxItem, today: chrono::NaiveDate) -> Line<'static> {
+    let captured = Span::styled(
+        format!("  {}", item.captured_at.format("%a %H:%M")),
+        Style::default().fg(Color::DarkGray),
+    );
+    match item.scheduled_for {
+        Some(day) if !item.is_due(today) => Line::from(vec![
+            Span::styled(item.text.clone(), Style::default().fg(Color::Gray)),
+            Span::styled(
+                format!("  → {}", day.format("%a %-d %b")),
+                Style::default().fg(Color::Yellow),
+            ),
+            captured,
+        ]),
+        _ => Line::from(vec![Span::raw(item.text.clone()), captured]),
+    }
+}
+
+fn render_resume_prompt(frame: &mut Frame, snapshot: &SessionSnapshot, area: Rect) {
+    let phase = match snapshot.kind {
+        crate::storage::PhaseKind::WarmUp => "Warm-up".to_string(),
+        crate::storage::PhaseKind::Pomodoro => format!("Pomodoro #{}", snapshot.session_count + 1),
+        crate::storage::PhaseKind::ShortBreak => "Short Break".to_string(),
+        crate::storage::PhaseKind::LongBreak => "Long Break".to_string(),
+    };
+    let remaining = snapshot.planned.saturating_sub(snapshot.elapsed);
+    let away = snapshot.closed_for(chrono::Local::now());
+
+    let prompt_widget = Paragraph::new(vec![
+        Line::from("⏯️  Unfinished session found"),
+        Line::from(""),
+        Line::from(format!(
+            "{} with {} left, {} ({})",
+            phase,
+            format_minutes_seconds(remaining),
+            snapshot.timer_state.key(),
+            snapshot.saved_at.format("%a %H:%M")
+        )),
+        Line::from(format!("You were away for {}", format_hours_minutes(away))),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "P: count the time away as paused | E: as elapsed | N: start fresh",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]),
+    ])
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Resume Session?"),
+    )
+    .wrap(ratatui::widgets::Wrap { trim: true });
+
+    frame.render_widget(prompt_widget, area);
+}
+
+fn render_guard_screen(frame: &mut Frame, app: &App, guard: &GuardScreen, area: Rect) {
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "🛑 You've focused for {} - take a real break",
+                format_hours_minutes(guard.focus)
+            ),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    if guard.skipped_breaks > 0 {
+        lines.push(Line::from(format!(
+            "That's {} break{} skipped in a row.",
+            guard.skipped_breaks,
+            if guard.skipped_breaks == 1 { "" } else { "s" }
+        )));
+    }
+    lines.push(Line::from(
+        "Stand up, drink some water, look at something far away.",
+    ));
+    lines.push(Line::from(""));
+    lines.push(match app.guard_dismiss_in() {
+        Some(wait) => Line::from(Span::styled(
+            format!("You can carry on in {}", format_minutes_seconds(wait)),
+            Style::default().fg(Color::DarkGray),
+        )),
+        None => Line::from(Span::styled(
+            "B: take the break now | K: keep going anyway",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+    });
+
+    let screen = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Hyperfocus Guard")
+                .border_style(Style::default().fg(Color::Red)),
+        )
+        .wrap(ratatui::widgets::Wrap { trim: true });
+
+    frame.render_widget(screen, area);
+}
+
+fn render_gap_prompt(frame: &mut Frame, app: &App, gap: &ClockGap, area: Rect) {
+    let phase = match app.mode() {
+        AppMode::WarmUp => "warm-up",
+        AppMode::Pomodoro => "pomodoro",
+        AppMode::Break => "break",
+    };
+
+    let prompt_widget = Paragraph::new(vec![
+        Line::from(format!(
+            "💤 The clock jumped ahead by {} ({}–{})",
+            format_hours_minutes(gap.duration()),
+            gap.started_at.format("%H:%M"),
+            gap.ended_at.format("%H:%M")
+        )),
+        Line::from(format!(
+            "The {} is paused - was that time work, a break, or neither?",
+            phase
+        )),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "W: work | B: break | N: neither",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )]),
+    ])
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Were You Away?"),
+    )
+    .wrap(ratatui::widgets::Wrap { trim: true });
+
+    frame.render_widget(prompt_widget, area);
+}
+
+/// `prefix` followed by the text of `input`, with the caret drawn at its cursor
+fn input_line<'a>(prefix: Span<'a>, input: &TextInput) -> Line<'a> {
+    let value = input.value();
+    let split = value
+        .char_indices()
+        .nth(input.cursor())
+        .map_or(value.len(), |(index, _)| index);
+    let (before, after) = value.split_at(split);
+    Line::from(vec![
+        prefix,
+        Span::raw(before.to_string()),
+        Span::raw("▏"),
+        Span::raw(after.to_string()),
+    ])
+}
+
+fn render_task_picker(frame: &mut Frame, picker: &TaskPicker, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(1)])
+        .split(area);
+
+    let query = Paragraph::new(input_line(
+        Span::styled("🔍 ", Style::default().fg(Color::Cyan)),
+        &picker.query,
+    ))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("What are you working on?")
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(query, chunks[0]);
+
+    let items: Vec<ListItem> = if picker.match_count() == 0 {
+        vec![ListItem::new(Line::from(Span::styled(
+            "No matching tasks - Ctrl+N starts without one",
+            Style::default().fg(Color::DarkGray),
+        )))]
+    } else {
+        picker
+            .matches()
+            .map(|task| ListItem::new(task_line(task, false)))
+            .collect()
+    };
+
+    let mut state = ListState::default();
+    if picker.match_count() > 0 {
+        state.select(Some(picker.selected));
+    }
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("Open Tasks"))
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("› ");
+    frame.render_stateful_widget(list, chunks[1], &mut state);
+}
+
+fn render_deadline_input(frame: &mut Frame, input: &TextInput, area: Rect) {
+    let prompt = Paragraph::new(input_line(
+        Span::styled("🎯 Work until ", Style::default().fg(Color::Cyan)),
+        input,
+    ))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Deadline (HH:MM)")
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(prompt, area);
+}
+
+fn render_interruption_input(frame: &mut Frame, input: &InterruptionInput, area: Rect) {
+    let prompt = Paragraph::new(input_line(
+        Span::styled(
+            format!("{} Note (optional): ", input.kind.mark()),
+            Style::default().fg(Color::Cyan),
+        ),
+        &input.note,
+    ))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("{} Interruption", input.kind.label()))
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(prompt, area);
+}
+
+fn render_inbox_input(frame: &mut Frame, input: &TextInput, area: Rect) {
+    let prompt = Paragraph::new(input_line(
+        Span::styled("📥 ", Style::default().fg(Color::Cyan)),
+        input,
+    ))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Park a thought - the timer keeps running")
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(prompt, area);
+}
+
+fn render_intention_input(frame: &mut Frame, input: &TextInput, area: Rect) {
+    let prompt = Paragraph::new(input_line(
+        Span::styled("🎯 ", Style::default().fg(Color::Cyan)),
+        input,
+    ))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("What will you do in this pomodoro?")
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(prompt, area);
+}
+
+fn render_check_in_prompt(frame: &mut Frame, prompt: &CheckInPrompt, area: Rect) {
+    let question = match prompt.energy {
+        None => "Energy? 1 (drained) … 5 (full of it)".to_string(),
+        Some(energy) => format!("Energy {}/5 · Mood? 1 (low) … 5 (great)", energy),
+    };
+    let title = if prompt.is_start() {
+        "Check-in Before You Start"
+    } else {
+        "Check-in - Pomodoro Done"
+    };
+    let check_in = Paragraph::new(vec![Line::from(question)])
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .wrap(ratatui::widgets::Wrap { trim: true });
+    frame.render_widget(check_in, area);
+}
+
+fn render_breadcrumb_input(frame: &mut Frame, input: &TextInput, area: Rect) {
+    let prompt = Paragraph::new(input_line(
+        Span::styled("📌 ", Style::default().fg(Color::Cyan)),
+        input,
+    ))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Where did you leave off? What's the next step?")
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(prompt, area);
+}
+
+fn render_intention_check(frame: &mut Frame, check: &IntentionCheck, area: Rect) {
+    let prompt = Paragraph::new(vec![
+        Line::from(Span::styled(
+            format!("🎯 {}", check.intention),
+            Style::default().add_modifier(Modifier::ITALIC),
+        )),
+        Line::from("Did you do it? yes / partly / no"),
+    ])
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Pomodoro Done")
+            .border_style(Style::default().fg(Color::Yellow)),
+    )
+    .wrap(ratatui::widgets::Wrap { trim: true });
+    frame.render_widget(prompt, area);
+}
+
+fn render_void_prompt(frame: &mut Frame, area: Rect) {
+    let mut spans = Vec::new();
+    for (index, reason) in VoidReason::ALL.iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::raw("  "));
+        }
+        spans.push(Span::styled(
+            format!("{}", index + 1),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw(format!(" {}", reason.display_name())));
+    }
+    let prompt = Paragraph::new(vec![
+        Line::from("Why are you throwing this pomodoro away? It won't count."),
+        Line::from(spans),
+    ])
+    .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Void Pomodoro")
+            .border_style(Style::default().fg(Color::Red)),
+    )
+    .wrap(ratatui::widgets::Wrap { trim: true });
+    frame.render_widget(prompt, area);
+}
+
+/// The phases left before the deadline, one line each
+fn schedule_lines(app: &App) -> Vec<Line<'static>> {
+    app.session()
+        .schedule()
+        .iter()
+        .enumerate()
+        .map(|(index, phase)| {
+            let (icon, name) = if phase.kind.is_break() {
+                ("☕", "Break")
+            } else {
+                ("🍅", "Pomodoro")
+            };
+            let style = if index == 0 {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            let mut spans = vec![Span::styled(
+                format!(
+                    "{}–{}  {} {} ({}m)",
+                    phase.start.format("%H:%M"),
+                    phase.end.format("%H:%M"),
+                    icon,
+                    name,
+                    (phase.duration().as_secs() + 30) / 60
+                ),
+                style,
+            )];
+            if phase.shortened {
+                spans.push(Span::styled(
+                    "  shortened",
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+fn render_plan_picker(frame: &mut Frame, picker: &PlanPicker, area: Rect) {
+    let mut items = vec![ListItem::new(Line::from(vec![
+        Span::raw("Standard cycle"),
+        Span::styled(
+            "  pomodoros and breaks",
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]))];
+    items.extend(picker.plans().iter().map(|plan| {
+        ListItem::new(Line::from(vec![
+            Span::raw(plan.name.clone()),
+            Span::styled(
+                format!("  {}", plan.summary()),
+                Style::default().fg(Color::Cyan),
+            ),
+        ]))
+    }));
+
+    let mut state = ListState::default();
+    state.select(Some(picker.selected));
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Session Plan")
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("› ");
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn task_line(task: &Task, active: bool) -> Line<'static> {
+    let mut spans = Vec::new();
+    spans.push(Span::raw(if active { "▶ " } else { "  " }));
+    spans.push(Span::raw(if task.is_done() { "[x] " } else { "[ ] " }));
+
+    let title_style = if task.is_done() {
+        Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::CROSSED_OUT)
+    } else if active {
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+    spans.push(Span::styled(task.title.clone(), title_style));
+
+    spans.push(Span::styled(
+        format!(
+            "  🍅 {}/{}",
+            task.completed_pomodoros, task.estimated_pomodoros
+        ),
+        Style::default().fg(Color::Red),
+    ));
+    spans.push(Span::styled(
+        format!("  {}", task.priority.display_name()),
+        Style::default().fg(priority_color(task.priority)),
+    ));
+    if !task.tags.is_empty() {
+        let tags: Vec<String> = task.tags.iter().map(|tag| format!("#{}", tag)).collect();
+        spans.push(Span::styled(
+            format!("  {}", tags.join(" ")),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+    Line::from(spans)
+}
+
+fn priority_color(priority: Priority) -> Color {
+    match priority {
+        Priority::Low => Color::Green,
+        Priority::Medium => Color::Yellow,
+        Priority::High => Color::Red,
+    }
+}
+
+fn render_task_form(frame: &mut Frame, form: &TaskForm, area: Rect) {
+    let lines: Vec<Line> = TaskField::ALL
+        .iter()
+        .map(|field| {
+            let input = match field {
+                TaskField::Title => Some(&form.title),
+                TaskField::Notes => Some(&form.notes),
+                TaskField::Tags => Some(&form.tags),
+                TaskField::Priority | TaskField::Estimate => None,
+            };
+            let focused = *field == form.field;
+            let label_style = if focused {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            let label = Span::styled(format!("{:>9}: ", field.label()), label_style);
+            match input {
+                Some(input) if focused => input_line(label, input),
+                Some(input) => Line::from(vec![label, Span::raw(input.value().to_string())]),
+                None => {
+                    let value = if *field == TaskField::Priority {
+                        format!("◀ {} ▶", form.priority.display_name())
+                    } else {
+                        format!("◀ {} 🍅 ▶", form.estimate)
+                    };
+                    Line::from(vec![
+                        label,
+                        Span::raw(value),
+                        Span::raw(if focused { "▏" } else { "" }),
+                    ])
+                }
+            }
+        })
+        .collect();
+
+    let title = if form.editing.is_some() {
+        "Edit Task"
+    } else {
+        "New Task"
+    };
+    let form_widget = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(form_widget, area);
+}
+
+fn render_focus_chart(frame: &mut Frame, stats: &Stats, area: Rect) {
+    let day_count = stats.days.len().max(1) as u16;
+    let inner_width = area.width.saturating_sub(2);
+    let bar_width = (inner_width / day_count).saturating_sub(1).max(1);
+
+    // Weekday names fit a week; day-of-month numbers fit a month
+    let label_format = if stats.days.len() <= 7 { "%a" } else { "%d" };
+    let bars: Vec<Bar> = stats
+        .days
+        .iter()
+        .map(|day| {
+            Bar::default()
+                .value(day.focus.as_secs() / 60)
+                .label(Line::from(day.date.format(label_format).to_string()))
+        })
+        .collect();
+
+    let chart = BarChart::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Focus Minutes per Day"),
+        )
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(bar_width)
+        .bar_gap(1)
+        .bar_style(Style::default().fg(Color::Cyan))
+        .value_style(Style::default().fg(Color::Black).bg(Color::Cyan));
+
+    frame.render_widget(chart, area);
+}
+
+fn format_hours_minutes(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    format!("{}h {:02}m", minutes / 60, minutes % 60)
+}
+
+fn format_minutes_seconds(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
 fn render_break_activity_selection(frame: &mut Frame, app: &App, area: Rect) {
     let selected = app.selected_option();
 
@@ -1046,7 +2586,7 @@ mod tests {
 
     #[test]
     fn test_responsive_controls_wide_terminal() {
-        let app = App::new().unwrap();
+        let app = App::for_test();
         let controls = get_responsive_controls(&app, 100);
 
         // Should contain all expected controls in first line
@@ -1073,7 +2613,7 @@ mod tests {
 
     #[test]
     fn test_responsive_controls_wide_terminal_break_mode() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
         app.skip_to_break(); // Switch to break mode
 
         let controls = get_responsive_controls(&app, 100);
@@ -1088,7 +2628,7 @@ mod tests {
 
     #[test]
     fn test_responsive_controls_medium_terminal() {
-        let app = App::new().unwrap();
+        let app = App::for_test();
         let controls = get_responsive_controls(&app, 50);
 
         // Medium terminal should have 2 lines for Pomodoro mode
@@ -1102,7 +2642,7 @@ mod tests {
 
     #[test]
     fn test_responsive_controls_narrow_terminal() {
-        let mut app = App::new().unwrap();
+        let mut app = App::for_test();
         app.skip_to_break(); // Switch to break mode
 
         let controls = get_responsive_controls(&app, 30);
@@ -1116,9 +2656,103 @@ mod tests {
         assert!(!control_text.contains("Space"));
     }
 
+    #[test]
+    fn test_stats_screen_controls() {
+        let mut app = App::for_test();
+        app.show_screen(Screen::Stats);
+
+        for width in [30, 60, 100] {
+            let controls = get_responsive_controls(&app, width);
+            let control_text = format!("{:?}", controls[0]);
+            assert!(control_text.contains("7/30"));
+            assert!(control_text.contains("Quit"));
+        }
+    }
+
+    #[test]
+    fn test_draw_stats_screen() {
+        let mut app = App::for_test();
+        app.skip_to_break();
+        app.show_screen(Screen::Stats);
+
+        for (width, height) in [(120, 40), (40, 20)] {
+            let mut terminal = Terminal::new(backend::TestBackend::new(width, height)).unwrap();
+            terminal.draw(|f| draw(f, &app)).unwrap();
+            let content = format!("{:?}", terminal.backend().buffer());
+            assert!(content.contains("Statistics"));
+        }
+    }
+
+    #[test]
+    fn test_draw_tasks_screen() {
+        let mut app = App::for_test();
+        app.storage()
+            .unwrap()
+            .add_task(&Task::new("Write the report"))
+            .unwrap();
+        app.show_screen(Screen::Tasks);
+
+        for (width, height) in [(120, 40), (40, 20)] {
+            let mut terminal = Terminal::new(backend::TestBackend::new(width, height)).unwrap();
+            terminal.draw(|f| draw(f, &app)).unwrap();
+            let content = format!("{:?}", terminal.backend().buffer());
+            assert!(content.contains("Write the"));
+            assert!(content.contains("Add"));
+        }
+    }
+
+    #[test]
+    fn test_draw_task_picker_and_active_task_title() {
+        let mut app = App::for_test();
+        app.storage()
+            .unwrap()
+            .add_task(&Task::new("Write the report"))
+            .unwrap();
+        app.handle_key(crossterm::event::KeyEvent::new(
+            crossterm::event::KeyCode::Char(' '),
+            crossterm::event::KeyModifiers::NONE,
+        ));
+        assert!(app.task_picker().is_some());
+
+        let mut terminal = Terminal::new(backend::TestBackend::new(120, 40)).unwrap();
+        terminal.draw(|f| draw(f, &app)).unwrap();
+        let content = format!("{:?}", terminal.backend().buffer());
+        assert!(content.contains("What are you working on?"));
+        assert!(content.contains("Decide for Me"));
+
+        app.handle_key(crossterm::event::KeyEvent::new(
+            crossterm::event::KeyCode::Enter,
+            crossterm::event::KeyModifiers::NONE,
+        ));
+        terminal.draw(|f| draw(f, &app)).unwrap();
+        let content = format!("{:?}", terminal.backend().buffer());
+        assert!(content.contains("Session #1 · Write the report"));
+    }
+
+    #[test]
+    fn test_resume_prompt_controls() {
+        let mut app = App::for_test();
+        app.skip_to_break();
+        let snapshot = app.snapshot();
+        let mut app = App::for_test();
+        app.set_resume_prompt(snapshot);
+
+        for width in [30, 60, 100] {
+            let control_text = format!("{:?}", get_responsive_controls(&app, width)[0]);
+            assert!(control_text.contains("Elapsed"));
+            assert!(control_text.contains("Fresh"));
+        }
+
+        let mut terminal = Terminal::new(backend::TestBackend::new(120, 40)).unwrap();
+        terminal.draw(|f| draw(f, &app)).unwrap();
+        let content = format!("{:?}", terminal.backend().buffer());
+        assert!(content.contains("Resume Session?"));
+        assert!(content.contains("Short Break with 05:00 left"));
+    }
+
     #[test]
     fn test_responsive_controls_edge_cases() {
-        let app = App::new().unwrap();
+        let app = App::for_test();
 
         // Test that we're actually using different control formats
         let narrow_controls = get_responsive_controls(&app, 39);
@@ -1134,7 +2768,7 @@ mod tests {
         assert!(wide_text.contains("Space"));
 
         // Test with break mode for clearer differences
-        let mut break_app = App::new().unwrap();
+        let mut break_app = App::for_test();
         break_app.skip_to_break();
 
         let narrow_break = get_responsive_controls(&break_app, 39);
@@ -1144,4 +2778,22 @@ mod tests {
         assert_eq!(narrow_break.len(), 3); // Most lines for narrow
         assert_eq!(wide_break.len(), 2); // Fewer lines for wide
     }
+
+    #[test]
+    fn test_input_line_draws_caret_at_cursor() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let mut input = TextInput::with_value("café au lait");
+        for _ in 0..8 {
+            input.handle_key(KeyEvent::new(KeyCode::Left, KeyModifiers::NONE));
+        }
+
+        let line = input_line(Span::raw("> "), &input);
+        let text: Vec<&str> = line
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(text, ["> ", "café", "▏", " au lait"]);
+    }
 }